@@ -4,8 +4,14 @@ set -e
 
 for hurl_file in "$@"; do
     echo "$hurl_file";
+    # optional command line options
+    OPTIONS=""
+    if [ -f "${hurl_file%.*}.options" ]; then
+        OPTIONS=$(cat "${hurl_file%.*}.options")
+    fi
     set +e
-    hurl "$hurl_file" --color 2>/tmp/test.stderr >/tmp/test.stdout
+    # shellcheck disable=SC2086
+    hurl "$hurl_file" --color $OPTIONS 2>/tmp/test.stderr >/tmp/test.stdout
     EXITCODE_ACTUAL=$?
     set -e
 
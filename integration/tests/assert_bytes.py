@@ -0,0 +1,9 @@
+from tests import app
+from flask import make_response
+
+
+@app.route('/assert-bytes')
+def assert_bytes():
+    resp = make_response(b'Hello World!')
+    resp.content_type = 'application/octet-stream'
+    return resp
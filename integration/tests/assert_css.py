@@ -0,0 +1,23 @@
+from tests import app
+
+
+@app.route("/assert-css")
+def assert_css():
+    return '''<html>
+  <body>
+    <div class="alert success">
+      <p>Saved</p>
+    </div>
+    <ul class="fruits">
+      <li>Apple</li>
+      <li>Banana</li>
+      <li>Cherry</li>
+    </ul>
+    <a class="next" href="/page/2">Next</a>
+  </body>
+</html>'''
+
+
+@app.route("/page/2")
+def assert_css_page2():
+    return ''
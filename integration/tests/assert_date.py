@@ -0,0 +1,12 @@
+from tests import app
+from flask import Response
+from email.utils import formatdate
+import time
+
+@app.route("/assert-date")
+def assert_date():
+    headers = {
+        'Expires': formatdate(time.time() + 3600, usegmt=True),
+        'Last-Modified': 'Wed, 21 Oct 2015 07:28:00 GMT',
+    }
+    return Response('{"created": "2015-10-21T07:28:00Z", "birthday": "21/10/2015", "name": "Bob"}', headers=headers, mimetype='application/json')
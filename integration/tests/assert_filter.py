@@ -0,0 +1,11 @@
+# coding=utf-8
+from tests import app
+from flask import make_response
+
+
+@app.route("/assert-filter")
+def assert_filter():
+    resp = make_response('{"count": "12", "token": "eyJzdWIiOiJib2IiLCJhZG1pbiI6dHJ1ZX0", "ids": [4, 5, 6], "date": "2020-01-30"}')
+    resp.headers['Content-Type'] = 'application/json'
+    resp.headers['X-Ids'] = '1,2,3'
+    return resp
@@ -0,0 +1,6 @@
+from tests import app
+from flask import Response
+
+@app.route("/assert-float")
+def assert_float():
+    return Response('{"price": 1.6, "discount": -0.5, "count": 2, "total": 0.30000000000000004, "prices": [1.5, -1.25]}', mimetype='application/json')
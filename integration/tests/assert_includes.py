@@ -0,0 +1,6 @@
+from tests import app
+from flask import Response
+
+@app.route("/assert-includes")
+def assert_includes():
+    return Response('{"tags": ["beta", "stable", "lts"], "ids": [3, 1, 2], "users": [{"name": "bob"}, {"name": "alice"}]}', mimetype='application/json')
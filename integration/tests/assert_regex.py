@@ -1,8 +1,16 @@
 # coding=utf-8
 from tests import app
+from flask import request, make_response
 
 
 @app.route("/assert-regex")
 def assert_regex():
-    return 'Hello World!'
+    resp = make_response('Hello World!\n<input type="hidden" name="csrf_token" value="3f9a1c">')
+    resp.headers['X-Version'] = 'v2.7'
+    return resp
 
+
+@app.route("/assert-regex-csrf", methods=['POST'])
+def assert_regex_csrf():
+    assert request.form['csrf_token'] == '3f9a1c'
+    return ''
@@ -0,0 +1,6 @@
+from tests import app
+from flask import request
+
+@app.route("/assert-request", methods=['POST'])
+def assert_request():
+    return request.args.get('name')
@@ -0,0 +1,6 @@
+from tests import app
+from flask import Response
+
+@app.route("/assert-schema")
+def assert_schema():
+    return Response('{"users": [{"id": 1, "name": "Bob", "role": "admin"}, {"id": 2, "name": "Alice"}]}', mimetype='application/json')
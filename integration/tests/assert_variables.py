@@ -0,0 +1,7 @@
+from tests import app
+from flask import Response
+
+@app.route("/assert-variables")
+def assert_variables():
+    headers = {'X-Pattern': '^[a-z]+$', 'X-Name': 'hurl'}
+    return Response('{"count": 2, "price": 1.25, "active": true, "tags": ["a", "b"], "name": "hurl"}', mimetype='application/json', headers=headers)
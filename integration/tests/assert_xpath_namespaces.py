@@ -0,0 +1,16 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-xpath-namespaces")
+def assert_xpath_namespaces():
+    body = '''<?xml version="1.0" encoding="utf-8"?>
+<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope">
+  <soap:Body>
+    <m:GetStockPriceResponse xmlns:m="https://www.example.org/stock">
+      <m:Price>34.5</m:Price>
+    </m:GetStockPriceResponse>
+  </soap:Body>
+</soap:Envelope>
+'''
+    return Response(body, mimetype='application/xml')
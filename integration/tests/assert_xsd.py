@@ -0,0 +1,11 @@
+from tests import app
+from flask import Response
+
+@app.route("/assert-xsd")
+def assert_xsd():
+    return Response('''<?xml version="1.0" encoding="utf-8"?>
+<food>
+  <fruit name="banana" price="1.1"/>
+  <fruit name="apple" price="2"/>
+</food>
+''', mimetype='application/xml')
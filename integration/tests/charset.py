@@ -0,0 +1,28 @@
+from tests import app
+from flask import Response
+
+@app.route("/charset/latin1")
+def charset_latin1():
+    return Response('café'.encode('iso-8859-1'), headers={'Content-Type': 'text/plain; Charset="ISO-8859-1"'})
+
+@app.route("/charset/windows1252")
+def charset_windows1252():
+    return Response('5 €'.encode('cp1252'), headers={'Content-Type': 'text/plain; charset=windows-1252'})
+
+@app.route("/charset/utf16")
+def charset_utf16():
+    return Response('café'.encode('utf-16'), headers={'Content-Type': 'text/plain; charset=UTF-16'})
+
+@app.route("/charset/xml")
+def charset_xml():
+    body = '<?xml version="1.0" encoding="ISO-8859-1"?>\n<menu><item>café</item></menu>'
+    return Response(body.encode('iso-8859-1'), headers={'Content-Type': 'application/xml'})
+
+@app.route("/charset/html")
+def charset_html():
+    body = '<html><head><meta charset="windows-1252"></head><body><p class="price">5 €</p></body></html>'
+    return Response(body.encode('cp1252'), headers={'Content-Type': 'text/html'})
+
+@app.route("/charset/json")
+def charset_json():
+    return Response('{"name": "café"}'.encode('utf-8'), headers={'Content-Type': 'application/json'})
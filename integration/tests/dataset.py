@@ -0,0 +1,9 @@
+from tests import app
+from flask import request
+
+USERS = {'bob': 'acme', 'alice': 'globex'}
+
+@app.route('/dataset/users/<user>')
+def dataset_user(user):
+    assert USERS[user] == request.args.get('tenant')
+    return 'Hello {}'.format(user)
@@ -0,0 +1,6 @@
+from tests import app
+from flask import Response
+
+@app.route("/error-assert-json-diff")
+def error_assert_json_diff():
+    return Response('{"user": {"id": 1, "tags": ["a", "c"], "admin": true}}', mimetype='application/json')
@@ -0,0 +1,6 @@
+from tests import app
+from flask import Response
+
+@app.route("/error-assert-schema")
+def error_assert_schema():
+    return Response('{"users": [{"id": 0, "name": "bob", "role": "guest"}, {"name": "Alice", "email": "alice@example.com"}]}', mimetype='application/json')
@@ -0,0 +1,12 @@
+from tests import app
+from flask import Response
+
+@app.route("/error-assert-xsd")
+def error_assert_xsd():
+    return Response('''<?xml version="1.0" encoding="utf-8"?>
+<food>
+  <fruit name="banana" price="cheap"/>
+  <fruit price="2"/>
+  <vegetable name="carrot"/>
+</food>
+''', mimetype='application/xml')
@@ -0,0 +1,15 @@
+from tests import app
+from flask import request
+
+@app.route('/include/setup')
+def include_setup():
+    return 'ok'
+
+@app.route('/include/login')
+def include_login():
+    return '{"token": "abc123"}'
+
+@app.route('/include/protected')
+def include_protected():
+    assert request.headers['Authorization'] == 'Bearer abc123'
+    return 'Welcome'
@@ -0,0 +1,16 @@
+from tests import app
+from flask import redirect, request
+
+@app.route('/options/redirect')
+def options_redirect():
+    return redirect('http://localhost:8000/options/target')
+
+@app.route('/options/target')
+def options_target():
+    return 'Redirected'
+
+@app.route('/options/variable')
+def options_variable():
+    assert request.args.get('user') == 'bob'
+    assert request.args.get('greeting') == 'Hello bob'
+    return 'Hello bob'
@@ -0,0 +1,7 @@
+from tests import app
+from flask import Response
+
+@app.route("/response-headers")
+def response_headers():
+    headers = [('X-Fruit', 'Apple'), ('X-Fruit', 'Banana'), ('X-Version', '1.2.3')]
+    return Response('Hello World!', headers=headers)
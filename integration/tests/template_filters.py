@@ -0,0 +1,16 @@
+from tests import app
+from flask import request, Response
+import json
+
+@app.route("/template-filters/data")
+def template_filters_data():
+    return Response('{"name": "bob", "query": "café & co", "ids": [1, 2]}', mimetype='application/json')
+
+@app.route("/template-filters", methods=['POST'])
+def template_filters():
+    assert request.query_string == b'q=caf%C3%A9%20%26%20co&lang=en'
+    assert request.headers['Authorization'] == 'Basic Ym9iOnNlY3JldA=='
+    assert request.headers['X-Name'] == 'BOB'
+    assert request.headers['X-Ids'] == '[1,2]'
+    assert request.headers['X-Name-Json'] == '"bob"'
+    return Response(request.data, mimetype='application/json')
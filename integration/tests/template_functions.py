@@ -0,0 +1,17 @@
+from tests import app
+from flask import request, Response
+import datetime
+import json
+import os
+import re
+
+@app.route("/template-functions", methods=['POST'])
+def template_functions():
+    assert re.match(r'^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$', request.headers['X-Request-Id'])
+    assert request.headers['X-Year'] == datetime.datetime.utcnow().strftime('%Y')
+    assert request.headers['X-Home'] == os.environ['HOME']
+    body = json.loads(request.data)
+    assert 1 <= body['count'] <= 100
+    assert body['id'] != request.headers['X-Request-Id']
+    assert re.match(r'^\d{4}-\d{2}-\d{2}T', body['created'])
+    return Response(request.data, mimetype='application/json')
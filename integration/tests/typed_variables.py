@@ -0,0 +1,10 @@
+from tests import app
+from flask import request, Response
+
+@app.route("/typed-variables")
+def typed_variables():
+    return Response('{"user": {"id": 1, "roles": ["admin", "dev"]}, "ids": [1, 2, 3], "price": 10.50, "name": "Bob \\"The\\" Builder"}', mimetype='application/json')
+
+@app.route("/typed-variables/echo", methods=['POST'])
+def typed_variables_echo():
+    return Response(request.data, mimetype='application/json')
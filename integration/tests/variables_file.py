@@ -0,0 +1,6 @@
+from tests import app
+from flask import request
+
+@app.route("/variables-file")
+def variables_file():
+    return request.args.get('greeting') + ' ' + request.args.get('name')
@@ -1,14 +1,18 @@
 //use hurl::parser::error::ParseError;
 use std;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fs;
 use std::io::{self, Read};
 use std::io::prelude::*;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Instant;
 
 use atty::Stream;
 
-use hurl::core::core::FormatError;
+use hurl::core::core::{FormatError, Value};
 use hurl::parser;
 use hurl::runner;
 use hurl::runner::core::*;
@@ -18,24 +22,33 @@ fn execute(filename: &str,
            verbose: bool,
            insecure: bool,
            fail_fast: bool,
+           from_entry: Option<usize>,
+           to_entry: Option<usize>,
            output_color: bool,
            noproxy_hosts: Vec<String>,
-           variables: &HashMap<String, String>,
+           variables: &HashMap<String, Value>,
+           dataset: &Option<Vec<HashMap<String, Value>>>,
+           namespaces: &HashMap<String, String>,
            current_dir: &Path,
            file_root: Option<String>,
-) -> HurlResult {
+) -> FileResult {
     let contents = if filename == "-" {
         let mut contents = String::new();
-        io::stdin()
-            .read_to_string(&mut contents)
-            .expect("Something went wrong reading standard input");
-        contents
+        match io::stdin().read_to_string(&mut contents) {
+            Ok(_) => contents,
+            Err(_) => {
+                runner::output::eprintln("Standard input can not be read!");
+                return Err(FileError::Read);
+            }
+        }
     } else {
-        if !Path::new(filename).exists() {
-            eprintln!("Input file {} does not exit!", filename);
-            std::process::exit(1);
+        match fs::read_to_string(filename) {
+            Ok(contents) => contents,
+            Err(_) => {
+                runner::output::eprintln(format!("Input file {} can not be read!", filename).as_str());
+                return Err(FileError::Read);
+            }
         }
-        fs::read_to_string(filename).expect("Something went wrong reading the file")
     };
 
     let mut lines: Vec<&str> = regex::Regex::new(r"\n|\r\n")
@@ -45,7 +58,6 @@ fn execute(filename: &str,
     // edd an empty line at the end?
     lines.push("");
 
-    let mut cookiejar = http::cookie::CookieJar::init();
     let mut parser = parser::core::Parser::init(contents.as_str());
     match parser::parser::hurl_file(&mut parser) {
         Err(e) => {
@@ -59,22 +71,25 @@ fn execute(filename: &str,
                 filename: filename.to_string(),
                 warning: false,
                 color: output_color,
+                includes: vec![],
             };
-            eprintln!("{}", error.format());
-            println!("{} - parsing error", filename);
-            std::process::exit(1);
+            runner::output::eprintln(error.format().as_str());
+            return Err(FileError::Parsing);
         }
         Ok(hurl_file) => {
             if verbose {
-                eprintln!("[DEBUG] no proxy for {:?}", noproxy_hosts);
-                eprintln!("[DEBUG] Fail fast: {}", fail_fast);
-                eprintln!("[DEBUG] variables: {:?}", variables);
+                runner::output::eprintln(format!("[DEBUG] no proxy for {:?}", noproxy_hosts).as_str());
+                runner::output::eprintln(format!("[DEBUG] Fail fast: {}", fail_fast).as_str());
+                runner::output::eprintln(format!("[DEBUG] Entries: {:?}..{:?}", from_entry, to_entry).as_str());
+                runner::output::eprintln(format!("[DEBUG] variables: {:?}", variables).as_str());
+                runner::output::eprintln(format!("[DEBUG] namespaces: {:?}", namespaces).as_str());
             }
 
-            let client = http::client::Client::init(http::client::ClientOptions {
+            let client_options = http::client::ClientOptions {
                 noproxy_hosts,
                 insecure,
-            });
+                follow_location: false,
+            };
 
             let context_dir = match file_root {
                 None => {
@@ -99,22 +114,155 @@ fn execute(filename: &str,
 //                parent.unwrap().to_str().unwrap()
 //            };
             let lines: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
-            let hurl_result = runner::runner::run(client,
-                                                  hurl_file,
-                                                  fail_fast,
-                                                  &variables,
-                                                  verbose,
-                                                  context_dir,
-                                                  filename.to_string(),
-                                                  output_color,
-                                                  lines.clone(),
-                                                  &mut cookiejar
-            );
-            return hurl_result;
+
+            // the file is run once per dataset row, with its own cookies
+            // the row values take precedence over the other variables
+            let iterations = match dataset {
+                None => vec![(None, variables.clone())],
+                Some(rows) => rows.iter().enumerate().map(|(index, row)| {
+                    let mut row_variables = variables.clone();
+                    row_variables.extend(row.clone());
+                    (Some(index + 1), row_variables)
+                }).collect()
+            };
+            let mut hurl_results = vec![];
+            for (dataset_row, variables) in iterations {
+                if verbose {
+                    if let Some(row) = dataset_row {
+                        runner::output::eprintln(format!("[DEBUG] dataset row {}: {:?}", row, variables).as_str());
+                    }
+                }
+                let client = http::client::Client::init(client_options.clone());
+                let mut cookiejar = http::cookie::CookieJar::init();
+                let mut hurl_result = runner::runner::run(client,
+                                                          hurl_file.clone(),
+                                                          fail_fast,
+                                                          from_entry,
+                                                          to_entry,
+                                                          &variables,
+                                                          namespaces,
+                                                          verbose,
+                                                          context_dir.clone(),
+                                                          filename.to_string(),
+                                                          output_color,
+                                                          lines.clone(),
+                                                          &mut cookiejar
+                );
+                hurl_result.dataset_row = dataset_row;
+                hurl_results.push(hurl_result);
+            }
+            return Ok(hurl_results);
         }
     }
 }
 
+// the results of a file (one per dataset row)
+type FileResult = Result<Vec<HurlResult>, FileError>;
+
+// the file has not been run to completion
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FileError {
+    Read,
+    Parsing,
+    // the job running the file has panicked
+    Interrupted,
+}
+
+// a panic only interrupts the file being run
+fn execute_file_safely(filename: &str, execute_file: &dyn Fn(&str) -> FileResult) -> FileResult {
+    return match panic::catch_unwind(AssertUnwindSafe(|| execute_file(filename))) {
+        Ok(result) => result,
+        Err(_) => Err(FileError::Interrupted),
+    };
+}
+
+// the files are shared between the jobs
+// their messages and results are written in the order of the files, as in a sequential run
+fn execute_parallel(filenames: Vec<String>,
+                    jobs: usize,
+                    execute_file: Arc<dyn Fn(&str) -> FileResult + Send + Sync>,
+) -> Vec<(String, FileResult)> {
+    let queue: VecDeque<(usize, String)> = filenames.clone().into_iter().enumerate().collect();
+    let queue = Arc::new(Mutex::new(queue));
+    let (sender, receiver) = mpsc::channel();
+    let mut workers = vec![];
+    for _ in 0..jobs {
+        let queue = queue.clone();
+        let sender = sender.clone();
+        let execute_file = execute_file.clone();
+        workers.push(thread::spawn(move || {
+            runner::output::start_buffering();
+            loop {
+                let next = queue.lock().unwrap().pop_front();
+                match next {
+                    None => break,
+                    Some((index, filename)) => {
+                        let result = execute_file_safely(filename.as_str(), execute_file.as_ref());
+                        let messages = runner::output::take_buffer();
+                        if sender.send((index, messages, (filename, result))).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }));
+    }
+    drop(sender);
+
+    let mut file_results = vec![];
+    let mut pending = BTreeMap::new();
+    let mut next_index = 0;
+    for (index, messages, file_result) in receiver {
+        pending.insert(index, (messages, file_result));
+        while let Some((messages, file_result)) = pending.remove(&next_index) {
+            eprint!("{}", messages);
+            file_results.push(file_result);
+            next_index += 1;
+        }
+    }
+    for worker in workers {
+        // the results of a failed job are missing
+        let _ = worker.join();
+    }
+    for (index, filename) in filenames.into_iter().enumerate().skip(next_index) {
+        match pending.remove(&index) {
+            Some((messages, file_result)) => {
+                eprint!("{}", messages);
+                file_results.push(file_result);
+            }
+            None => file_results.push((filename, Err(FileError::Interrupted))),
+        }
+    }
+    return file_results;
+}
+
+// NAME=VALUE
+fn parse_variable(s: &str) -> (String, String) {
+    return match s.find('=') {
+        None => {
+            eprintln!("Missing variable value for {}!", s);
+            std::process::exit(1);
+        }
+        Some(index) => {
+            let (name, value) = s.split_at(index);
+            (name.to_string(), value[1..].to_string())
+        }
+    };
+}
+
+fn parse_entry_number(matches: &clap::ArgMatches, name: &str, option: &str) -> Option<usize> {
+    return match matches.value_of(name) {
+        None => None,
+        Some(value) => match value.parse::<usize>() {
+            Ok(n) if n > 0 => Some(n),
+            _ => {
+                eprintln!("Invalid value {} for {}, expecting an entry number starting at 1!", value, option);
+                std::process::exit(1);
+            }
+        }
+    };
+}
+
 fn main() {
     let app = clap::App::new("hurl")
         .author(clap::crate_authors!())
@@ -177,11 +325,30 @@ fn main() {
             .help("set root filesystem to import file in hurl (default is curent directory)")
             .takes_value(true)
         )
+        .arg(clap::Arg::with_name("jobs")
+            .long("jobs")
+            .short("j")
+            .value_name("NUM")
+            .help("Run NUM files in parallel (default 1)")
+            .takes_value(true)
+        )
         .arg(clap::Arg::with_name("fail_at_end")
             .long("fail-at-end")
             .help("Fail at end")
             .takes_value(false)
         )
+        .arg(clap::Arg::with_name("from_entry")
+            .long("from-entry")
+            .value_name("NUM")
+            .help("Execute hurl file from entry NUM (starting at 1)")
+            .takes_value(true)
+        )
+        .arg(clap::Arg::with_name("to_entry")
+            .long("to-entry")
+            .value_name("NUM")
+            .help("Execute hurl file to entry NUM (starting at 1)")
+            .takes_value(true)
+        )
         .arg(clap::Arg::with_name("variable")
             .long("variable")
             .short("x")
@@ -190,6 +357,26 @@ fn main() {
             .number_of_values(1)
             .help("Define a variable")
             .takes_value(true)
+        )
+        .arg(clap::Arg::with_name("variables_file")
+            .long("variables-file")
+            .value_name("FILE")
+            .help("Define variables from a properties file (NAME=VALUE per line)")
+            .takes_value(true)
+        )
+        .arg(clap::Arg::with_name("dataset")
+            .long("dataset")
+            .value_name("FILE")
+            .help("Run each file once per row of a dataset (csv with a header row or json array of objects)")
+            .takes_value(true)
+        )
+        .arg(clap::Arg::with_name("namespace")
+            .long("namespace")
+            .value_name("PREFIX=URI")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Define a namespace prefix for xpath queries")
+            .takes_value(true)
         );
 
     let matches = app.clone().get_matches();
@@ -221,22 +408,106 @@ fn main() {
         filenames.push("-");
     }
 
+    for filename in filenames.clone() {
+        if filename != "-" && !Path::new(filename).exists() {
+            eprintln!("Input file {} does not exit!", filename);
+            std::process::exit(1);
+        }
+    }
+
 
+    // variables are defined (by increasing precedence)
+    // - in the environment, with the prefix HURL_
+    // - in the variables file
+    // - with the --variable option
     let mut variables = HashMap::new();
+    for (name, value) in std::env::vars() {
+        if name.starts_with("HURL_") && name.len() > "HURL_".len() {
+            variables.insert(name["HURL_".len()..].to_string(), Value::String(value));
+        }
+    }
+
+    if let Some(filename) = matches.value_of("variables_file") {
+        let contents = match fs::read_to_string(filename) {
+            Ok(contents) => contents,
+            Err(why) => {
+                eprintln!("Issue reading from {}: {:?}", filename, why);
+                std::process::exit(1);
+            }
+        };
+        let mut file_variables = HashMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // spaces around the separator are ignored (properties format)
+            let (name, value) = parse_variable(line);
+            let name = name.trim().to_string();
+            if file_variables.contains_key(&name) {
+                eprintln!("Variable {} defined twice in {}!", name, filename);
+                std::process::exit(1);
+            }
+            file_variables.insert(name, Value::String(value.trim().to_string()));
+        }
+        variables.extend(file_variables);
+    }
+
     if matches.is_present("variable") {
         let input: Vec<_> = matches.values_of("variable").unwrap().collect();
+        let mut option_variables = HashMap::new();
+        for s in input {
+            let (name, value) = parse_variable(s);
+            if option_variables.contains_key(&name) {
+                eprintln!("Variable {} defined twice!", name);
+                std::process::exit(1);
+            }
+            option_variables.insert(name, Value::String(value));
+        }
+        variables.extend(option_variables);
+    }
+
+    let dataset = match matches.value_of("dataset") {
+        None => None,
+        Some(filename) => {
+            let contents = match fs::read_to_string(filename) {
+                Ok(contents) => contents,
+                Err(why) => {
+                    eprintln!("Issue reading from {}: {:?}", filename, why);
+                    std::process::exit(1);
+                }
+            };
+            let rows = if filename.ends_with(".json") {
+                runner::dataset::parse_json(contents.as_str())
+            } else {
+                runner::dataset::parse_csv(contents.as_str())
+            };
+            match rows {
+                Ok(rows) => Some(rows),
+                Err(message) => {
+                    eprintln!("Invalid dataset {}: {}", filename, message);
+                    std::process::exit(1);
+                }
+            }
+        }
+    };
+
+    let mut namespaces = HashMap::new();
+    if matches.is_present("namespace") {
+        let input: Vec<_> = matches.values_of("namespace").unwrap().collect();
         for s in input {
             match s.find('=') {
                 None => {
-                    eprintln!("Missing variable value for {}!", s);
+                    eprintln!("Missing namespace uri for {}!", s);
                     std::process::exit(1);
                 }
                 Some(index) => {
-                    let (name, value) = s.split_at(index);
-                    if variables.contains_key(name) {
+                    let (prefix, uri) = s.split_at(index);
+                    if namespaces.contains_key(prefix) {
+                        eprintln!("Namespace {} defined twice!", prefix);
                         std::process::exit(1);
                     }
-                    variables.insert(name.to_string(), value[1..].to_string());
+                    namespaces.insert(prefix.to_string(), uri[1..].to_string());
                 }
             };
         }
@@ -253,6 +524,14 @@ fn main() {
     let verbose = matches.is_present("verbose");
     let insecure = matches.is_present("insecure");
     let fail_fast = !matches.is_present("fail_at_end");
+    let from_entry = parse_entry_number(&matches, "from_entry", "--from-entry");
+    let to_entry = parse_entry_number(&matches, "to_entry", "--to-entry");
+    if let (Some(from_entry), Some(to_entry)) = (from_entry, to_entry) {
+        if from_entry > to_entry {
+            eprintln!("--from-entry {} is greater than --to-entry {}!", from_entry, to_entry);
+            std::process::exit(1);
+        }
+    }
     let noproxy_hosts: Vec<String> = match matches.value_of("noproxy") {
         Some(value) => {
             value.split(",").map(|e| e.trim().to_string()).collect()
@@ -260,21 +539,58 @@ fn main() {
         _ => vec![]
     };
 
-    let mut hurl_results = vec![];
-    for filename in filenames {
-        let hurl_result = execute(
-            filename,
-            verbose,
-            insecure,
-            fail_fast,
-            output_color,
-            noproxy_hosts.clone(),
-            &variables,
-            current_dir,
-            file_root.clone(),
-        );
-        hurl_results.push(hurl_result.clone());
-    }
+    let jobs = match matches.value_of("jobs") {
+        None => 1,
+        Some(value) => match value.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                eprintln!("Invalid value {} for --jobs, expecting a number of jobs greater than 0!", value);
+                std::process::exit(1);
+            }
+        }
+    };
+
+    let with_dataset = dataset.is_some();
+
+    // each file is run with its own cookies and variables
+    let current_dir = current_dir.to_path_buf();
+    let execute_file = move |filename: &str| execute(
+        filename,
+        verbose,
+        insecure,
+        fail_fast,
+        from_entry,
+        to_entry,
+        output_color,
+        noproxy_hosts.clone(),
+        &variables,
+        &dataset,
+        &namespaces,
+        current_dir.as_path(),
+        file_root.clone(),
+    );
+
+    let start = Instant::now();
+    let file_results = if jobs == 1 || filenames.len() == 1 {
+        let mut file_results = vec![];
+        for filename in filenames {
+            file_results.push((filename.to_string(), execute_file_safely(filename, &execute_file)));
+        }
+        file_results
+    } else {
+        runner::runner::init();
+        let filenames = filenames.iter().map(|s| s.to_string()).collect();
+        execute_parallel(filenames, jobs, Arc::new(execute_file))
+    };
+    let duration = start.elapsed();
+
+    let hurl_results: Vec<HurlResult> = file_results
+        .iter()
+        .flat_map(|(_, file_result)| match file_result {
+            Ok(hurl_results) => hurl_results.clone(),
+            Err(_) => vec![],
+        })
+        .collect();
 
     if matches.is_present("har_report") {
         let har_file = matches.value_of("har_report").unwrap();
@@ -303,27 +619,86 @@ fn main() {
 
 
     // Summary + exit code
+    // each dataset row is reported and counted as a run of its file
+    let count_files = file_results.len();
+    let mut count_files_failed = 0;
+    let mut count_runs = 0;
+    let mut count_errors_parsing = 0;
     let mut count_errors_runner = 0;
     let mut count_errors_assert = 0;
-    for hurl_result in hurl_results {
-        let runner_errors: Vec<runner::core::Error> = hurl_result.clone().errors().iter().filter(|e| !e.assert).map(|e| e.clone()).collect();
-
-        if hurl_result.clone().errors().is_empty() {
-            println!("{} - success", hurl_result.filename);
-        } else if runner_errors.is_empty() {
-            println!("{} - assert error", hurl_result.filename);
-            count_errors_assert += 1;
-        } else {
-            println!("{} - runner error", hurl_result.filename);
-            count_errors_runner += 1;
-        }
-        let exit_code = if count_errors_runner > 0 {
-            2
-        } else if count_errors_assert > 0 {
-            3
-        } else {
-            0
+    for (filename, file_result) in file_results {
+        let hurl_results = match file_result {
+            Ok(hurl_results) => hurl_results,
+            Err(FileError::Read) => {
+                println!("{} - read error", filename);
+                count_runs += 1;
+                count_files_failed += 1;
+                count_errors_parsing += 1;
+                continue;
+            }
+            Err(FileError::Parsing) => {
+                println!("{} - parsing error", filename);
+                count_runs += 1;
+                count_files_failed += 1;
+                count_errors_parsing += 1;
+                continue;
+            }
+            Err(FileError::Interrupted) => {
+                println!("{} - runner error", filename);
+                count_runs += 1;
+                count_files_failed += 1;
+                count_errors_runner += 1;
+                continue;
+            }
         };
-        std::process::exit(exit_code as i32);
+        let mut file_failed = false;
+        for hurl_result in hurl_results {
+            let errors = hurl_result.clone().errors();
+            count_runs += 1;
+            if errors.is_empty() {
+                println!("{} - success", hurl_result.name());
+            } else if errors.iter().all(|e| e.assert) {
+                println!("{} - assert error", hurl_result.name());
+                count_errors_assert += 1;
+                file_failed = true;
+            } else {
+                println!("{} - runner error", hurl_result.name());
+                count_errors_runner += 1;
+                file_failed = true;
+            }
+        }
+        if file_failed {
+            count_files_failed += 1;
+        }
+    }
+
+    let entries: Vec<EntryResult> = hurl_results.iter().flat_map(|e| e.entries.clone()).collect();
+    let count_entries_skipped = entries.iter().filter(|e| e.skipped).count();
+    let count_entries_failed = entries.iter().filter(|e| !e.errors.is_empty()).count();
+    let asserts: Vec<AssertResult> = entries.iter().flat_map(|e| e.asserts.clone()).collect();
+    let count_asserts_failed = asserts.iter().filter(|&a| a.clone().error().is_some()).count();
+    println!("{}", "-".repeat(80));
+    println!("Executed files:   {} ({} failed)", count_files, count_files_failed);
+    if with_dataset {
+        println!("Executed runs:    {} ({} failed)",
+                 count_runs,
+                 count_errors_parsing + count_errors_runner + count_errors_assert);
     }
+    println!("Executed entries: {} ({} failed, {} skipped)",
+             entries.len() - count_entries_skipped,
+             count_entries_failed,
+             count_entries_skipped);
+    println!("Executed asserts: {} ({} failed)", asserts.len(), count_asserts_failed);
+    println!("Duration:         {} ms", duration.as_millis());
+
+    let exit_code = if count_errors_parsing > 0 {
+        1
+    } else if count_errors_runner > 0 {
+        2
+    } else if count_errors_assert > 0 {
+        3
+    } else {
+        0
+    };
+    std::process::exit(exit_code);
 }
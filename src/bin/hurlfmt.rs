@@ -159,6 +159,7 @@ fn main() {
                 filename: filename.to_string(),
                 warning: true,
                 color: output_color,
+                includes: vec![],
             };
             eprintln!("{}", error.format());
             process::exit(1);
@@ -175,6 +176,7 @@ fn main() {
                         filename: filename.to_string(),
                         warning: true,
                         color: output_color,
+                        includes: vec![],
                     };
                     eprintln!("{}", error.format());
                 }
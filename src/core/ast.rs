@@ -2,10 +2,37 @@ use super::core::SourceInfo;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HurlFile {
-    pub entries: Vec<Entry>,
+    pub statements: Vec<Statement>,
     pub line_terminators: Vec<LineTerminator>,
 }
 
+impl HurlFile {
+    pub fn entries(self) -> Vec<Entry> {
+        return self.statements
+            .into_iter()
+            .filter_map(|statement| match statement {
+                Statement::Entry(entry) => Some(entry),
+                Statement::Include(_) => None,
+            })
+            .collect();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Statement {
+    Entry(Entry),
+    Include(Include),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Include {
+    pub line_terminators: Vec<LineTerminator>,
+    pub space0: Whitespace,
+    pub space1: Whitespace,
+    pub filename: Filename,
+    pub line_terminator0: LineTerminator,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Entry {
     pub request: Request,
@@ -57,6 +84,15 @@ impl Request {
         }
         return vec![];
     }
+    pub fn options(self) -> Vec<EntryOption> {
+        for section in self.sections {
+            match section.value {
+                SectionValue::Options(options) => return options,
+                _ => {}
+            }
+        }
+        return vec![];
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -67,7 +103,7 @@ pub struct Response {
     pub status: Status,
     pub space1: Whitespace,
     pub line_terminator0: LineTerminator,
-    pub headers: Vec<Header>,
+    pub headers: Vec<ResponseHeader>,
     pub sections: Vec<Section>,
     pub body: Option<Body>,
     pub source_info: SourceInfo,
@@ -92,6 +128,15 @@ impl Response {
         }
         return vec![];
     }
+    pub fn namespaces(self) -> Vec<Param> {
+        for section in self.sections {
+            match section.value {
+                SectionValue::Namespaces(params) => return params,
+                _ => {}
+            }
+        }
+        return vec![];
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -163,6 +208,26 @@ pub struct Header {
     pub line_terminator0: LineTerminator,
 }
 
+// implicit response header
+// Content-Type: text/html        one of the headers equals the value
+// Content-Type matches "text/.*" one of the headers matches the predicate
+// X-Powered-By not exists        none of the headers matches the predicate
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResponseHeader {
+    Value(Header),
+    Assert(HeaderAssert),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeaderAssert {
+    pub line_terminators: Vec<LineTerminator>,
+    pub space0: Whitespace,
+    pub name: HurlString,
+    pub space1: Whitespace,
+    pub predicate: Predicate,
+    pub line_terminator0: LineTerminator,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Body {
     pub line_terminators: Vec<LineTerminator>,
@@ -200,6 +265,8 @@ impl Section {
             SectionValue::FormParams(_) => "FormParams",
             SectionValue::Cookies(_) => "Cookies",
             SectionValue::Captures(_) => "Captures",
+            SectionValue::Namespaces(_) => "Namespaces",
+            SectionValue::Options(_) => "Options",
         };
     }
 }
@@ -211,6 +278,8 @@ pub enum SectionValue {
     Cookies(Vec<Cookie>),
     Captures(Vec<Capture>),
     Asserts(Vec<Assert>),
+    Namespaces(Vec<Param>),
+    Options(Vec<EntryOption>),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -240,6 +309,47 @@ pub struct CookieValue {
     pub value: String,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryOption {
+    pub line_terminators: Vec<LineTerminator>,
+    pub space0: Whitespace,
+    pub space1: Whitespace,
+    pub space2: Whitespace,
+    pub kind: OptionKind,
+    pub line_terminator0: LineTerminator,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OptionKind {
+    Insecure(bool),
+    FollowLocation(bool),
+    Delay(u64),
+    Verbose(bool),
+    Skip(bool),
+    Variable(VariableDefinition),
+}
+
+impl OptionKind {
+    pub fn name(&self) -> &str {
+        return match self {
+            OptionKind::Insecure(_) => "insecure",
+            OptionKind::FollowLocation(_) => "location",
+            OptionKind::Delay(_) => "delay",
+            OptionKind::Verbose(_) => "verbose",
+            OptionKind::Skip(_) => "skip",
+            OptionKind::Variable(_) => "variable",
+        };
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariableDefinition {
+    pub name: String,
+    pub space0: Whitespace,
+    pub space1: Whitespace,
+    pub value: HurlTemplate,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Capture {
     pub line_terminators: Vec<LineTerminator>,
@@ -265,20 +375,31 @@ pub struct Assert {
 pub struct Query {
     pub source_info: SourceInfo,
     pub value: QueryValue,
+    pub filters: Vec<Filter>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum QueryValue {
     Status {},
+    Url {},
+    Method {},
     Header {
         space0: Whitespace,
         name: HurlString,
     },
+    RequestHeader {
+        space0: Whitespace,
+        space1: Whitespace,
+        name: HurlString,
+    },
     Cookie {
         space0: Whitespace,
         name: HurlString,
     },
     Body {},
+    Bytes {},
+    Sha256 {},
+    Md5 {},
     Xpath {
         space0: Whitespace,
         expr: HurlString,
@@ -291,16 +412,74 @@ pub enum QueryValue {
         space0: Whitespace,
         expr: HurlString,
     },
+    Css {
+        space0: Whitespace,
+        expr: HurlString,
+    },
 }
 impl Query {
+    // the last stage of the query (query itself or its last filter) returns a list
     pub fn is_jsonpath(self) -> bool {
+        return match self.filters.last() {
+            Some(Filter { value: FilterValue::Jsonpath { .. }, .. }) => true,
+            Some(_) => false,
+            None => match self.value {
+                QueryValue::Jsonpath { .. } => true,
+                _ => false,
+            }
+        };
+    }
+    pub fn is_css(self) -> bool {
+        if !self.filters.is_empty() {
+            return false;
+        }
         return match self.value {
-            QueryValue::Jsonpath {..} => true,
+            QueryValue::Css {..} => true,
             _ => false,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    pub space0: Whitespace,
+    pub source_info: SourceInfo,
+    pub value: FilterValue,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterValue {
+    Regex {
+        space0: Whitespace,
+        expr: HurlString,
+    },
+    Count {},
+    Nth {
+        space0: Whitespace,
+        n: u64,
+    },
+    ToInt {},
+    Split {
+        space0: Whitespace,
+        sep: HurlString,
+    },
+    Replace {
+        space0: Whitespace,
+        old_value: HurlString,
+        space1: Whitespace,
+        new_value: HurlString,
+    },
+    Base64Decode {},
+    ToDate {
+        space0: Whitespace,
+        format: HurlString,
+    },
+    Jsonpath {
+        space0: Whitespace,
+        expr: HurlString,
+    },
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Predicate {
     pub not: bool,
@@ -338,10 +517,29 @@ pub enum PredicateFuncValue {
         space0: Whitespace,
         value: bool,
     },
+    ApproxEqual {
+        space0: Whitespace,
+        value: Float,
+        space1: Whitespace,
+        space2: Whitespace,
+        tolerance: Float,
+    },
+    EqualBytes {
+        space0: Whitespace,
+        value: Bytes,
+    },
+    EqualJson {
+        space0: Whitespace,
+        value: String,
+    },
     CountEqual {
         space0: Whitespace,
         value: u64,
     },
+    CountEqualExpression {
+        space0: Whitespace,
+        value: Expr,
+    },
     StartWith {
         space0: Whitespace,
         value: HurlTemplate,
@@ -352,12 +550,68 @@ pub enum PredicateFuncValue {
     },
     Match {
         space0: Whitespace,
-        value: HurlString,
+        value: HurlTemplate,
+    },
+    IncludeString {
+        space0: Whitespace,
+        value: HurlTemplate,
+    },
+    IncludeInt {
+        space0: Whitespace,
+        value: i64,
+    },
+    IncludeFloat {
+        space0: Whitespace,
+        value: Float,
+    },
+    IncludeBool {
+        space0: Whitespace,
+        value: bool,
+    },
+    AllMatch {
+        space0: Whitespace,
+        value: HurlTemplate,
+    },
+    ValidateSchema {
+        space0: Whitespace,
+        value: Bytes,
+    },
+    ValidateXsd {
+        space0: Whitespace,
+        value: Bytes,
+    },
+    ValidateDtd {
+        space0: Whitespace,
+        value: Bytes,
+    },
+    AnyMatch {
+        space0: Whitespace,
+        value: HurlTemplate,
+    },
+    IsIsoDate {},
+    Before {
+        space0: Whitespace,
+        value: DateValue,
+    },
+    After {
+        space0: Whitespace,
+        value: DateValue,
     },
     FirstEqualInt {
         space0: Whitespace,
         value: i64,
     },
+    FirstEqualFloat {
+        space0: Whitespace,
+        value: Float,
+    },
+    FirstApproxEqual {
+        space0: Whitespace,
+        value: Float,
+        space1: Whitespace,
+        space2: Whitespace,
+        tolerance: Float,
+    },
     FirstEqualBool {
         space0: Whitespace,
         value: bool,
@@ -366,13 +620,82 @@ pub enum PredicateFuncValue {
         space0: Whitespace,
         value: HurlTemplate,
     },
+    FirstEqualJson {
+        space0: Whitespace,
+        value: String,
+    },
     FirstCountEqual {
         space0: Whitespace,
         value: u64,
     },
+    FirstCountEqualExpression {
+        space0: Whitespace,
+        value: Expr,
+    },
+    FirstValidateSchema {
+        space0: Whitespace,
+        value: Bytes,
+    },
+    FirstIsIsoDate {},
+    FirstBefore {
+        space0: Whitespace,
+        value: DateValue,
+    },
+    FirstAfter {
+        space0: Whitespace,
+        value: DateValue,
+    },
     Exist {}
 }
 
+// date compared with before/after
+// either an iso/http date string or the current date (now - 1d)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DateValue {
+    Template { value: HurlTemplate },
+    Now { offset: Option<DateOffset> },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DateOffset {
+    pub space0: Whitespace,
+    pub negative: bool,
+    pub space1: Whitespace,
+    pub value: u64,
+    pub unit: DurationUnit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DurationUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+}
+
+impl DurationUnit {
+    pub fn to_string(&self) -> String {
+        return match self {
+            DurationUnit::Second => String::from("s"),
+            DurationUnit::Minute => String::from("m"),
+            DurationUnit::Hour => String::from("h"),
+            DurationUnit::Day => String::from("d"),
+            DurationUnit::Week => String::from("w"),
+        };
+    }
+
+    pub fn seconds(&self) -> i64 {
+        return match self {
+            DurationUnit::Second => 1,
+            DurationUnit::Minute => 60,
+            DurationUnit::Hour => 3600,
+            DurationUnit::Day => 86400,
+            DurationUnit::Week => 604800,
+        };
+    }
+}
+
 //
 // Primitives
 //
@@ -416,29 +739,35 @@ pub struct Number {
 // keep Number terminology for both Integer and Decimal Numbers
 // different representation for the same float value
 // 1.01 and 1.010
-
-// TBC: Issue with equality for f64
-// represent your float only with integers
-// must be easily compared to the core float value
-#[derive(Clone, Debug, PartialEq, Eq)]
+// the source representation is kept in encoded
+#[derive(Clone, Debug)]
 pub struct Float {
-    pub int: i64,
-    pub decimal: u64,   // use 18 digits
-    pub decimal_digits: usize,   // number of digits
+    pub value: f64,
+    pub encoded: String,
 }
+
 impl Float {
     pub fn to_string(&self) -> String {
-        let decimal_str : String = format!("{:018}",self.decimal).chars().into_iter().take(self.decimal_digits).collect();
-        return format!("{}.{}",self.int, decimal_str);
+        return self.encoded.clone();
     }
 }
 
+impl PartialEq for Float {
+    fn eq(&self, other: &Float) -> bool {
+        return self.value.to_bits() == other.value.to_bits() && self.encoded == other.encoded;
+    }
+}
+
+impl Eq for Float {}
+
 #[test]
 fn test_float() {
-    assert_eq!(Float{ int: 1, decimal: 0, decimal_digits: 1 }.to_string(), "1.0");
-    assert_eq!(Float{ int: 1, decimal: 10000000000000000, decimal_digits: 2 }.to_string(), "1.01");
-    assert_eq!(Float{ int: 1, decimal: 10000000000000000, decimal_digits: 3 }.to_string(), "1.010");
-    assert_eq!(Float{ int: -1, decimal: 333333333333333333, decimal_digits: 3 }.to_string(), "-1.333");
+    assert_eq!(Float { value: 1.0, encoded: String::from("1.0") }.to_string(), "1.0");
+    assert_eq!(Float { value: 1.01, encoded: String::from("1.010") }.to_string(), "1.010");
+    assert_ne!(
+        Float { value: 1.0, encoded: String::from("1.0") },
+        Float { value: 1.0, encoded: String::from("1.00") }
+    );
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -490,6 +819,13 @@ pub struct HurlTemplate {
     pub source_info: SourceInfo,
 }
 
+impl HurlTemplate {
+    // a single variable without delimiter: {{var}}
+    pub fn is_expression(&self) -> bool {
+        return self.delimiter.is_empty() && matches!(self.elements.as_slice(), [HurlTemplateElement::Expression { .. }]);
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum HurlTemplateElement {
     Literal { value: HurlString2 },
@@ -500,9 +836,46 @@ pub enum HurlTemplateElement {
 pub struct Expr {
     pub space0: Whitespace,
     pub variable: Variable,
+    pub arguments: Vec<ExprArgument>,
+    pub filters: Vec<ExprFilter>,
     pub space1: Whitespace,
 }
 
+// argument of a function call such as {{randomInt 1 100}}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprArgument {
+    pub space0: Whitespace,
+    pub value: ExprArgumentValue,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprArgumentValue {
+    String(HurlString),
+    Integer { value: i64, source_info: SourceInfo },
+}
+
+// filter applied to the value of an expression, such as {{ host | default: "localhost" }}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprFilter {
+    pub space0: Whitespace,
+    pub space1: Whitespace,
+    pub source_info: SourceInfo,
+    pub value: ExprFilterValue,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprFilterValue {
+    Default {
+        space0: Whitespace,
+        space1: Whitespace,
+        value: ExprArgumentValue,
+    },
+    UrlEncode {},
+    Base64Encode {},
+    Upper {},
+    JsonEncode {},
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Variable {
     pub name: String,
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::date::Date;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum DeprecatedValue {
@@ -12,31 +14,43 @@ pub enum DeprecatedValue {
     ListInt(Vec<i32>),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-//#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Value {
     Bool(bool),
     Integer(i64),
-
-    // can use simply Float(f64)
-    // the trait `std::cmp::Eq` is not implemented for `f64`
-    // integer/ decimals with 18 digits
-    Float(i64, u64),
-    // integer part, decimal part (9 digits) TODO Clarify your custom type
+    Float(f64),
     String(String),
     List(Vec<Value>),
     Object(Vec<(String, Value)>),
     Nodeset(usize),
     Bytes(Vec<u8>),
+    Date(Date),
     None,
 }
 
-impl Value {
-    pub fn from_f64(value: f64) -> Value {
-        let integer = value.round() as i64;
-        let decimal = (value.abs().fract() * 1000000000000000000.0).round() as u64;
-        return Value::Float(integer, decimal);
+// floats are compared by value (NaN being equal to itself)
+// an integer is never equal to a float, even with the same value
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        return match (self, other) {
+            (Value::Bool(v1), Value::Bool(v2)) => v1 == v2,
+            (Value::Integer(v1), Value::Integer(v2)) => v1 == v2,
+            (Value::Float(v1), Value::Float(v2)) => v1 == v2 || (v1.is_nan() && v2.is_nan()),
+            (Value::String(v1), Value::String(v2)) => v1 == v2,
+            (Value::List(v1), Value::List(v2)) => v1 == v2,
+            (Value::Object(v1), Value::Object(v2)) => v1 == v2,
+            (Value::Nodeset(v1), Value::Nodeset(v2)) => v1 == v2,
+            (Value::Bytes(v1), Value::Bytes(v2)) => v1 == v2,
+            (Value::Date(v1), Value::Date(v2)) => v1 == v2,
+            (Value::None, Value::None) => true,
+            _ => false,
+        };
     }
+}
+
+impl Eq for Value {}
+
+impl Value {
 
     pub fn is_scalar(&self) -> bool {
         return match self {
@@ -48,7 +62,7 @@ impl Value {
         return match self {
             Value::Integer(x) => x.to_string(),
             Value::Bool(x) => x.to_string(),
-            Value::Float(int, dec) => format!("{}.{}", int, dec),
+            Value::Float(f) => format_float(*f),
             Value::String(x) => x.to_string(),
             Value::List(values) => {
                 let values : Vec<String> = values.iter().map(|e| e.to_string()).collect();
@@ -56,21 +70,86 @@ impl Value {
             }
             Value::Object(_) => format!("Object()"),
             Value::Nodeset(x) => format!("Nodeset{:?}", x),
-            Value::Bytes(x) => format!("Bytes({:?})", x),
+            Value::Bytes(x) => {
+                let hex: Vec<String> = x.iter().map(|b| format!("{:02x}", b)).collect();
+                format!("Bytes({})", hex.join(""))
+            }
+            Value::Date(date) => date.to_string(),
             Value::None => format!("None"),
         };
     }
+
+    // bytes are represented as an hexadecimal string
+    // None if the value has no json representation (nodeset, NaN or infinite float)
+    pub fn to_json(&self) -> Option<String> {
+        return match self {
+            Value::Bool(b) => Some(b.to_string()),
+            Value::Integer(i) => Some(i.to_string()),
+            Value::Float(f) => if f.is_finite() { Some(format_float(*f)) } else { None },
+            Value::String(s) => Some(serde_json::Value::String(s.clone()).to_string()),
+            Value::Date(date) => Some(serde_json::Value::String(date.to_string()).to_string()),
+            Value::Bytes(bytes) => {
+                let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                Some(format!("\"{}\"", hex.join("")))
+            }
+            Value::List(values) => {
+                let mut elements = vec![];
+                for value in values {
+                    elements.push(value.to_json()?);
+                }
+                Some(format!("[{}]", elements.join(",")))
+            }
+            Value::Object(members) => {
+                let mut elements = vec![];
+                for (name, value) in members {
+                    elements.push(format!("{}:{}", serde_json::Value::String(name.clone()), value.to_json()?));
+                }
+                Some(format!("{{{}}}", elements.join(",")))
+            }
+            Value::Nodeset(_) => None,
+            Value::None => Some(String::from("null")),
+        };
+    }
+}
+
+// shortest representation, with at least one decimal
+pub fn format_float(value: f64) -> String {
+    if value.is_finite() && value.fract() == 0.0 {
+        return format!("{:.1}", value);
+    } else {
+        return format!("{}", value);
+    }
 }
 
 #[test]
-fn test_from_f64() {
-    assert_eq!(Value::from_f64(1.0), Value::Float(1, 0));
-    assert_eq!(Value::from_f64(-1.0), Value::Float(-1, 0));
-    assert_eq!(Value::from_f64(1.1), Value::Float(1, 100000000000000096)); //TBC!!
-    assert_eq!(Value::from_f64(-1.1), Value::Float(-1, 100000000000000096));
+fn test_float() {
+    assert_eq!(Value::Float(1.0), Value::Float(1.0));
+    assert_eq!(Value::Float(-0.5) == Value::Float(0.5), false);
+    assert_eq!(Value::Float(1.0) == Value::Integer(1), false);
+    assert_eq!(Value::Float(f64::NAN), Value::Float(f64::NAN));
+
+    assert_eq!(Value::Float(1.0).to_string(), "1.0");
+    assert_eq!(Value::Float(-1.0).to_string(), "-1.0");
+    assert_eq!(Value::Float(1.6).to_string(), "1.6");
+    assert_eq!(Value::Float(-0.05).to_string(), "-0.05");
+    assert_eq!(Value::Float(1.1).to_string(), "1.1");
 }
 
 
+#[test]
+fn test_to_json() {
+    assert_eq!(Value::Integer(1).to_json().unwrap(), "1");
+    assert_eq!(Value::Float(1.0).to_json().unwrap(), "1.0");
+    assert_eq!(Value::String(String::from("a\"b")).to_json().unwrap(), "\"a\\\"b\"");
+    assert_eq!(Value::Bytes(vec![1, 171]).to_json().unwrap(), "\"01ab\"");
+    assert_eq!(Value::List(vec![Value::Bool(true), Value::None]).to_json().unwrap(), "[true,null]");
+    assert_eq!(Value::Object(vec![(String::from("id"), Value::Integer(1))]).to_json().unwrap(), "{\"id\":1}");
+
+    assert_eq!(Value::Float(f64::NAN).to_json(), None);
+    assert_eq!(Value::Nodeset(2).to_json(), None);
+    assert_eq!(Value::List(vec![Value::Integer(1), Value::Float(f64::INFINITY)]).to_json(), None);
+}
+
 #[test]
 fn test_is_scalar() {
     assert_eq!(Value::Integer(1).is_scalar(), true);
@@ -0,0 +1,393 @@
+// date
+// a point in time (utc) with a nanosecond precision
+// dates are read from
+// - ISO 8601 strings (2020-01-01, 2020-01-01T10:00:00Z, 2020-01-01T10:00:00.250+02:00)
+// - http dates (Wed, 21 Oct 2015 07:28:00 GMT and the obsolete rfc 850 and asctime formats)
+// - a strptime-like format (toDate filter)
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Date {
+    pub seconds: i64,
+    pub nanos: u32,
+}
+
+const MONTHS: [&str; 12] = ["january", "february", "march", "april", "may", "june", "july", "august", "september", "october", "november", "december"];
+const WEEKDAYS: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+const ISO_FORMATS: [&str; 7] = [
+    "%Y-%m-%d",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%dT%H:%M%z",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%z",
+    "%Y-%m-%dT%H:%M:%S.%f",
+    "%Y-%m-%dT%H:%M:%S.%f%z",
+];
+
+const HTTP_FORMATS: [&str; 3] = [
+    "%a, %d %b %Y %H:%M:%S GMT",
+    "%A, %d-%b-%y %H:%M:%S GMT",
+    "%a %b %e %H:%M:%S %Y",
+];
+
+impl Date {
+    pub fn now() -> Date {
+        return match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => Date { seconds: d.as_secs() as i64, nanos: d.subsec_nanos() },
+            Err(e) => {
+                let d = e.duration();
+                Date { seconds: 0, nanos: 0 }.add_seconds(-(d.as_secs() as i64))
+            }
+        };
+    }
+
+    pub fn add_seconds(&self, seconds: i64) -> Date {
+        return Date { seconds: self.seconds + seconds, nanos: self.nanos };
+    }
+
+    // a date-time without offset is considered in utc
+    pub fn parse_iso(s: &str) -> Option<Date> {
+        return ISO_FORMATS.iter().find_map(|format| parse_format(s, format, true));
+    }
+
+    pub fn parse_http(s: &str) -> Option<Date> {
+        return HTTP_FORMATS.iter().find_map(|format| parse_format(s, format, false));
+    }
+
+    // date given as an iso or http string
+    pub fn from_string(s: &str) -> Option<Date> {
+        let s = s.trim();
+        return match Date::parse_iso(s) {
+            Some(date) => Some(date),
+            None => Date::parse_http(s),
+        };
+    }
+
+    // the format must have been checked with check_format
+    pub fn parse(s: &str, format: &str) -> Option<Date> {
+        return parse_format(s, format, false);
+    }
+
+    // ISO 8601 in utc, with the fraction of second only if not null
+    pub fn to_string(&self) -> String {
+        let days = self.seconds.div_euclid(86400);
+        let time = self.seconds.rem_euclid(86400);
+        let (year, month, day) = civil_from_days(days);
+        let fraction = if self.nanos == 0 {
+            String::from("")
+        } else {
+            format!(".{:09}", self.nanos).trim_end_matches('0').to_string()
+        };
+        return format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z", year, month, day, time / 3600, time % 3600 / 60, time % 60, fraction);
+    }
+
+    // the format must have been checked with check_format
+    // the date is formatted in utc
+    pub fn format(&self, format: &str) -> String {
+        let days = self.seconds.div_euclid(86400);
+        let time = self.seconds.rem_euclid(86400);
+        let (year, month, day) = civil_from_days(days);
+        let month_name = capitalize(MONTHS[month as usize - 1]);
+        // 1970-01-01 is a thursday
+        let weekday_name = capitalize(WEEKDAYS[(days + 3).rem_euclid(7) as usize]);
+        let format = format.replace("%T", "%H:%M:%S").replace("%F", "%Y-%m-%d");
+        let mut s = String::from("");
+        let mut directives = format.chars();
+        while let Some(c) = directives.next() {
+            if c != '%' {
+                s.push(c);
+                continue;
+            }
+            match directives.next() {
+                Some('Y') => s.push_str(format!("{:04}", year).as_str()),
+                Some('y') => s.push_str(format!("{:02}", year.rem_euclid(100)).as_str()),
+                Some('m') => s.push_str(format!("{:02}", month).as_str()),
+                Some('d') => s.push_str(format!("{:02}", day).as_str()),
+                Some('e') => s.push_str(format!("{:2}", day).as_str()),
+                Some('H') => s.push_str(format!("{:02}", time / 3600).as_str()),
+                Some('M') => s.push_str(format!("{:02}", time % 3600 / 60).as_str()),
+                Some('S') => s.push_str(format!("{:02}", time % 60).as_str()),
+                Some('f') => s.push_str(format!("{:09}", self.nanos).as_str()),
+                Some('b') | Some('h') => s.push_str(&month_name[..3]),
+                Some('B') => s.push_str(month_name.as_str()),
+                Some('a') => s.push_str(&weekday_name[..3]),
+                Some('A') => s.push_str(weekday_name.as_str()),
+                Some('z') => s.push_str("+0000"),
+                Some('Z') => s.push_str("UTC"),
+                Some('s') => s.push_str(self.seconds.to_string().as_str()),
+                Some('%') => s.push('%'),
+                _ => {}
+            }
+        }
+        return s;
+    }
+}
+
+// supported directives
+// %Y %y %m %d %e %H %M %S %f %b %h %B %a %A %z %Z %s %T %F %%
+// a space matches any number of spaces
+pub fn check_format(format: &str) -> Result<(), String> {
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        match chars.next() {
+            Some(d) if "YymdeHMSfbhBaAzZsTF%".contains(d) => {}
+            Some(d) => return Err(format!("unknown directive %{}", d)),
+            None => return Err(String::from("incomplete directive %")),
+        }
+    }
+    return Ok(());
+}
+
+#[derive(Clone, Debug)]
+struct Fields {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    nanos: u32,
+    offset: i64,
+    timestamp: Option<i64>,
+}
+
+// numbers have a fixed width in strict mode (iso)
+fn parse_format(s: &str, format: &str, strict: bool) -> Option<Date> {
+    let format = format.replace("%T", "%H:%M:%S").replace("%F", "%Y-%m-%d");
+    let mut input = Input { chars: s.chars().collect(), pos: 0 };
+    let mut fields = Fields { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0, nanos: 0, offset: 0, timestamp: None };
+    let mut directives = format.chars();
+    while let Some(c) = directives.next() {
+        match c {
+            '%' => match directives.next()? {
+                'Y' => fields.year = input.number(if strict { 4 } else { 1 }, 4)?,
+                'y' => {
+                    let year = input.number(2, 2)?;
+                    fields.year = if year < 69 { 2000 + year } else { 1900 + year };
+                }
+                'm' => fields.month = input.number(if strict { 2 } else { 1 }, 2)? as u32,
+                'd' => fields.day = input.number(if strict { 2 } else { 1 }, 2)? as u32,
+                'e' => {
+                    input.spaces();
+                    fields.day = input.number(1, 2)? as u32;
+                }
+                'H' => fields.hour = input.number(if strict { 2 } else { 1 }, 2)?,
+                'M' => fields.minute = input.number(if strict { 2 } else { 1 }, 2)?,
+                'S' => fields.second = input.number(if strict { 2 } else { 1 }, 2)?,
+                'f' => {
+                    let start = input.pos;
+                    let fraction = input.number(1, 9)?;
+                    fields.nanos = (fraction * 10_i64.pow(9 - (input.pos - start) as u32)) as u32;
+                }
+                'b' | 'h' => fields.month = input.name(&MONTHS, 3)? as u32 + 1,
+                'B' => fields.month = input.name(&MONTHS, 0)? as u32 + 1,
+                'a' => { input.name(&WEEKDAYS, 3)?; }
+                'A' => { input.name(&WEEKDAYS, 0)?; }
+                'z' => fields.offset = input.offset()?,
+                'Z' => {
+                    if !input.keyword("UTC") && !input.keyword("GMT") && !input.keyword("Z") {
+                        return None;
+                    }
+                }
+                's' => {
+                    let negative = input.keyword("-");
+                    let seconds = input.number(1, 18)?;
+                    fields.timestamp = Some(if negative { -seconds } else { seconds });
+                }
+                '%' => if !input.keyword("%") { return None; },
+                _ => return None,
+            },
+            ' ' => input.spaces(),
+            c => if !input.keyword(c.to_string().as_str()) { return None; },
+        }
+    }
+    if input.pos != input.chars.len() {
+        return None;
+    }
+
+    if let Some(seconds) = fields.timestamp {
+        return Some(Date { seconds, nanos: fields.nanos });
+    }
+    if fields.month < 1 || fields.month > 12 || fields.day < 1 || fields.day > days_in_month(fields.year, fields.month)
+        || fields.hour > 23 || fields.minute > 59 || fields.second > 60 {
+        return None;
+    }
+    let seconds = days_from_civil(fields.year, fields.month, fields.day) * 86400
+        + fields.hour * 3600 + fields.minute * 60 + fields.second - fields.offset;
+    return Some(Date { seconds, nanos: fields.nanos });
+}
+
+struct Input {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Input {
+    fn number(&mut self, min_width: usize, max_width: usize) -> Option<i64> {
+        let start = self.pos;
+        while self.pos < self.chars.len() && self.pos - start < max_width && self.chars[self.pos].is_ascii_digit() {
+            self.pos += 1;
+        }
+        if self.pos - start < min_width {
+            return None;
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        return digits.parse().ok();
+    }
+
+    fn spaces(&mut self) {
+        while self.pos < self.chars.len() && self.chars[self.pos] == ' ' {
+            self.pos += 1;
+        }
+    }
+
+    fn keyword(&mut self, s: &str) -> bool {
+        let chars: Vec<char> = s.chars().collect();
+        if self.chars.len() - self.pos < chars.len() || self.chars[self.pos..self.pos + chars.len()] != chars[..] {
+            return false;
+        }
+        self.pos += chars.len();
+        return true;
+    }
+
+    // index of the (case-insensitive) name, abbreviated to its first letters if length is not null
+    fn name(&mut self, names: &[&str], length: usize) -> Option<usize> {
+        for (index, name) in names.iter().enumerate() {
+            let name = if length == 0 { name.to_string() } else { name[..length].to_string() };
+            let end = self.pos + name.len();
+            if end <= self.chars.len() && self.chars[self.pos..end].iter().collect::<String>().to_lowercase() == name {
+                self.pos = end;
+                return Some(index);
+            }
+        }
+        return None;
+    }
+
+    // Z, +HH:MM, +HHMM or +HH, in seconds
+    fn offset(&mut self) -> Option<i64> {
+        if self.keyword("Z") {
+            return Some(0);
+        }
+        let sign = if self.keyword("+") {
+            1
+        } else if self.keyword("-") {
+            -1
+        } else {
+            return None;
+        };
+        let hours = self.number(2, 2)?;
+        self.keyword(":");
+        let minutes = self.number(2, 2).unwrap_or(0);
+        if hours > 23 || minutes > 59 {
+            return None;
+        }
+        return Some(sign * (hours * 3600 + minutes * 60));
+    }
+}
+
+fn capitalize(name: &str) -> String {
+    return format!("{}{}", name[..1].to_uppercase(), &name[1..]);
+}
+
+fn is_leap_year(year: i64) -> bool {
+    return year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    return match month {
+        2 => if is_leap_year(year) { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+}
+
+// number of days since 1970-01-01
+// http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month_index = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    return era * 146097 + day_of_era - 719468;
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400;
+    return (if month <= 2 { year + 1 } else { year }, month, day);
+}
+
+
+#[test]
+fn test_parse_iso() {
+    assert_eq!(Date::parse_iso("1970-01-01").unwrap(), Date { seconds: 0, nanos: 0 });
+    assert_eq!(Date::parse_iso("2020-02-29").unwrap(), Date { seconds: 1582934400, nanos: 0 });
+    assert_eq!(Date::parse_iso("2020-02-29T10:30:00Z").unwrap(), Date { seconds: 1582972200, nanos: 0 });
+    assert_eq!(Date::parse_iso("2020-02-29T12:30:00+02:00").unwrap(), Date { seconds: 1582972200, nanos: 0 });
+    assert_eq!(Date::parse_iso("2020-02-29T10:30:00.25Z").unwrap(), Date { seconds: 1582972200, nanos: 250_000_000 });
+    assert_eq!(Date::parse_iso("1969-12-31T23:59:59").unwrap(), Date { seconds: -1, nanos: 0 });
+
+    assert_eq!(Date::parse_iso("2019-02-29"), None);
+    assert_eq!(Date::parse_iso("2020-2-1"), None);
+    assert_eq!(Date::parse_iso("2020-01-01T25:00:00Z"), None);
+    assert_eq!(Date::parse_iso("2020-01-01 10:00:00"), None);
+    assert_eq!(Date::parse_iso("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+}
+
+#[test]
+fn test_parse_http() {
+    let expected = Date::parse_iso("2015-10-21T07:28:00Z").unwrap();
+    assert_eq!(Date::parse_http("Wed, 21 Oct 2015 07:28:00 GMT").unwrap(), expected);
+    assert_eq!(Date::parse_http("Wednesday, 21-Oct-15 07:28:00 GMT").unwrap(), expected);
+    assert_eq!(Date::parse_http("Wed Oct 21 07:28:00 2015").unwrap(), expected);
+    assert_eq!(Date::from_string(" Wed, 21 Oct 2015 07:28:00 GMT").unwrap(), expected);
+    assert_eq!(Date::parse_http("Wed, 21 Oct 2015 07:28:00"), None);
+}
+
+#[test]
+fn test_parse_format() {
+    assert_eq!(Date::parse("21/10/2015", "%d/%m/%Y").unwrap(), Date::parse_iso("2015-10-21").unwrap());
+    assert_eq!(Date::parse("2015-10-21 07:28", "%F %H:%M").unwrap(), Date::parse_iso("2015-10-21T07:28:00Z").unwrap());
+    assert_eq!(Date::parse("October 1, 2015", "%B %d, %Y").unwrap(), Date::parse_iso("2015-10-01").unwrap());
+    assert_eq!(Date::parse("1445412480", "%s").unwrap(), Date::parse_iso("2015-10-21T07:28:00Z").unwrap());
+    assert_eq!(Date::parse("2015-10-21", "%d/%m/%Y"), None);
+
+    assert_eq!(check_format("%Y-%m-%d"), Ok(()));
+    assert_eq!(check_format("%Y-%q"), Err(String::from("unknown directive %q")));
+    assert_eq!(check_format("%"), Err(String::from("incomplete directive %")));
+}
+
+#[test]
+fn test_to_string() {
+    assert_eq!(Date { seconds: 0, nanos: 0 }.to_string(), "1970-01-01T00:00:00Z");
+    assert_eq!(Date { seconds: -1, nanos: 0 }.to_string(), "1969-12-31T23:59:59Z");
+    assert_eq!(Date { seconds: 1582972200, nanos: 250_000_000 }.to_string(), "2020-02-29T10:30:00.25Z");
+    assert_eq!(Date::parse_iso("0001-01-01").unwrap().to_string(), "0001-01-01T00:00:00Z");
+}
+
+#[test]
+fn test_format() {
+    let date = Date { seconds: 1582972200, nanos: 250_000_000 };
+    assert_eq!(date.format("%Y-%m-%d"), "2020-02-29");
+    assert_eq!(date.format("%FT%T.%f%z"), "2020-02-29T10:30:00.250000000+0000");
+    assert_eq!(date.format("%a, %d %b %Y %H:%M:%S GMT"), "Sat, 29 Feb 2020 10:30:00 GMT");
+    assert_eq!(date.format("%A %e %B %y"), "Saturday 29 February 20");
+    assert_eq!(date.format("%s %Z 100%%"), "1582972200 UTC 100%");
+    assert_eq!(Date { seconds: 0, nanos: 0 }.format("%a %e"), "Thu  1");
+}
@@ -29,13 +29,22 @@ impl Expr {
 }
 
 
+// json text as a value (null members are ignored as in jsonpath results)
+pub fn parse_value(json: &str) -> Result<Value, serde_json::Error> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    return Ok(match to_value(&value) {
+        Some(value) => value,
+        None => Value::None,
+    });
+}
+
 fn to_value(value: &serde_json::Value) -> Option<Value> {
     return match value {
         serde_json::Value::Null => None,
         serde_json::Value::Bool(bool) => Some(Value::Bool(*bool)),
         serde_json::Value::Number(n) => Some(
             if n.is_f64() {
-                Value::from_f64(n.as_f64().unwrap())
+                Value::Float(n.as_f64().unwrap())
             } else {
                 Value::Integer(n.as_i64().unwrap())
             }
@@ -69,7 +78,7 @@ fn test_to_value() {
     assert_eq!(to_value(&serde_json::from_str("true").unwrap()).unwrap(), Value::Bool(true));
     assert_eq!(to_value(&serde_json::from_str("1").unwrap()).unwrap(), Value::Integer(1));
     assert_eq!(to_value(&serde_json::from_str("-1").unwrap()).unwrap(), Value::Integer(-1));
-    assert_eq!(to_value(&serde_json::from_str("1.0").unwrap()).unwrap(), Value::from_f64(1.0));
+    assert_eq!(to_value(&serde_json::from_str("1.0").unwrap()).unwrap(), Value::Float(1.0));
     assert_eq!(to_value(&serde_json::from_str(r#""hello""#).unwrap()).unwrap(), Value::String(String::from("hello")));
     assert_eq!(to_value(&serde_json::from_str("[]").unwrap()).unwrap(), Value::List(vec![]));
     assert_eq!(to_value(&serde_json::from_str("[true,1,null]").unwrap()).unwrap(), Value::List(vec![
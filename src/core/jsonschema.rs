@@ -0,0 +1,564 @@
+// jsonschema
+// validation of a json document against a JSON Schema (draft-07)
+// reference https://json-schema.org/specification-links.html#draft-7
+// supported: boolean schemas, type, enum, const, numeric/string/array/object constraints,
+// allOf/anyOf/oneOf/not, if/then/else and $ref within the schema document
+// not supported: remote references, format, dependencies
+
+
+extern crate regex;
+extern crate serde_json;
+
+use std::cell::RefCell;
+
+use regex::Regex;
+use serde_json::Value;
+
+pub struct Schema {
+    root: Value,
+    // references being resolved, with their instance, to detect cycles
+    references: RefCell<Vec<(String, *const Value)>>,
+    // the schema can not be used to validate the instance
+    error: RefCell<Option<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    InvalidJson,
+    InvalidSchema(String),
+}
+
+impl Schema {
+    pub fn parse(json: &str) -> Result<Schema, String> {
+        let root: Value = match serde_json::from_str(json) {
+            Ok(root) => root,
+            Err(e) => return Err(format!("the schema is not a valid json ({})", e)),
+        };
+        check(&root, &root)?;
+        return Ok(Schema { root, references: RefCell::new(vec![]), error: RefCell::new(None) });
+    }
+
+    // violations located by the jsonpath of the instance
+    pub fn validate(&self, json: &str) -> Result<Vec<String>, ValidationError> {
+        let instance: Value = match serde_json::from_str(json) {
+            Ok(instance) => instance,
+            Err(_) => return Err(ValidationError::InvalidJson),
+        };
+        self.references.borrow_mut().clear();
+        self.error.replace(None);
+        let mut errors = vec![];
+        self.validate_node(&self.root, &instance, String::from("$"), &mut errors);
+        return match self.error.replace(None) {
+            None => Ok(errors),
+            Some(message) => Err(ValidationError::InvalidSchema(message)),
+        };
+    }
+
+    fn is_valid(&self, schema: &Value, instance: &Value) -> bool {
+        let mut errors = vec![];
+        self.validate_node(schema, instance, String::from("$"), &mut errors);
+        return errors.is_empty();
+    }
+
+    fn validate_node(&self, schema: &Value, instance: &Value, path: String, errors: &mut Vec<String>) {
+        if self.error.borrow().is_some() {
+            return;
+        }
+        let keywords = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => {
+                errors.push(format!("{} is not allowed", path));
+                return;
+            }
+            Value::Object(keywords) => keywords,
+            _ => return,
+        };
+
+        // other keywords are ignored next to a reference (draft-07)
+        match keywords.get("$ref") {
+            Some(Value::String(reference)) => {
+                // resolving the same reference again for the same instance would never end
+                let key = (reference.clone(), instance as *const Value);
+                if self.references.borrow().contains(&key) {
+                    self.error.replace(Some(format!("the reference {} is circular", reference)));
+                    return;
+                }
+                match resolve(&self.root, reference) {
+                    Some(target) => {
+                        self.references.borrow_mut().push(key);
+                        self.validate_node(target, instance, path, errors);
+                        self.references.borrow_mut().pop();
+                    }
+                    None => {
+                        self.error.replace(Some(format!("the reference {} can not be resolved within the schema", reference)));
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        // generic
+        match keywords.get("type") {
+            Some(Value::String(name)) if !is_type(instance, name) => {
+                errors.push(format!("{} should be {} but was {}", path, name, type_name(instance)));
+            }
+            Some(Value::Array(names)) => {
+                let names: Vec<&str> = names.iter().filter_map(|name| name.as_str()).collect();
+                if !names.iter().any(|name| is_type(instance, name)) {
+                    errors.push(format!("{} should be {} but was {}", path, names.join(" or "), type_name(instance)));
+                }
+            }
+            _ => {}
+        }
+        match keywords.get("enum") {
+            Some(Value::Array(values)) if !values.iter().any(|value| json_equal(value, instance)) => {
+                errors.push(format!("{} should be one of {}", path, Value::Array(values.clone())));
+            }
+            _ => {}
+        }
+        match keywords.get("const") {
+            Some(value) if !json_equal(value, instance) => {
+                errors.push(format!("{} should be {}", path, value));
+            }
+            _ => {}
+        }
+
+        match instance {
+            Value::Number(n) => self.validate_number(keywords, n.as_f64().unwrap(), path.clone(), errors),
+            Value::String(s) => self.validate_string(keywords, s, path.clone(), errors),
+            Value::Array(elements) => self.validate_array(keywords, elements, path.clone(), errors),
+            Value::Object(members) => self.validate_object(keywords, members, path.clone(), errors),
+            _ => {}
+        }
+
+        // combinations
+        match keywords.get("allOf") {
+            Some(Value::Array(schemas)) => for schema in schemas {
+                self.validate_node(schema, instance, path.clone(), errors);
+            },
+            _ => {}
+        }
+        match keywords.get("anyOf") {
+            Some(Value::Array(schemas)) if !schemas.iter().any(|schema| self.is_valid(schema, instance)) => {
+                errors.push(format!("{} does not match any schema of anyOf", path));
+            }
+            _ => {}
+        }
+        match keywords.get("oneOf") {
+            Some(Value::Array(schemas)) => {
+                let count = schemas.iter().filter(|schema| self.is_valid(schema, instance)).count();
+                if count != 1 {
+                    errors.push(format!("{} should match exactly one schema of oneOf ({} matched)", path, count));
+                }
+            }
+            _ => {}
+        }
+        match keywords.get("not") {
+            Some(schema) if self.is_valid(schema, instance) => {
+                errors.push(format!("{} should not match the schema of not", path));
+            }
+            _ => {}
+        }
+        match keywords.get("if") {
+            Some(condition) => {
+                let branch = if self.is_valid(condition, instance) { keywords.get("then") } else { keywords.get("else") };
+                match branch {
+                    Some(schema) => self.validate_node(schema, instance, path, errors),
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn validate_number(&self, keywords: &serde_json::Map<String, Value>, value: f64, path: String, errors: &mut Vec<String>) {
+        match number(keywords, "minimum") {
+            Some(minimum) if value < minimum => {
+                errors.push(format!("{} should be greater than or equal to {}", path, minimum));
+            }
+            _ => {}
+        }
+        match number(keywords, "maximum") {
+            Some(maximum) if value > maximum => {
+                errors.push(format!("{} should be less than or equal to {}", path, maximum));
+            }
+            _ => {}
+        }
+        match number(keywords, "exclusiveMinimum") {
+            Some(minimum) if value <= minimum => {
+                errors.push(format!("{} should be greater than {}", path, minimum));
+            }
+            _ => {}
+        }
+        match number(keywords, "exclusiveMaximum") {
+            Some(maximum) if value >= maximum => {
+                errors.push(format!("{} should be less than {}", path, maximum));
+            }
+            _ => {}
+        }
+        match number(keywords, "multipleOf") {
+            Some(divisor) if divisor > 0.0 && (value / divisor).fract() != 0.0 => {
+                errors.push(format!("{} should be a multiple of {}", path, divisor));
+            }
+            _ => {}
+        }
+    }
+
+    fn validate_string(&self, keywords: &serde_json::Map<String, Value>, value: &str, path: String, errors: &mut Vec<String>) {
+        let length = value.chars().count();
+        match count(keywords, "minLength") {
+            Some(minimum) if length < minimum => {
+                errors.push(format!("{} should have at least {} characters", path, minimum));
+            }
+            _ => {}
+        }
+        match count(keywords, "maxLength") {
+            Some(maximum) if length > maximum => {
+                errors.push(format!("{} should have at most {} characters", path, maximum));
+            }
+            _ => {}
+        }
+        match keywords.get("pattern") {
+            Some(Value::String(pattern)) if !Regex::new(pattern).unwrap().is_match(value) => {
+                errors.push(format!("{} does not match pattern {}", path, Value::String(pattern.clone())));
+            }
+            _ => {}
+        }
+    }
+
+    fn validate_array(&self, keywords: &serde_json::Map<String, Value>, elements: &[Value], path: String, errors: &mut Vec<String>) {
+        match keywords.get("items") {
+            Some(Value::Array(schemas)) => {
+                for (i, element) in elements.iter().enumerate() {
+                    let element_path = format!("{}[{}]", path, i);
+                    match schemas.get(i) {
+                        Some(schema) => self.validate_node(schema, element, element_path, errors),
+                        None => match keywords.get("additionalItems") {
+                            Some(schema) => self.validate_node(schema, element, element_path, errors),
+                            None => {}
+                        }
+                    }
+                }
+            }
+            Some(schema) => for (i, element) in elements.iter().enumerate() {
+                self.validate_node(schema, element, format!("{}[{}]", path, i), errors);
+            },
+            None => {}
+        }
+        match count(keywords, "minItems") {
+            Some(minimum) if elements.len() < minimum => {
+                errors.push(format!("{} should have at least {} elements", path, minimum));
+            }
+            _ => {}
+        }
+        match count(keywords, "maxItems") {
+            Some(maximum) if elements.len() > maximum => {
+                errors.push(format!("{} should have at most {} elements", path, maximum));
+            }
+            _ => {}
+        }
+        match keywords.get("uniqueItems") {
+            Some(Value::Bool(true)) => {
+                let duplicated = elements.iter().enumerate()
+                    .any(|(i, element)| elements[i + 1..].iter().any(|other| json_equal(element, other)));
+                if duplicated {
+                    errors.push(format!("{} should have unique elements", path));
+                }
+            }
+            _ => {}
+        }
+        match keywords.get("contains") {
+            Some(schema) if !elements.iter().any(|element| self.is_valid(schema, element)) => {
+                errors.push(format!("{} does not contain any element matching the schema of contains", path));
+            }
+            _ => {}
+        }
+    }
+
+    fn validate_object(&self, keywords: &serde_json::Map<String, Value>, members: &serde_json::Map<String, Value>, path: String, errors: &mut Vec<String>) {
+        match keywords.get("required") {
+            Some(Value::Array(names)) => for name in names.iter().filter_map(|name| name.as_str()) {
+                if !members.contains_key(name) {
+                    errors.push(format!("{} is required", member_path(&path, name)));
+                }
+            },
+            _ => {}
+        }
+
+        let properties = match keywords.get("properties") {
+            Some(Value::Object(properties)) => properties.clone(),
+            _ => serde_json::Map::new(),
+        };
+        let pattern_properties: Vec<(Regex, &Value)> = match keywords.get("patternProperties") {
+            Some(Value::Object(patterns)) => patterns.iter().map(|(pattern, schema)| (Regex::new(pattern).unwrap(), schema)).collect(),
+            _ => vec![],
+        };
+        for (name, value) in members {
+            let value_path = member_path(&path, name);
+            let mut additional = true;
+            match properties.get(name) {
+                Some(schema) => {
+                    additional = false;
+                    self.validate_node(schema, value, value_path.clone(), errors);
+                }
+                None => {}
+            }
+            for (re, schema) in pattern_properties.iter() {
+                if re.is_match(name) {
+                    additional = false;
+                    self.validate_node(schema, value, value_path.clone(), errors);
+                }
+            }
+            if additional {
+                match keywords.get("additionalProperties") {
+                    Some(Value::Bool(false)) => errors.push(format!("{} is not allowed", value_path)),
+                    Some(schema) => self.validate_node(schema, value, value_path, errors),
+                    None => {}
+                }
+            }
+        }
+
+        match count(keywords, "minProperties") {
+            Some(minimum) if members.len() < minimum => {
+                errors.push(format!("{} should have at least {} members", path, minimum));
+            }
+            _ => {}
+        }
+        match count(keywords, "maxProperties") {
+            Some(maximum) if members.len() > maximum => {
+                errors.push(format!("{} should have at most {} members", path, maximum));
+            }
+            _ => {}
+        }
+    }
+}
+
+// the schema must be an object or a boolean
+// patterns must be valid regex and references must be local and resolvable
+fn check(root: &Value, schema: &Value) -> Result<(), String> {
+    match schema {
+        Value::Object(keywords) => {
+            for (name, value) in keywords {
+                match (name.as_str(), value) {
+                    ("$ref", Value::String(reference)) => if resolve(root, reference).is_none() {
+                        return Err(format!("the reference {} can not be resolved within the schema", reference));
+                    },
+                    ("pattern", Value::String(pattern)) => if Regex::new(pattern).is_err() {
+                        return Err(format!("the pattern {} is not a valid regex", pattern));
+                    },
+                    ("patternProperties", Value::Object(patterns)) => for pattern in patterns.keys() {
+                        if Regex::new(pattern).is_err() {
+                            return Err(format!("the pattern {} is not a valid regex", pattern));
+                        }
+                    },
+                    _ => {}
+                }
+                check(root, value)?;
+            }
+            return Ok(());
+        }
+        Value::Array(values) => {
+            for value in values {
+                check(root, value)?;
+            }
+            return Ok(());
+        }
+        _ => return Ok(()),
+    }
+}
+
+// local reference only (json pointer)
+fn resolve<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    if !reference.starts_with('#') {
+        return None;
+    }
+    let pointer = &reference[1..];
+    if pointer.is_empty() {
+        return Some(root);
+    }
+    return root.pointer(pointer);
+}
+
+fn is_type(instance: &Value, name: &str) -> bool {
+    return match (name, instance) {
+        ("null", Value::Null) => true,
+        ("boolean", Value::Bool(_)) => true,
+        ("object", Value::Object(_)) => true,
+        ("array", Value::Array(_)) => true,
+        ("string", Value::String(_)) => true,
+        ("number", Value::Number(_)) => true,
+        ("integer", Value::Number(n)) => n.is_i64() || n.is_u64() || n.as_f64().unwrap().fract() == 0.0,
+        _ => false,
+    };
+}
+
+fn type_name(instance: &Value) -> String {
+    return match instance {
+        Value::Null => String::from("null"),
+        Value::Bool(_) => String::from("boolean"),
+        Value::Object(_) => String::from("object"),
+        Value::Array(_) => String::from("array"),
+        Value::String(_) => String::from("string"),
+        Value::Number(_) => if is_type(instance, "integer") { String::from("integer") } else { String::from("number") },
+    };
+}
+
+// numbers are compared by value (1 equals 1.0)
+fn json_equal(value1: &Value, value2: &Value) -> bool {
+    return match (value1, value2) {
+        (Value::Number(n1), Value::Number(n2)) => n1.as_f64() == n2.as_f64(),
+        (Value::Array(elements1), Value::Array(elements2)) => {
+            elements1.len() == elements2.len() && elements1.iter().zip(elements2.iter()).all(|(e1, e2)| json_equal(e1, e2))
+        }
+        (Value::Object(members1), Value::Object(members2)) => {
+            members1.len() == members2.len() && members1.iter().all(|(name, value)| match members2.get(name) {
+                Some(other) => json_equal(value, other),
+                None => false,
+            })
+        }
+        _ => value1 == value2,
+    };
+}
+
+fn number(keywords: &serde_json::Map<String, Value>, name: &str) -> Option<f64> {
+    return match keywords.get(name) {
+        Some(Value::Number(n)) => n.as_f64(),
+        _ => None,
+    };
+}
+
+fn count(keywords: &serde_json::Map<String, Value>, name: &str) -> Option<usize> {
+    return match keywords.get(name) {
+        Some(Value::Number(n)) => n.as_u64().map(|n| n as usize),
+        _ => None,
+    };
+}
+
+fn member_path(path: &str, name: &str) -> String {
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return format!("{}.{}", path, name);
+    } else {
+        return format!("{}['{}']", path, name);
+    }
+}
+
+
+#[cfg(test)]
+fn validate(schema: &str, instance: &str) -> Vec<String> {
+    return Schema::parse(schema).unwrap().validate(instance).unwrap();
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(Schema::parse("{").err().unwrap().starts_with("the schema is not a valid json"), true);
+    assert_eq!(Schema::parse(r##"{"$ref": "#/definitions/user"}"##).err().unwrap(), "the reference #/definitions/user can not be resolved within the schema");
+    assert_eq!(Schema::parse(r#"{"$ref": "user.json"}"#).err().unwrap(), "the reference user.json can not be resolved within the schema");
+    assert_eq!(Schema::parse(r#"{"pattern": "["}"#).err().unwrap(), "the pattern [ is not a valid regex");
+}
+
+#[test]
+fn test_type() {
+    assert_eq!(validate(r#"{"type": "integer"}"#, "1"), Vec::<String>::new());
+    assert_eq!(validate(r#"{"type": "integer"}"#, "1.0"), Vec::<String>::new());
+    assert_eq!(validate(r#"{"type": "integer"}"#, "1.5"), vec!["$ should be integer but was number"]);
+    assert_eq!(validate(r#"{"type": "number"}"#, "1"), Vec::<String>::new());
+    assert_eq!(validate(r#"{"type": ["string", "null"]}"#, "null"), Vec::<String>::new());
+    assert_eq!(validate(r#"{"type": ["string", "null"]}"#, "true"), vec!["$ should be string or null but was boolean"]);
+    assert_eq!(validate("true", "1"), Vec::<String>::new());
+    assert_eq!(validate("false", "1"), vec!["$ is not allowed"]);
+}
+
+#[test]
+fn test_object() {
+    let schema = r#"{
+        "type": "object",
+        "required": ["id", "name"],
+        "properties": {
+            "id": {"type": "integer", "minimum": 1},
+            "name": {"type": "string", "minLength": 1, "pattern": "^[a-z]+$"},
+            "email": {"type": "string"}
+        },
+        "additionalProperties": false
+    }"#;
+    assert_eq!(validate(schema, r#"{"id": 1, "name": "bob"}"#), Vec::<String>::new());
+    assert_eq!(validate(schema, r#"{"id": 0, "name": "Bob", "first name": "bob"}"#), vec![
+        "$['first name'] is not allowed",
+        "$.id should be greater than or equal to 1",
+        "$.name does not match pattern \"^[a-z]+$\"",
+    ]);
+    assert_eq!(validate(schema, r#"{"name": ""}"#), vec![
+        "$.id is required",
+        "$.name should have at least 1 characters",
+        "$.name does not match pattern \"^[a-z]+$\"",
+    ]);
+    assert_eq!(validate(schema, "[]"), vec!["$ should be object but was array"]);
+}
+
+#[test]
+fn test_array() {
+    let schema = r#"{"type": "array", "items": {"enum": ["a", "b", 1]}, "minItems": 1, "uniqueItems": true}"#;
+    assert_eq!(validate(schema, r#"["a", 1.0]"#), Vec::<String>::new());
+    assert_eq!(validate(schema, "[]"), vec!["$ should have at least 1 elements"]);
+    assert_eq!(validate(schema, r#"["a", "c", "a"]"#), vec![
+        "$[1] should be one of [\"a\",\"b\",1]",
+        "$ should have unique elements",
+    ]);
+
+    let schema = r#"{"items": [{"type": "string"}, {"type": "integer"}], "additionalItems": false}"#;
+    assert_eq!(validate(schema, r#"["a", 1]"#), Vec::<String>::new());
+    assert_eq!(validate(schema, r#"[1, 1, 1]"#), vec![
+        "$[0] should be string but was integer",
+        "$[2] is not allowed",
+    ]);
+}
+
+#[test]
+fn test_references() {
+    let schema = r##"{
+        "definitions": {
+            "user": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {"name": {"type": "string"}, "friends": {"type": "array", "items": {"$ref": "#/definitions/user"}}}
+            }
+        },
+        "type": "object",
+        "properties": {"users": {"type": "array", "items": {"$ref": "#/definitions/user"}}}
+    }"##;
+    assert_eq!(validate(schema, r#"{"users": [{"name": "bob", "friends": [{"name": "alice"}]}]}"#), Vec::<String>::new());
+    assert_eq!(validate(schema, r#"{"users": [{"name": "bob", "friends": [{"id": 1}]}, {"name": 1}]}"#), vec![
+        "$.users[0].friends[0].name is required",
+        "$.users[1].name should be string but was integer",
+    ]);
+}
+
+#[test]
+fn test_reference_cycle() {
+    let schema = Schema::parse(r##"{"$ref": "#"}"##).unwrap();
+    assert_eq!(schema.validate("1").err().unwrap(), ValidationError::InvalidSchema(String::from("the reference # is circular")));
+
+    let schema = Schema::parse(r##"{"definitions": {"a": {"anyOf": [{"$ref": "#/definitions/b"}]}, "b": {"allOf": [{"$ref": "#/definitions/a"}]}}, "$ref": "#/definitions/a"}"##).unwrap();
+    assert_eq!(schema.validate("1").err().unwrap(), ValidationError::InvalidSchema(String::from("the reference #/definitions/a is circular")));
+
+    assert_eq!(Schema::parse("{}").unwrap().validate("{").err().unwrap(), ValidationError::InvalidJson);
+}
+
+#[test]
+fn test_combinations() {
+    let schema = r#"{"anyOf": [{"type": "string"}, {"type": "integer"}]}"#;
+    assert_eq!(validate(schema, "1"), Vec::<String>::new());
+    assert_eq!(validate(schema, "true"), vec!["$ does not match any schema of anyOf"]);
+
+    let schema = r#"{"oneOf": [{"type": "number"}, {"type": "integer"}]}"#;
+    assert_eq!(validate(schema, "1.5"), Vec::<String>::new());
+    assert_eq!(validate(schema, "1"), vec!["$ should match exactly one schema of oneOf (2 matched)"]);
+
+    let schema = r#"{"not": {"const": "admin"}, "allOf": [{"maxLength": 4}]}"#;
+    assert_eq!(validate(schema, r#""bob""#), Vec::<String>::new());
+    assert_eq!(validate(schema, r#""admin""#), vec!["$ should have at most 4 characters", "$ should not match the schema of not"]);
+
+    let schema = r#"{"if": {"properties": {"kind": {"const": "user"}}}, "then": {"required": ["name"]}, "else": {"required": ["id"]}}"#;
+    assert_eq!(validate(schema, r#"{"kind": "user", "name": "bob"}"#), Vec::<String>::new());
+    assert_eq!(validate(schema, r#"{"kind": "group"}"#), vec!["$.id is required"]);
+}
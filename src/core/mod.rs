@@ -1,3 +1,5 @@
 pub mod ast;
 pub mod core;
-pub mod jsonpath;
\ No newline at end of file
+pub mod date;
+pub mod jsonpath;
+pub mod jsonschema;
@@ -13,6 +13,8 @@ pub struct Error {
     pub filename: String,
     pub warning: bool,
     pub color: bool,
+    // include directives (filename, line) leading to this file, innermost first
+    pub includes: Vec<(String, usize)>,
 }
 
 
@@ -68,16 +70,26 @@ impl Error {
         );
 
 
+        // the first line of the fixme follows the cursor
+        // the next ones (list of errors) are aligned below
+        let indent = " ".repeat(self.source_info.start.column - 1 + tab_shift * 3);
+        let mut fixme_lines = self.fixme.lines();
         s.push_str(
             format!(
                 "   | {}{} {fixme}\n",
-                " ".repeat(self.source_info.start.column - 1 + tab_shift * 3),
+                indent,
                 "^".repeat(if width > 1 { width } else { 1 }),
-                fixme = self.fixme.as_str(),
+                fixme = fixme_lines.next().unwrap_or(""),
             )
                 .as_str(),
         );
+        for fixme_line in fixme_lines {
+            s.push_str(format!("   | {}  {}\n", indent, fixme_line).as_str());
+        }
         s.push_str("   |\n");
+        for (filename, line) in self.includes {
+            s.push_str(format!("   = included from {}:{}\n", filename, line).as_str());
+        }
 
         return s.to_string();
     }
@@ -99,6 +111,7 @@ fn test_basic() {
         filename,
         warning: true,
         color: false,
+        includes: vec![],
     };
     assert_eq!(error.format(),
                String::from(r#"warning: One space
@@ -126,6 +139,7 @@ fn test_with_tabs() {
         filename,
         warning: true,
         color: false,
+        includes: vec![],
     };
     assert_eq!(error.format(),
                concat!(
@@ -159,6 +173,7 @@ fn test_end_of_file() {
         filename,
         warning: true,
         color: false,
+        includes: vec![],
     };
     assert_eq!(error.format(),
                String::from(r#"warning: Parsing json
@@ -169,4 +184,67 @@ fn test_end_of_file() {
    |
 "#)
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_multiline_fixme() {
+    let filename = String::from("integration/tests/error_assert_xsd.hurl");
+    let lines = vec![
+        String::from("body validatesXsd file,food.xsd;")
+    ];
+    let error = Error {
+        exit_code: 0,
+        source_info: SourceInfo::init(1, 6, 1, 33),
+        description: String::from("Assert - Predicate Value Failed"),
+        fixme: String::from("actual value does not validate the schema:\nline 2: invalid price\nline 3: missing price"),
+        lines,
+        filename,
+        warning: false,
+        color: false,
+        includes: vec![],
+    };
+    assert_eq!(error.format(),
+               concat!(
+"error: Assert - Predicate Value Failed\n",
+"  --> integration/tests/error_assert_xsd.hurl:1:6\n",
+"   |\n",
+" 1 | body validatesXsd file,food.xsd;\n",
+"   |      ^^^^^^^^^^^^^^^^^^^^^^^^^^^ actual value does not validate the schema:\n",
+"   |        line 2: invalid price\n",
+"   |        line 3: missing price\n",
+"   |\n")
+    );
+}
+
+#[test]
+fn test_included() {
+    let filename = String::from("login.hurl");
+    let lines = vec![
+        String::from("GET http://localhost:8000/login")
+    ];
+    let error = Error {
+        exit_code: 0,
+        source_info: SourceInfo::init(1, 5, 1, 32),
+        description: String::from("Http Connection"),
+        fixme: String::from("can not connect to http://localhost:8000/login"),
+        lines,
+        filename,
+        warning: false,
+        color: false,
+        includes: vec![
+            (String::from("common/setup.hurl"), 3),
+            (String::from("main.hurl"), 1),
+        ],
+    };
+    assert_eq!(error.format(),
+               concat!(
+"error: Http Connection\n",
+"  --> login.hurl:1:5\n",
+"   |\n",
+" 1 | GET http://localhost:8000/login\n",
+"   |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^ can not connect to http://localhost:8000/login\n",
+"   |\n",
+"   = included from common/setup.hurl:3\n",
+"   = included from main.hurl:1\n")
+    );
+}
@@ -32,8 +32,8 @@ impl Htmlable for HurlFile {
     fn to_html(&self) -> String {
         let mut buffer = String::from("");
         buffer.push_str("<div class=\"hurl-file\">");
-        for entry in self.clone().entries {
-            buffer.push_str(entry.to_html().as_str());
+        for statement in self.clone().statements {
+            buffer.push_str(statement.to_html().as_str());
         }
         for line_terminator in self.line_terminators.clone() {
             buffer.push_str(line_terminator.to_html().as_str());
@@ -44,6 +44,36 @@ impl Htmlable for HurlFile {
 }
 // endregion
 
+// region statement
+impl Htmlable for Statement {
+    fn to_html(&self) -> String {
+        return match self {
+            Statement::Entry(entry) => entry.to_html(),
+            Statement::Include(include) => include.to_html(),
+        };
+    }
+}
+// endregion
+
+// region include
+impl Htmlable for Include {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        buffer.push_str("<div class=\"include\">");
+        add_line_terminators(&mut buffer, self.line_terminators.clone());
+        buffer.push_str("<span class=\"line\">");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str("<span class=\"keyword\">include</span>");
+        buffer.push_str(self.space1.to_html().as_str());
+        buffer.push_str(format!("<span class=\"filename\">{}</span>", self.filename.value).as_str());
+        buffer.push_str(self.line_terminator0.to_html().as_str());
+        buffer.push_str("</span>");
+        buffer.push_str("</div>");
+        return buffer;
+    }
+}
+// endregion
+
 // region hurl-entry
 impl Htmlable for Entry {
     fn to_html(&self) -> String {
@@ -99,6 +129,9 @@ impl Htmlable for Response {
         buffer.push_str(self.space1.to_html().as_str());
         buffer.push_str(self.status.to_html().as_str());
         buffer.push_str("</span>");
+        for header in self.headers.clone() {
+            buffer.push_str(header.to_html().as_str());
+        }
         for section in self.sections.clone() {
             buffer.push_str(section.to_html().as_str());
         }
@@ -151,6 +184,30 @@ impl Htmlable for Header {
         return buffer;
     }
 }
+
+impl Htmlable for ResponseHeader {
+    fn to_html(&self) -> String {
+        return match self {
+            ResponseHeader::Value(header) => header.to_html(),
+            ResponseHeader::Assert(header_assert) => header_assert.to_html(),
+        };
+    }
+}
+
+impl Htmlable for HeaderAssert {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        add_line_terminators(&mut buffer, self.line_terminators.clone());
+        buffer.push_str("<span class=\"line\">");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str(self.name.to_html().as_str());
+        buffer.push_str(self.space1.to_html().as_str());
+        buffer.push_str(self.predicate.to_html().as_str());
+        buffer.push_str("</span>");
+        buffer.push_str(self.line_terminator0.to_html().as_str());
+        return buffer;
+    }
+}
 // endregion
 
 // region section
@@ -200,6 +257,16 @@ impl Htmlable for SectionValue {
                     buffer.push_str(item.to_html().as_str())
                 }
             }
+            SectionValue::Namespaces(items) => {
+                for item in items {
+                    buffer.push_str(item.to_html().as_str())
+                }
+            }
+            SectionValue::Options(items) => {
+                for item in items {
+                    buffer.push_str(item.to_html().as_str())
+                }
+            }
         }
         return buffer;
     }
@@ -285,6 +352,50 @@ impl Htmlable for CookieValue {
 }
 // endregion
 
+// region option
+impl Htmlable for EntryOption {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        add_line_terminators(&mut buffer, self.line_terminators.clone());
+        buffer.push_str("<span class=\"line\">");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str(format!("<span class=\"keyword\">{}</span>", self.kind.name()).as_str());
+        buffer.push_str(self.space1.to_html().as_str());
+        buffer.push_str("<span>:</span>");
+        buffer.push_str(self.space2.to_html().as_str());
+        buffer.push_str(self.kind.to_html().as_str());
+        buffer.push_str("</span>");
+        buffer.push_str(self.line_terminator0.to_html().as_str());
+        return buffer;
+    }
+}
+
+impl Htmlable for OptionKind {
+    fn to_html(&self) -> String {
+        return match self {
+            OptionKind::Insecure(value)
+            | OptionKind::FollowLocation(value)
+            | OptionKind::Verbose(value)
+            | OptionKind::Skip(value) => format!("<span class=\"boolean\">{}</span>", value),
+            OptionKind::Delay(value) => format!("<span class=\"number\">{}</span>", value),
+            OptionKind::Variable(value) => value.to_html(),
+        };
+    }
+}
+
+impl Htmlable for VariableDefinition {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        buffer.push_str(self.name.as_str());
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str("<span>=</span>");
+        buffer.push_str(self.space1.to_html().as_str());
+        buffer.push_str(self.value.to_html().as_str());
+        return buffer;
+    }
+}
+// endregion
+
 // region capture
 impl Htmlable for Capture {
     fn to_html(&self) -> String {
@@ -310,9 +421,65 @@ impl Htmlable for Capture {
 
 impl Htmlable for Query {
     fn to_html(&self) -> String {
-        return self.value.to_html();
+        let mut buffer = self.value.to_html();
+        for filter in self.filters.clone() {
+            buffer.push_str(filter.to_html().as_str());
+        }
+        return buffer;
+    }
+}
+
+impl Htmlable for Filter {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        buffer.push_str(self.space0.to_html().as_str());
+        match self.value.clone() {
+            FilterValue::Regex { space0, expr } => {
+                buffer.push_str("<span class=\"query-type\">regex</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(expr.to_html().as_str());
+            }
+            FilterValue::Count {} => {
+                buffer.push_str("<span class=\"query-type\">count</span>");
+            }
+            FilterValue::Nth { space0, n } => {
+                buffer.push_str("<span class=\"query-type\">nth</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", n).as_str());
+            }
+            FilterValue::ToInt {} => {
+                buffer.push_str("<span class=\"query-type\">toInt</span>");
+            }
+            FilterValue::Split { space0, sep } => {
+                buffer.push_str("<span class=\"query-type\">split</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(sep.to_html().as_str());
+            }
+            FilterValue::Replace { space0, old_value, space1, new_value } => {
+                buffer.push_str("<span class=\"query-type\">replace</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(old_value.to_html().as_str());
+                buffer.push_str(space1.to_html().as_str());
+                buffer.push_str(new_value.to_html().as_str());
+            }
+            FilterValue::Base64Decode {} => {
+                buffer.push_str("<span class=\"query-type\">base64Decode</span>");
+            }
+            FilterValue::ToDate { space0, format } => {
+                buffer.push_str("<span class=\"query-type\">toDate</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format.to_html().as_str());
+            }
+            FilterValue::Jsonpath { space0, expr } => {
+                buffer.push_str("<span class=\"query-type\">jsonpath</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(expr.to_html().as_str());
+            }
+        }
+        return buffer;
     }
 }
+
 impl Htmlable for QueryValue {
     fn to_html(&self) -> String {
         let mut buffer = String::from("");
@@ -320,11 +487,24 @@ impl Htmlable for QueryValue {
             QueryValue::Status {} => {
                 buffer.push_str("<span class=\"query-type\">status</span>");
             }
+            QueryValue::Url {} => {
+                buffer.push_str("<span class=\"query-type\">url</span>");
+            }
+            QueryValue::Method {} => {
+                buffer.push_str("<span class=\"query-type\">method</span>");
+            }
             QueryValue::Header { space0, name } => {
                 buffer.push_str("<span class=\"query-type\">header</span>");
                 buffer.push_str(space0.to_html().as_str());
                 buffer.push_str(name.to_html().as_str());
             }
+            QueryValue::RequestHeader { space0, space1, name } => {
+                buffer.push_str("<span class=\"query-type\">request</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str("<span class=\"query-type\">header</span>");
+                buffer.push_str(space1.to_html().as_str());
+                buffer.push_str(name.to_html().as_str());
+            }
             QueryValue::Cookie { space0, name } => {
                 buffer.push_str("<span class=\"query-type\">cookie</span>");
                 buffer.push_str(space0.to_html().as_str());
@@ -333,6 +513,15 @@ impl Htmlable for QueryValue {
             QueryValue::Body {} => {
                 buffer.push_str("<span class=\"query-type\">status</span>");
             }
+            QueryValue::Bytes {} => {
+                buffer.push_str("<span class=\"query-type\">bytes</span>");
+            }
+            QueryValue::Sha256 {} => {
+                buffer.push_str("<span class=\"query-type\">sha256</span>");
+            }
+            QueryValue::Md5 {} => {
+                buffer.push_str("<span class=\"query-type\">md5</span>");
+            }
             QueryValue::Xpath { space0, expr } => {
                 buffer.push_str("<span class=\"query-type\">xpath</span>");
                 buffer.push_str(space0.to_html().as_str());
@@ -348,6 +537,11 @@ impl Htmlable for QueryValue {
                 buffer.push_str(space0.to_html().as_str());
                 buffer.push_str(expr.to_html().as_str());
             }
+            QueryValue::Css { space0, expr } => {
+                buffer.push_str("<span class=\"query-type\">css</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(expr.to_html().as_str());
+            }
         }
 
         return buffer;
@@ -404,6 +598,11 @@ impl Htmlable for PredicateFuncValue {
                 buffer.push_str(space0.to_html().as_str());
                 buffer.push_str(format!("<span class=\"number\">{}</span>", value).as_str());
             }
+            PredicateFuncValue::CountEqualExpression { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">countEquals</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(value.to_html().as_str());
+            }
             PredicateFuncValue::EqualString {
                 space0: _,
                 value: _,
@@ -424,11 +623,25 @@ impl Htmlable for PredicateFuncValue {
                     .as_str(),
                 );
             }
+            PredicateFuncValue::ApproxEqual { space0, value, space1, space2, tolerance } => {
+                buffer.push_str("<span class=\"predicate-type\">approxEquals</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", value.to_string()).as_str());
+                buffer.push_str(space1.to_html().as_str());
+                buffer.push_str("<span class=\"keyword\">within</span>");
+                buffer.push_str(space2.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", tolerance.to_string()).as_str());
+            }
             PredicateFuncValue::EqualBool { space0, value } => {
                 buffer.push_str("<span class=\"predicate-type\">equals</span>");
                 buffer.push_str(space0.to_html().as_str());
                 buffer.push_str(format!("<span class=\"boolean\">{}</span>", value).as_str());
             }
+            PredicateFuncValue::EqualBytes { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">equals</span>");
+                buffer.push_str(space0.to_html().as_str());
+                add_predicate_bytes(&mut buffer, value);
+            }
             PredicateFuncValue::StartWith {
                 space0: _,
                 value: _,
@@ -441,11 +654,70 @@ impl Htmlable for PredicateFuncValue {
                 space0: _,
                 value: _,
             } => {}
+            PredicateFuncValue::IncludeString { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">includes</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"string\">{}</span>", value.to_html()).as_str());
+            }
+            PredicateFuncValue::IncludeInt { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">includes</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", value).as_str());
+            }
+            PredicateFuncValue::IncludeFloat { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">includes</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", value.to_string()).as_str());
+            }
+            PredicateFuncValue::IncludeBool { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">includes</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"boolean\">{}</span>", value).as_str());
+            }
+            PredicateFuncValue::AllMatch { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">allMatch</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(value.to_html().as_str());
+            }
+            PredicateFuncValue::AnyMatch { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">anyMatch</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(value.to_html().as_str());
+            }
             PredicateFuncValue::FirstEqualInt { space0, value } => {
                 buffer.push_str("<span class=\"predicate-type\">equals</span>");
                 buffer.push_str(space0.to_html().as_str());
                 buffer.push_str(format!("<span class=\"number\">{}</span>", value).as_str());
             }
+            PredicateFuncValue::FirstEqualFloat { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">equals</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", value.to_string()).as_str());
+            }
+            PredicateFuncValue::FirstApproxEqual { space0, value, space1, space2, tolerance } => {
+                buffer.push_str("<span class=\"predicate-type\">approxEquals</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", value.to_string()).as_str());
+                buffer.push_str(space1.to_html().as_str());
+                buffer.push_str("<span class=\"keyword\">within</span>");
+                buffer.push_str(space2.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", tolerance.to_string()).as_str());
+            }
+            PredicateFuncValue::ValidateSchema { space0, value } | PredicateFuncValue::FirstValidateSchema { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">validatesSchema</span>");
+                buffer.push_str(space0.to_html().as_str());
+                add_predicate_bytes(&mut buffer, value);
+            }
+            PredicateFuncValue::ValidateXsd { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">validatesXsd</span>");
+                buffer.push_str(space0.to_html().as_str());
+                add_predicate_bytes(&mut buffer, value);
+            }
+            PredicateFuncValue::ValidateDtd { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">validatesDtd</span>");
+                buffer.push_str(space0.to_html().as_str());
+                add_predicate_bytes(&mut buffer, value);
+            }
             PredicateFuncValue::FirstEqualBool { space0, value } => {
                 buffer.push_str("<span class=\"predicate-type\">equals</span>");
                 buffer.push_str(space0.to_html().as_str());
@@ -456,11 +728,34 @@ impl Htmlable for PredicateFuncValue {
                 buffer.push_str(space0.to_html().as_str());
                 buffer.push_str(format!("<span class=\"string\">{}</span>", value.to_html()).as_str());
             }
+            PredicateFuncValue::EqualJson { space0, value } | PredicateFuncValue::FirstEqualJson { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">equals</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"string\">{}</span>", value).as_str());
+            }
             PredicateFuncValue::FirstCountEqual { space0, value } => {
                 buffer.push_str("<span class=\"predicate-type\">countEquals</span>");
                 buffer.push_str(space0.to_html().as_str());
                 buffer.push_str(format!("<span class=\"number\">{}</span>", value).as_str());
             }
+            PredicateFuncValue::FirstCountEqualExpression { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">countEquals</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(value.to_html().as_str());
+            }
+            PredicateFuncValue::IsIsoDate {} | PredicateFuncValue::FirstIsIsoDate {} => {
+                buffer.push_str("<span class=\"predicate-type\">isIsoDate</span>");
+            }
+            PredicateFuncValue::Before { space0, value } | PredicateFuncValue::FirstBefore { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">before</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(value.to_html().as_str());
+            }
+            PredicateFuncValue::After { space0, value } | PredicateFuncValue::FirstAfter { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">after</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(value.to_html().as_str());
+            }
             PredicateFuncValue::Exist { } => {
                 buffer.push_str("<span class=\"predicate-type\">exists</span>");
             }
@@ -468,6 +763,28 @@ impl Htmlable for PredicateFuncValue {
         return buffer;
     }
 }
+
+impl Htmlable for DateValue {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        match self {
+            DateValue::Template { value } => {
+                buffer.push_str(format!("<span class=\"string\">{}</span>", value.to_html()).as_str());
+            }
+            DateValue::Now { offset } => {
+                buffer.push_str("<span class=\"keyword\">now</span>");
+                if let Some(DateOffset { space0, negative, space1, value, unit }) = offset {
+                    buffer.push_str(space0.to_html().as_str());
+                    buffer.push_str(format!("<span class=\"keyword\">{}</span>", if *negative { "-" } else { "+" }).as_str());
+                    buffer.push_str(space1.to_html().as_str());
+                    buffer.push_str(format!("<span class=\"number\">{}</span>", value).as_str());
+                    buffer.push_str(format!("<span class=\"keyword\">{}</span>", unit.to_string()).as_str());
+                }
+            }
+        }
+        return buffer;
+    }
+}
 // endregion
 
 // region whitespace
@@ -562,8 +879,55 @@ impl Htmlable for HurlTemplateElement {
 // region expr
 impl Htmlable for Expr {
     fn to_html(&self) -> String {
-        return format!("<span class=\"variable\">{}</span>", self.variable.name)
+        let mut buffer = format!("<span class=\"variable\">{}</span>", self.variable.name);
+        for argument in self.arguments.clone() {
+            buffer.push_str(argument.to_html().as_str());
+        }
+        for filter in self.filters.clone() {
+            buffer.push_str(filter.to_html().as_str());
+        }
+        return buffer;
+    }
+}
 
+impl Htmlable for ExprArgument {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str(self.value.to_html().as_str());
+        return buffer;
+    }
+}
+
+impl Htmlable for ExprArgumentValue {
+    fn to_html(&self) -> String {
+        return match self {
+            ExprArgumentValue::String(value) => value.to_html(),
+            ExprArgumentValue::Integer { value, .. } => format!("<span class=\"number\">{}</span>", value),
+        };
+    }
+}
+
+impl Htmlable for ExprFilter {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str("<span class=\"keyword\">|</span>");
+        buffer.push_str(self.space1.to_html().as_str());
+        match self.value.clone() {
+            ExprFilterValue::Default { space0, space1, value } => {
+                buffer.push_str("<span class=\"keyword\">default</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str("<span>:</span>");
+                buffer.push_str(space1.to_html().as_str());
+                buffer.push_str(value.to_html().as_str());
+            }
+            ExprFilterValue::UrlEncode {} => buffer.push_str("<span class=\"keyword\">urlEncode</span>"),
+            ExprFilterValue::Base64Encode {} => buffer.push_str("<span class=\"keyword\">base64Encode</span>"),
+            ExprFilterValue::Upper {} => buffer.push_str("<span class=\"keyword\">upper</span>"),
+            ExprFilterValue::JsonEncode {} => buffer.push_str("<span class=\"keyword\">jsonEncode</span>"),
+        }
+        return buffer;
     }
 }
 // endregion
@@ -573,6 +937,27 @@ fn to_line(v: String) -> String {
     format!("<span class=\"line\">{}</span>", v)
 }
 
+// file or base64 predicate value
+fn add_predicate_bytes(buffer: &mut String, value: &Bytes) {
+    match value {
+        Bytes::File { space0, filename, space1 } => {
+            buffer.push_str("<span class=\"keyword\">file,</span>");
+            buffer.push_str(space0.to_html().as_str());
+            buffer.push_str(format!("<span class=\"filename\">{}</span>", filename.value).as_str());
+            buffer.push_str(space1.to_html().as_str());
+            buffer.push_str("<span class=\"keyword\">;</span>");
+        }
+        Bytes::Base64 { space0, encoded, space1, .. } => {
+            buffer.push_str("<span class=\"keyword\">base64,</span>");
+            buffer.push_str(space0.to_html().as_str());
+            buffer.push_str(format!("<span class=\"string\">{}</span>", encoded).as_str());
+            buffer.push_str(space1.to_html().as_str());
+            buffer.push_str("<span class=\"keyword\">;</span>");
+        }
+        _ => {}
+    }
+}
+
 fn add_line_terminators(buffer: &mut String, line_terminators: Vec<LineTerminator>) {
     for line_terminator in line_terminators.clone() {
         buffer.push_str(to_line(line_terminator.to_html()).as_str());
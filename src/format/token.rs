@@ -43,7 +43,7 @@ impl Tokenizable for HurlFile {
         let mut tokens: Vec<Token> = vec![];
         add_tokens(
             &mut tokens,
-            self.entries.iter().flat_map(|e| e.tokenize()).collect(),
+            self.statements.iter().flat_map(|e| e.tokenize()).collect(),
         );
         add_tokens(
             &mut tokens,
@@ -57,6 +57,38 @@ impl Tokenizable for HurlFile {
 }
 // endregion
 
+// region statement
+impl Tokenizable for Statement {
+    fn tokenize(&self) -> Vec<Token> {
+        return match self {
+            Statement::Entry(entry) => entry.tokenize(),
+            Statement::Include(include) => include.tokenize(),
+        };
+    }
+}
+// endregion
+
+// region include
+impl Tokenizable for Include {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(
+            &mut tokens,
+            self.line_terminators
+                .iter()
+                .flat_map(|e| e.tokenize())
+                .collect(),
+        );
+        add_tokens(&mut tokens, self.space0.tokenize());
+        tokens.push(Token::Keyword(String::from("include")));
+        add_tokens(&mut tokens, self.space1.tokenize());
+        add_tokens(&mut tokens, self.filename.tokenize());
+        add_tokens(&mut tokens, self.line_terminator0.tokenize());
+        return tokens;
+    }
+}
+// endregion
+
 // region entry
 impl Tokenizable for Entry {
     fn tokenize(&self) -> Vec<Token> {
@@ -176,6 +208,34 @@ impl Tokenizable for Header {
         return tokens;
     }
 }
+
+impl Tokenizable for ResponseHeader {
+    fn tokenize(&self) -> Vec<Token> {
+        return match self {
+            ResponseHeader::Value(header) => header.tokenize(),
+            ResponseHeader::Assert(header_assert) => header_assert.tokenize(),
+        };
+    }
+}
+
+impl Tokenizable for HeaderAssert {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(
+            &mut tokens,
+            self.line_terminators
+                .iter()
+                .flat_map(|e| e.tokenize())
+                .collect(),
+        );
+        add_tokens(&mut tokens, self.space0.tokenize());
+        add_tokens(&mut tokens, self.name.tokenize());
+        add_tokens(&mut tokens, self.space1.tokenize());
+        add_tokens(&mut tokens, self.predicate.tokenize());
+        add_tokens(&mut tokens, self.line_terminator0.tokenize());
+        return tokens;
+    }
+}
 // endregion
 
 // region body
@@ -302,6 +362,18 @@ impl Tokenizable for SectionValue {
                     items.iter().flat_map(|e| e.tokenize()).collect(),
                 );
             }
+            SectionValue::Namespaces(items) => {
+                add_tokens(
+                    &mut tokens,
+                    items.iter().flat_map(|e| e.tokenize()).collect(),
+                );
+            }
+            SectionValue::Options(items) => {
+                add_tokens(
+                    &mut tokens,
+                    items.iter().flat_map(|e| e.tokenize()).collect(),
+                );
+            }
         }
         return tokens;
     }
@@ -420,6 +492,53 @@ impl Tokenizable for CookieValue {
 }
 // endregion
 
+// region option
+impl Tokenizable for EntryOption {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(
+            &mut tokens,
+            self.line_terminators
+                .iter()
+                .flat_map(|e| e.tokenize())
+                .collect(),
+        );
+        add_tokens(&mut tokens, self.space0.tokenize());
+        tokens.push(Token::String(self.kind.name().to_string()));
+        add_tokens(&mut tokens, self.space1.tokenize());
+        tokens.push(Token::Colon(String::from(":")));
+        add_tokens(&mut tokens, self.space2.tokenize());
+        add_tokens(&mut tokens, self.kind.tokenize());
+        add_tokens(&mut tokens, self.line_terminator0.tokenize());
+        return tokens;
+    }
+}
+
+impl Tokenizable for OptionKind {
+    fn tokenize(&self) -> Vec<Token> {
+        return match self {
+            OptionKind::Insecure(value)
+            | OptionKind::FollowLocation(value)
+            | OptionKind::Verbose(value)
+            | OptionKind::Skip(value) => vec![Token::Boolean(value.to_string())],
+            OptionKind::Delay(value) => vec![Token::Number(value.to_string())],
+            OptionKind::Variable(value) => value.tokenize(),
+        };
+    }
+}
+
+impl Tokenizable for VariableDefinition {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![Token::String(self.name.clone())];
+        add_tokens(&mut tokens, self.space0.tokenize());
+        tokens.push(Token::Keyword(String::from("=")));
+        add_tokens(&mut tokens, self.space1.tokenize());
+        add_tokens(&mut tokens, self.value.tokenize());
+        return tokens;
+    }
+}
+// endregion
+
 // region capture
 impl Tokenizable for Capture {
     fn tokenize(&self) -> Vec<Token> {
@@ -470,17 +589,29 @@ impl Tokenizable for Query {
         let mut tokens: Vec<Token> = vec![];
         match self.value.clone() {
             QueryValue::Status {} => tokens.push(Token::QueryType(String::from("status"))),
+            QueryValue::Url {} => tokens.push(Token::QueryType(String::from("url"))),
+            QueryValue::Method {} => tokens.push(Token::QueryType(String::from("method"))),
             QueryValue::Header { space0, name } => {
                 tokens.push(Token::QueryType(String::from("header")));
                 add_tokens(&mut tokens, space0.tokenize());
                 add_tokens(&mut tokens, name.tokenize());
             }
+            QueryValue::RequestHeader { space0, space1, name } => {
+                tokens.push(Token::QueryType(String::from("request")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::QueryType(String::from("header")));
+                add_tokens(&mut tokens, space1.tokenize());
+                add_tokens(&mut tokens, name.tokenize());
+            }
             QueryValue::Cookie { space0, name } => {
                 tokens.push(Token::QueryType(String::from("cookie")));
                 add_tokens(&mut tokens, space0.tokenize());
                 add_tokens(&mut tokens, name.tokenize());
             }
             QueryValue::Body {} => tokens.push(Token::QueryType(String::from("body"))),
+            QueryValue::Bytes {} => tokens.push(Token::QueryType(String::from("bytes"))),
+            QueryValue::Sha256 {} => tokens.push(Token::QueryType(String::from("sha256"))),
+            QueryValue::Md5 {} => tokens.push(Token::QueryType(String::from("md5"))),
             QueryValue::Xpath { space0, expr } => {
                 tokens.push(Token::QueryType(String::from("xpath")));
                 add_tokens(&mut tokens, space0.tokenize());
@@ -496,6 +627,57 @@ impl Tokenizable for Query {
                 add_tokens(&mut tokens, space0.tokenize());
                 add_tokens(&mut tokens, expr.tokenize());
             }
+            QueryValue::Css { space0, expr } => {
+                tokens.push(Token::QueryType(String::from("css")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, expr.tokenize());
+            }
+        }
+        add_tokens(&mut tokens, self.filters.iter().flat_map(|e| e.tokenize()).collect());
+        return tokens;
+    }
+}
+
+impl Tokenizable for Filter {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(&mut tokens, self.space0.tokenize());
+        match self.value.clone() {
+            FilterValue::Regex { space0, expr } => {
+                tokens.push(Token::QueryType(String::from("regex")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, expr.tokenize());
+            }
+            FilterValue::Count {} => tokens.push(Token::QueryType(String::from("count"))),
+            FilterValue::Nth { space0, n } => {
+                tokens.push(Token::QueryType(String::from("nth")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Number(n.to_string()));
+            }
+            FilterValue::ToInt {} => tokens.push(Token::QueryType(String::from("toInt"))),
+            FilterValue::Split { space0, sep } => {
+                tokens.push(Token::QueryType(String::from("split")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, sep.tokenize());
+            }
+            FilterValue::Replace { space0, old_value, space1, new_value } => {
+                tokens.push(Token::QueryType(String::from("replace")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, old_value.tokenize());
+                add_tokens(&mut tokens, space1.tokenize());
+                add_tokens(&mut tokens, new_value.tokenize());
+            }
+            FilterValue::Base64Decode {} => tokens.push(Token::QueryType(String::from("base64Decode"))),
+            FilterValue::ToDate { space0, format } => {
+                tokens.push(Token::QueryType(String::from("toDate")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, format.tokenize());
+            }
+            FilterValue::Jsonpath { space0, expr } => {
+                tokens.push(Token::QueryType(String::from("jsonpath")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, expr.tokenize());
+            }
         }
         return tokens;
     }
@@ -559,6 +741,11 @@ impl Tokenizable for PredicateFuncValue {
                 add_tokens(&mut tokens, space0.tokenize());
                 tokens.push(Token::Boolean(value.to_string()));
             }
+            PredicateFuncValue::CountEqualExpression { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("countEquals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
             PredicateFuncValue::StartWith { space0, value } => {
                 tokens.push(Token::PredicateType(String::from("startsWith")));
                 add_tokens(&mut tokens, space0.tokenize());
@@ -574,6 +761,70 @@ impl Tokenizable for PredicateFuncValue {
                 add_tokens(&mut tokens, space0.tokenize());
                 add_tokens(&mut tokens, value.tokenize());
             }
+            PredicateFuncValue::ApproxEqual { space0, value, space1, space2, tolerance } => {
+                tokens.push(Token::PredicateType(String::from("approxEquals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Number(value.to_string()));
+                add_tokens(&mut tokens, space1.tokenize());
+                tokens.push(Token::Keyword(String::from("within")));
+                add_tokens(&mut tokens, space2.tokenize());
+                tokens.push(Token::Number(tolerance.to_string()));
+            }
+            PredicateFuncValue::EqualBytes { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("equals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::ValidateSchema { space0, value } | PredicateFuncValue::FirstValidateSchema { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("validatesSchema")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::ValidateXsd { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("validatesXsd")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::ValidateDtd { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("validatesDtd")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::EqualJson { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("equals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::String(value.to_string()));
+            }
+            PredicateFuncValue::IncludeString { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("includes")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::IncludeInt { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("includes")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Number(value.to_string()));
+            }
+            PredicateFuncValue::IncludeFloat { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("includes")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Number(value.to_string()));
+            }
+            PredicateFuncValue::IncludeBool { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("includes")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Boolean(value.to_string()));
+            }
+            PredicateFuncValue::AllMatch { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("allMatch")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::AnyMatch { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("anyMatch")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
             // used by jsonpath only
             PredicateFuncValue::FirstEqualBool{ space0, value } => {
                 tokens.push(Token::PredicateType(String::from("equals")));
@@ -590,11 +841,48 @@ impl Tokenizable for PredicateFuncValue {
                 add_tokens(&mut tokens, space0.tokenize());
                 add_tokens(&mut tokens, value.tokenize());
             }
+            PredicateFuncValue::FirstEqualJson { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("equals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::String(value.to_string()));
+            }
+            PredicateFuncValue::FirstEqualFloat { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("equals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Number(value.to_string()));
+            }
+            PredicateFuncValue::FirstApproxEqual { space0, value, space1, space2, tolerance } => {
+                tokens.push(Token::PredicateType(String::from("approxEquals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Number(value.to_string()));
+                add_tokens(&mut tokens, space1.tokenize());
+                tokens.push(Token::Keyword(String::from("within")));
+                add_tokens(&mut tokens, space2.tokenize());
+                tokens.push(Token::Number(tolerance.to_string()));
+            }
             PredicateFuncValue::FirstCountEqual { space0, value } => {
                 tokens.push(Token::PredicateType(String::from("countEquals")));
                 add_tokens(&mut tokens, space0.tokenize());
                 tokens.push(Token::Number(value.to_string()));
             }
+            PredicateFuncValue::FirstCountEqualExpression { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("countEquals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::IsIsoDate {} | PredicateFuncValue::FirstIsIsoDate {} => {
+                tokens.push(Token::PredicateType(String::from("isIsoDate")));
+            }
+            PredicateFuncValue::Before { space0, value } | PredicateFuncValue::FirstBefore { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("before")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::After { space0, value } | PredicateFuncValue::FirstAfter { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("after")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
             PredicateFuncValue::Exist{} => {
                 tokens.push(Token::PredicateType(String::from("exists")));
             }
@@ -602,6 +890,26 @@ impl Tokenizable for PredicateFuncValue {
         return tokens;
     }
 }
+
+impl Tokenizable for DateValue {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        match self {
+            DateValue::Template { value } => add_tokens(&mut tokens, value.tokenize()),
+            DateValue::Now { offset } => {
+                tokens.push(Token::Keyword(String::from("now")));
+                if let Some(DateOffset { space0, negative, space1, value, unit }) = offset {
+                    add_tokens(&mut tokens, space0.tokenize());
+                    tokens.push(Token::Keyword(String::from(if *negative { "-" } else { "+" })));
+                    add_tokens(&mut tokens, space1.tokenize());
+                    tokens.push(Token::Number(value.to_string()));
+                    tokens.push(Token::Keyword(unit.to_string()));
+                }
+            }
+        }
+        return tokens;
+    }
+}
 // endregion
 
 // region hurl-string
@@ -676,6 +984,8 @@ fn test_hurl_template() {
                         name: String::from("name"),
                         source_info: SourceInfo::init(0, 0, 0, 0),
                     },
+                    arguments: vec![],
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::from(""),
                         source_info: SourceInfo::init(0, 0, 0, 0),
@@ -740,11 +1050,58 @@ impl Tokenizable for Expr {
         tokens.push(Token::CodeDelimiter(String::from("{{")));
         add_tokens(&mut tokens, self.space0.tokenize());
         tokens.push(Token::CodeVariable(self.variable.name.clone()));
+        for argument in self.arguments.clone() {
+            add_tokens(&mut tokens, argument.tokenize());
+        }
+        for filter in self.filters.clone() {
+            add_tokens(&mut tokens, filter.tokenize());
+        }
         add_tokens(&mut tokens, self.space1.tokenize());
         tokens.push(Token::CodeDelimiter(String::from("}}")));
         return tokens;
     }
 }
+
+impl Tokenizable for ExprFilter {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(&mut tokens, self.space0.tokenize());
+        tokens.push(Token::CodeDelimiter(String::from("|")));
+        add_tokens(&mut tokens, self.space1.tokenize());
+        match self.value.clone() {
+            ExprFilterValue::Default { space0, space1, value } => {
+                tokens.push(Token::Keyword(String::from("default")));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Colon(String::from(":")));
+                add_tokens(&mut tokens, space1.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            ExprFilterValue::UrlEncode {} => tokens.push(Token::Keyword(String::from("urlEncode"))),
+            ExprFilterValue::Base64Encode {} => tokens.push(Token::Keyword(String::from("base64Encode"))),
+            ExprFilterValue::Upper {} => tokens.push(Token::Keyword(String::from("upper"))),
+            ExprFilterValue::JsonEncode {} => tokens.push(Token::Keyword(String::from("jsonEncode"))),
+        }
+        return tokens;
+    }
+}
+
+impl Tokenizable for ExprArgument {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(&mut tokens, self.space0.tokenize());
+        add_tokens(&mut tokens, self.value.tokenize());
+        return tokens;
+    }
+}
+
+impl Tokenizable for ExprArgumentValue {
+    fn tokenize(&self) -> Vec<Token> {
+        return match self {
+            ExprArgumentValue::String(value) => value.tokenize(),
+            ExprArgumentValue::Integer { value, .. } => vec![Token::Number(value.to_string())],
+        };
+    }
+}
 // endregion
 
 // region line-terminator
@@ -7,13 +7,14 @@ use super::response::*;
 
 pub struct Client {
     inner_client: reqwest::Client,
-    _options: ClientOptions,
+    pub options: ClientOptions,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ClientOptions {
     pub noproxy_hosts: Vec<String>,
     pub insecure: bool,
+    pub follow_location: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -32,8 +33,13 @@ impl Client {
 //headers.insert(reqwest::header::USER_AGENT, reqwest::header::HeaderValue::from_static(""));
 //eprintln!("{}", clap::crate_version!());
 
+        let redirect_policy = if options.follow_location {
+            reqwest::RedirectPolicy::default()
+        } else {
+            reqwest::RedirectPolicy::none()
+        };
         let client_builder = reqwest::Client::builder()
-            .redirect(reqwest::RedirectPolicy::none())
+            .redirect(redirect_policy)
 //.default_headers(headers)
             .use_sys_proxy()
             .danger_accept_invalid_hostnames(options.insecure)
@@ -42,7 +48,7 @@ impl Client {
             .cookie_store(false);
         return Client {
             inner_client: client_builder.build().unwrap(),
-            _options: options,
+            options,
         };
     }
 
@@ -133,6 +139,7 @@ impl Client {
                 return Ok(Response {
                     version,
                     status: resp.status().as_u16(),
+                    url: resp.url().to_string(),
                     headers,
                     body: buf,
                 });
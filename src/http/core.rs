@@ -26,6 +26,8 @@ pub struct Url {
     pub host: String,
     pub port: Option<u16>,
     pub path: String,
+    // the query string of the url, as given (not decoded)
+    pub querystring: Option<String>,
 }
 
 impl Url {
@@ -72,6 +74,7 @@ pub fn hello_url() -> Url {
         host: String::from("localhost"),
         port: Some(8000),
         path: String::from("/hello"),
+        querystring: None,
     }
 }
 
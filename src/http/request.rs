@@ -46,10 +46,11 @@ impl Request {
             None => String::from(""),
             Some(p) => format!(":{}", p)
         };
-        let querystring = if self.querystring.is_empty() {
+        // the query string of the url is kept as is, only the params are encoded
+        let querystring = if self.querystring.is_empty() && self.url.querystring.is_none() {
             String::from("")
         } else {
-            let mut buf = String::from("");
+            let mut buf = self.url.querystring.clone().unwrap_or_default();
             for param in self.querystring {
                 if !buf.is_empty() {
                     buf.push('&');
@@ -68,6 +69,18 @@ impl Request {
         );
     }
 
+    // the params of the url query string (decoded) and the querystring params
+    pub fn querystring_params(&self) -> Vec<Param> {
+        let mut params = vec![];
+        if let Some(querystring) = &self.url.querystring {
+            for (name, value) in ::url::form_urlencoded::parse(querystring.as_bytes()) {
+                params.push(Param { name: name.to_string(), value: value.to_string() });
+            }
+        }
+        params.extend(self.querystring.clone());
+        return params;
+    }
+
     pub fn headers(self) -> Vec<Header> {
         let mut headers: Vec<Header> = self.headers.clone();
         let user_agent = format!("hurl/{}", clap::crate_version!());
@@ -95,6 +108,18 @@ impl Request {
         return headers;
     }
 
+    // headers actually sent (including default ones)
+    pub fn get_header(&self, name: &str, case_sensitive: bool) -> Option<String> {
+        for header in self.clone().headers() {
+            if header.name == name
+                || !case_sensitive && header.name.to_lowercase() == name.to_lowercase()
+            {
+                return Some(header.value);
+            }
+        }
+        return None;
+    }
+
     pub fn content_type(self) -> Option<String> {
         for Header { name, value } in self.headers {
             if name == String::from("Content-Type") {
@@ -173,6 +198,7 @@ pub fn hello_http_request() -> Request {
             host: "localhost".to_string(),
             port: Some(8000),
             path: "/hello".to_string(),
+            querystring: None,
         },
         querystring: vec![],
         headers: vec![],
@@ -192,6 +218,7 @@ pub fn query_http_request() -> Request {
             host: "localhost".to_string(),
             port: Some(8000),
             path: "/querystring-params".to_string(),
+            querystring: None,
         },
 //String::from("http://localhost:8000/querystring-params"),
 //        querystring_params: vec![
@@ -221,6 +248,7 @@ pub fn custom_http_request() -> Request {
             host: "localhost".to_string(),
             port: None,
             path: "/custom".to_string(),
+            querystring: None,
         },
         querystring: vec![],
         headers: vec![
@@ -267,6 +295,7 @@ pub fn form_http_request() -> Request {
             host: "localhost".to_string(),
             port: None,
             path: "/form-params".to_string(),
+            querystring: None,
         },
         querystring: vec![],
         headers: vec![
@@ -341,6 +370,16 @@ pub fn test_headers() {
 pub fn test_url() {
     assert_eq!(hello_http_request().url(), String::from("http://localhost:8000/hello"));
     assert_eq!(query_http_request().url(), String::from("http://localhost:8000/querystring-params?param1=value1&param2=a%20b"));
+
+    // the query string of the url is sent as is
+    let mut request = query_http_request();
+    request.url.querystring = Some(String::from("q=a%26b"));
+    request.querystring = vec![Param { name: String::from("lang"), value: String::from("a%20b") }];
+    assert_eq!(request.clone().url(), String::from("http://localhost:8000/querystring-params?q=a%26b&lang=a%20b"));
+    assert_eq!(request.querystring_params(), vec![
+        Param { name: String::from("q"), value: String::from("a&b") },
+        Param { name: String::from("lang"), value: String::from("a%20b") },
+    ]);
 }
 
 
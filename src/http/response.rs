@@ -1,3 +1,7 @@
+use encoding_rs::{Encoding, UTF_8};
+use lazy_static::lazy_static;
+use regex::bytes::Regex;
+
 use super::cookie::*;
 use super::core::*;
 
@@ -5,6 +9,8 @@ use super::core::*;
 pub struct Response {
     pub version: Version,
     pub status: u16,
+    // the last url requested (when redirects are followed)
+    pub url: String,
     pub headers: Vec<Header>,
     pub body: Vec<u8>,
 }
@@ -29,6 +35,13 @@ impl Response {
         return None;
     }
 
+    pub fn get_headers(&self, name: &str) -> Vec<String> {
+        return self.headers.iter()
+            .filter(|header| header.name.to_lowercase() == name.to_lowercase())
+            .map(|header| header.value.clone())
+            .collect();
+    }
+
     pub fn get_cookie(&self, name: &str) -> Option<String> {
         for cookie in self.cookies() {
             if cookie.name == name.to_string()
@@ -74,19 +87,50 @@ impl Response {
 
 
 impl Response {
-    pub fn has_utf8_body(&self) -> bool {
-        return match self.get_header("content-type", false) {
-            Some(s) => {
-                if s.contains("charset=utf-8") {
-                    true
-                } else {
-                    false
-                }
-            }
-            _ => false
+    // charset of the body, given by (in order)
+    // - a byte order mark
+    // - the charset parameter of the Content-Type header
+    // - the xml prolog or the html meta tag
+    // text and json bodies without charset are considered utf-8
+    // None for binary (or unknown) content types
+    pub fn encoding(&self) -> Option<&'static Encoding> {
+        match Encoding::for_bom(self.body.as_slice()) {
+            Some((encoding, _)) => return Some(encoding),
+            None => {}
+        }
+        let content_type = match self.get_header("content-type", false) {
+            None => return None,
+            Some(s) => s.to_lowercase(),
         };
+        match charset_parameter(content_type.as_str()).and_then(|label| Encoding::for_label(label.as_bytes())) {
+            Some(encoding) => return Some(encoding),
+            None => {}
+        }
+        if content_type.contains("xml") || content_type.contains("html") {
+            match declared_charset(self.body.as_slice()).and_then(|label| Encoding::for_label(label.as_bytes())) {
+                Some(encoding) => return Some(encoding),
+                None => {}
+            }
+        }
+        if content_type.starts_with("text/")
+            || content_type.contains("json")
+            || content_type.contains("xml")
+            || content_type.contains("javascript") {
+            return Some(UTF_8);
+        }
+        return None;
     }
 
+    // body decoded with its charset (utf-8 if unknown)
+    // None if the body is not valid for this charset
+    pub fn text(&self) -> Option<String> {
+        let encoding = self.encoding().unwrap_or(UTF_8);
+        let (text, _, has_errors) = encoding.decode(self.body.as_slice());
+        if has_errors {
+            return None;
+        }
+        return Some(text.to_string());
+    }
 
     pub fn is_html(&self) -> bool {
         return match self.get_header("content-type", false) {
@@ -102,6 +146,72 @@ impl Response {
     }
 }
 
+// text/html; charset="ISO-8859-1"
+fn charset_parameter(content_type: &str) -> Option<String> {
+    for parameter in content_type.split(';').skip(1) {
+        if let Some(value) = parameter.trim().strip_prefix("charset=") {
+            return Some(value.trim_matches(|c| c == '"' || c == '\'').to_string());
+        }
+    }
+    return None;
+}
+
+// <?xml version="1.0" encoding="ISO-8859-1"?>
+// <meta charset="windows-1252"> or <meta http-equiv="Content-Type" content="text/html; charset=windows-1252">
+// only the beginning of the body is read
+fn declared_charset(body: &[u8]) -> Option<String> {
+    lazy_static! {
+        static ref XML_ENCODING: Regex = Regex::new(r#"^\s*<\?xml[^>]*\sencoding\s*=\s*["']([A-Za-z0-9._:-]+)["']"#).unwrap();
+        static ref META_CHARSET: Regex = Regex::new(r#"(?i)<meta[^>]*charset\s*=\s*["']?([A-Za-z0-9._:-]+)"#).unwrap();
+    }
+    let head = &body[..std::cmp::min(body.len(), 1024)];
+    let captures = match XML_ENCODING.captures(head) {
+        Some(captures) => captures,
+        None => META_CHARSET.captures(head)?,
+    };
+    return Some(String::from_utf8_lossy(&captures[1]).to_string());
+}
+
+#[test]
+fn test_encoding() {
+    let response = |content_type: Option<&str>, body: &[u8]| Response {
+        version: Version::Http10,
+        status: 200,
+        url: String::from("http://localhost:8000/"),
+        headers: match content_type {
+            None => vec![],
+            Some(value) => vec![Header { name: String::from("Content-Type"), value: value.to_string() }],
+        },
+        body: body.to_vec(),
+    };
+    assert_eq!(response(Some("text/html; charset=UTF-8"), b"").encoding(), Some(UTF_8));
+    assert_eq!(response(Some("text/plain; Charset=\"iso-8859-1\""), b"").encoding().unwrap().name(), "windows-1252");
+    assert_eq!(response(Some("text/plain; charset=utf-16le"), b"").encoding().unwrap().name(), "UTF-16LE");
+    assert_eq!(response(Some("application/xml"), b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<a/>").encoding().unwrap().name(), "windows-1252");
+    assert_eq!(response(Some("text/html"), b"<html><head><META http-equiv=\"Content-Type\" content=\"text/html; charset=windows-1252\">").encoding().unwrap().name(), "windows-1252");
+    assert_eq!(response(Some("text/html"), b"<html><head><meta charset=\"latin1\">").encoding().unwrap().name(), "windows-1252");
+    assert_eq!(response(Some("application/json"), b"{}").encoding(), Some(UTF_8));
+    assert_eq!(response(Some("application/octet-stream"), b"").encoding(), None);
+    assert_eq!(response(None, b"").encoding(), None);
+    assert_eq!(response(Some("application/octet-stream"), b"\xff\xfeh\x00").encoding().unwrap().name(), "UTF-16LE");
+}
+
+#[test]
+fn test_text() {
+    let response = |content_type: &str, body: &[u8]| Response {
+        version: Version::Http10,
+        status: 200,
+        url: String::from("http://localhost:8000/"),
+        headers: vec![Header { name: String::from("Content-Type"), value: content_type.to_string() }],
+        body: body.to_vec(),
+    };
+    assert_eq!(response("text/plain; charset=ISO-8859-1", b"caf\xe9").text().unwrap(), "café");
+    assert_eq!(response("text/plain; charset=windows-1252", b"\x80").text().unwrap(), "€");
+    assert_eq!(response("text/plain; charset=UTF-16", b"c\x00a\x00f\x00\xe9\x00").text().unwrap(), "café");
+    assert_eq!(response("text/plain; charset=utf-16be", b"\xfe\xff\x00c\x00a\x00f\x00\xe9").text().unwrap(), "café");
+    assert_eq!(response("application/json", b"{\"name\":\"caf\xc3\xa9\"}").text().unwrap(), "{\"name\":\"café\"}");
+    assert_eq!(response("text/plain", b"caf\xe9").text(), None);
+}
 
 // region test
 
@@ -110,6 +220,7 @@ pub fn hello_http_response() -> Response {
     return Response {
         version: Version::Http10,
         status: 200,
+        url: String::from("http://localhost:8000/hello"),
         headers: vec![
             Header { name: String::from("Content-Type"), value: String::from("text/html; charset=utf-8") },
             Header { name: String::from("Content-Length"), value: String::from("12") },
@@ -123,6 +234,7 @@ pub fn html_http_response() -> Response {
     return Response {
         version: Version::Http10,
         status: 200,
+        url: String::from("http://localhost:8000/html"),
         headers: vec![
             Header { name: String::from("Content-Type"), value: String::from("text/html; charset=utf-8") },
         ],
@@ -136,6 +248,7 @@ pub fn xml_invalid_response() -> Response {
     return Response {
         version: Version::Http10,
         status: 200,
+        url: String::from("http://localhost:8000/xml-invalid"),
         headers: vec![
             Header { name: String::from("Content-Type"), value: String::from("text/html; charset=utf-8") },
             Header { name: String::from("Content-Length"), value: String::from("12") },
@@ -151,6 +264,7 @@ pub fn xml_two_users_http_response() -> Response {
     return Response {
         version: Version::Http10,
         status: 200,
+        url: String::from("http://localhost:8000/xml-two-users"),
         headers: vec![
             Header { name: String::from("Content-Type"), value: String::from("text/html; charset=utf-8") },
             Header { name: String::from("Content-Length"), value: String::from("12") },
@@ -170,6 +284,7 @@ pub fn xml_three_users_http_response() -> Response {
     return Response {
         version: Version::Http10,
         status: 200,
+        url: String::from("http://localhost:8000/xml-three-users"),
         headers: vec![
             Header { name: String::from("Content-Type"), value: String::from("text/html; charset=utf-8") },
             Header { name: String::from("Content-Length"), value: String::from("12") },
@@ -190,6 +305,7 @@ pub fn json_http_response() -> Response {
     return Response {
         version: Version::Http10,
         status: 0,
+        url: String::from("http://localhost:8000/json"),
         headers: vec![],
         body: String::into_bytes(r#"
 {
@@ -209,6 +325,7 @@ pub fn bytes_http_response() -> Response {
     return Response {
         version: Version::Http10,
         status: 200,
+        url: String::from("http://localhost:8000/bytes"),
         headers: vec![
             Header { name: String::from("Content-Type"), value: String::from("application/octet-stream") },
             Header { name: String::from("Content-Length"), value: String::from("1") },
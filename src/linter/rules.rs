@@ -13,15 +13,15 @@ use super::core::{Error, Lintable, LinterError};
 impl Lintable<HurlFile> for HurlFile {
     fn errors(&self) -> Vec<Error> {
         let mut errors = vec![];
-        for entry in self.entries.clone() {
-            errors.append(&mut (entry.errors()));
+        for statement in self.statements.clone() {
+            errors.append(&mut (statement.errors()));
         }
         return errors;
     }
 
     fn lint(&self) -> HurlFile {
         return HurlFile {
-            entries: self.entries.iter().map(|e| e.lint()).collect(),
+            statements: self.statements.iter().map(|e| e.lint()).collect(),
             line_terminators: self.line_terminators.clone(),
         };
     }
@@ -30,11 +30,11 @@ impl Lintable<HurlFile> for HurlFile {
 #[test]
 fn test_hurl_file() {
     let hurl_file = HurlFile {
-        entries: vec![],
+        statements: vec![],
         line_terminators: vec![],
     };
     let hurl_file_linted = HurlFile {
-        entries: vec![],
+        statements: vec![],
         line_terminators: vec![],
     };
     assert_eq!(hurl_file.errors(), vec![]);
@@ -43,6 +43,62 @@ fn test_hurl_file() {
 
 // endregion
 
+// region statement
+
+impl Lintable<Statement> for Statement {
+    fn errors(&self) -> Vec<Error> {
+        return match self {
+            Statement::Entry(entry) => entry.errors(),
+            Statement::Include(include) => include.errors(),
+        };
+    }
+
+    fn lint(&self) -> Statement {
+        return match self {
+            Statement::Entry(entry) => Statement::Entry(entry.lint()),
+            Statement::Include(include) => Statement::Include(include.lint()),
+        };
+    }
+}
+
+// endregion
+
+// region include
+
+impl Lintable<Include> for Include {
+    fn errors(&self) -> Vec<Error> {
+        let mut errors = vec![];
+        if !self.space0.value.is_empty() {
+            errors.push(Error {
+                source_info: self.clone().space0.source_info,
+                inner: LinterError::UnneccessarySpace {},
+            });
+        }
+        if self.space1.value != " " {
+            errors.push(Error {
+                source_info: self.clone().space1.source_info,
+                inner: LinterError::OneSpace {},
+            });
+        }
+        for error in self.line_terminator0.errors() {
+            errors.push(error);
+        }
+        return errors;
+    }
+
+    fn lint(&self) -> Include {
+        return Include {
+            line_terminators: self.clone().line_terminators,
+            space0: empty_whitespace(),
+            space1: one_whitespace(),
+            filename: self.clone().filename,
+            line_terminator0: self.line_terminator0.lint(),
+        };
+    }
+}
+
+// endregion
+
 // region entry
 
 impl Lintable<Entry> for Entry {
@@ -66,11 +122,11 @@ impl Lintable<Entry> for Entry {
 #[test]
 fn test_entry() {
     let entry = HurlFile {
-        entries: vec![],
+        statements: vec![],
         line_terminators: vec![],
     };
     let entry_linted = HurlFile {
-        entries: vec![],
+        statements: vec![],
         line_terminators: vec![],
     };
     assert_eq!(entry.errors(), vec![]);
@@ -99,6 +155,9 @@ impl Lintable<Request> for Request {
         for error in self.line_terminator0.errors() {
             errors.push(error);
         }
+        for section in self.sections.clone() {
+            errors.append(&mut section.errors());
+        }
         return errors;
     }
 
@@ -115,7 +174,11 @@ impl Lintable<Request> for Request {
             None => None,
             Some(body) => Some(body.lint()),
         };
-        let sections = self.sections.clone();
+        // only the options are linted within the request sections
+        let sections = self.sections.iter().map(|section| match section.value {
+            SectionValue::Options(_) => section.lint(),
+            _ => section.clone(),
+        }).collect();
         //        let query_params = match self.clone().query_params {
         //            None => None,
         //            Some(query_params) => Some(query_params.lint())
@@ -178,8 +241,7 @@ impl Lintable<Response> for Response {
 
 impl Lintable<Section> for Section {
     fn errors(&self) -> Vec<Error> {
-        let errors = vec![];
-        return errors;
+        return self.value.errors();
     }
 
     fn lint(&self) -> Section {
@@ -195,8 +257,10 @@ impl Lintable<Section> for Section {
 
 impl Lintable<SectionValue> for SectionValue {
     fn errors(&self) -> Vec<Error> {
-        let errors = vec![];
-        return errors;
+        return match self {
+            SectionValue::Options(options) => options.iter().flat_map(|e| e.errors()).collect(),
+            _ => vec![],
+        };
     }
 
     fn lint(&self) -> SectionValue {
@@ -210,6 +274,12 @@ impl Lintable<SectionValue> for SectionValue {
             SectionValue::Asserts(asserts) => {
                 SectionValue::Asserts(asserts.iter().map(|e| e.lint()).collect())
             }
+            SectionValue::Namespaces(params) => {
+                SectionValue::Namespaces(params.iter().map(|e| e.lint()).collect())
+            }
+            SectionValue::Options(options) => {
+                SectionValue::Options(options.iter().map(|e| e.lint()).collect())
+            }
             _ => SectionValue::QueryParams(vec![]),
         };
     }
@@ -265,6 +335,64 @@ impl Lintable<Capture> for Capture {
     }
 }
 
+impl Lintable<EntryOption> for EntryOption {
+    fn errors(&self) -> Vec<Error> {
+        let mut errors = vec![];
+        if !self.space0.value.is_empty() {
+            errors.push(Error {
+                source_info: self.clone().space0.source_info,
+                inner: LinterError::UnneccessarySpace {},
+            });
+        }
+        if !self.space1.value.is_empty() {
+            errors.push(Error {
+                source_info: self.clone().space1.source_info,
+                inner: LinterError::UnneccessarySpace {},
+            });
+        }
+        if self.space2.value != " " {
+            errors.push(Error {
+                source_info: self.clone().space2.source_info,
+                inner: LinterError::OneSpace {},
+            });
+        }
+        if let OptionKind::Variable(value) = self.clone().kind {
+            for space in &[value.space0, value.space1] {
+                if !space.value.is_empty() {
+                    errors.push(Error {
+                        source_info: space.source_info.clone(),
+                        inner: LinterError::UnneccessarySpace {},
+                    });
+                }
+            }
+        }
+        for error in self.line_terminator0.errors() {
+            errors.push(error);
+        }
+        return errors;
+    }
+
+    fn lint(&self) -> EntryOption {
+        let kind = match self.clone().kind {
+            OptionKind::Variable(value) => OptionKind::Variable(VariableDefinition {
+                name: value.name,
+                space0: empty_whitespace(),
+                space1: empty_whitespace(),
+                value: value.value,
+            }),
+            kind => kind,
+        };
+        return EntryOption {
+            line_terminators: self.clone().line_terminators,
+            space0: empty_whitespace(),
+            space1: empty_whitespace(),
+            space2: one_whitespace(),
+            kind,
+            line_terminator0: self.line_terminator0.lint(),
+        };
+    }
+}
+
 fn empty_whitespace() -> Whitespace {
     return Whitespace {
         value: "".to_string(),
@@ -431,6 +559,56 @@ impl Lintable<Header> for Header {
     }
 }
 
+impl Lintable<ResponseHeader> for ResponseHeader {
+    fn errors(&self) -> Vec<Error> {
+        return match self {
+            ResponseHeader::Value(header) => header.errors(),
+            ResponseHeader::Assert(header_assert) => header_assert.errors(),
+        };
+    }
+
+    fn lint(&self) -> ResponseHeader {
+        return match self {
+            ResponseHeader::Value(header) => ResponseHeader::Value(header.lint()),
+            ResponseHeader::Assert(header_assert) => ResponseHeader::Assert(header_assert.lint()),
+        };
+    }
+}
+
+impl Lintable<HeaderAssert> for HeaderAssert {
+    fn errors(&self) -> Vec<Error> {
+        let mut errors = vec![];
+        if !self.space0.value.is_empty() {
+            errors.push(Error {
+                source_info: self.clone().space0.source_info,
+                inner: LinterError::UnneccessarySpace {},
+            });
+        }
+        match error_hurl_string(self.clone().name, ALPHANUM_CHARACTERS.chars().collect()) {
+            Some(e) => errors.push(e),
+            _ => {}
+        }
+        if self.space1.value != " " {
+            errors.push(Error {
+                source_info: self.clone().space1.source_info,
+                inner: LinterError::OneSpace {},
+            });
+        }
+        return errors;
+    }
+
+    fn lint(&self) -> HeaderAssert {
+        return HeaderAssert {
+            line_terminators: self.clone().line_terminators,
+            space0: empty_whitespace(),
+            name: lint_hurl_string(self.clone().name, ALPHANUM_CHARACTERS.chars().collect()),
+            space1: one_whitespace(),
+            predicate: self.clone().predicate,
+            line_terminator0: self.clone().line_terminator0,
+        };
+    }
+}
+
 #[test]
 fn test_header() {
     // "Foo":"Bar"
@@ -805,6 +983,8 @@ fn test_hurl_template() {
                         name: "name".to_string(),
                         source_info: SourceInfo::init(0, 0, 0, 0),
                     },
+                    arguments: vec![],
+                    filters: vec![],
                     space1: Whitespace {
                         value: "".to_string(),
                         source_info: SourceInfo::init(0, 0, 0, 0),
@@ -854,6 +1034,8 @@ fn test_template() {
                         name: "name".to_string(),
                         source_info: SourceInfo::init(0, 0, 0, 0),
                     },
+                    arguments: vec![],
+                    filters: vec![],
                     space1: Whitespace {
                         value: "".to_string(),
                         source_info: SourceInfo::init(0, 0, 0, 0),
@@ -18,9 +18,12 @@ pub enum ParseError {
     Filename {},
     Space {},
     SectionName{ name : String},
+    OptionName{ name : String},
     JsonpathExpr {},
     XPathExpr {},
+    CssExpr {},
     TemplateVariable {},
+    TemplateFilter {},
     Json {},
     Xml {},
     Predicate,
@@ -52,9 +55,12 @@ impl FormatError for Error {
             ParseError::Expecting { .. } => "Parsing literal".to_string(),
             ParseError::Space { .. } => "Parsing space".to_string(),
             ParseError::SectionName { .. } => "Parsing section name".to_string(),
+            ParseError::OptionName { .. } => "Parsing option name".to_string(),
             ParseError::JsonpathExpr { .. } => "Parsing jsonpath expression".to_string(),
             ParseError::XPathExpr { .. } => "Parsing xpath expression".to_string(),
+            ParseError::CssExpr { .. } => "Parsing css selector".to_string(),
             ParseError::TemplateVariable { .. } => "Parsing template variable".to_string(),
+            ParseError::TemplateFilter { .. } => "Parsing template filter".to_string(),
             ParseError::Json { .. } => "Parsing json".to_string(),
             ParseError::Predicate { .. } => "Parsing predicate".to_string(),
             ParseError::PredicateValue { .. } => "Parsing predicate value".to_string(),
@@ -72,9 +78,12 @@ impl FormatError for Error {
             ParseError::Expecting { value } => format!("expecting '{}'", value),
             ParseError::Space { .. } => "expecting a space".to_string(),
             ParseError::SectionName {name} =>  format!("the section {} is not valid", name),
+            ParseError::OptionName {name} =>  format!("the option {} is not valid (insecure, location, delay, verbose, skip or variable)", name),
             ParseError::JsonpathExpr { .. } => "expecting a jsonpath expression".to_string(),
             ParseError::XPathExpr { .. } => "expecting a xpath expression".to_string(),
+            ParseError::CssExpr { .. } => "expecting a css selector".to_string(),
             ParseError::TemplateVariable { .. } => "expecting a variable".to_string(),
+            ParseError::TemplateFilter { .. } => "expecting a filter (default, urlEncode, base64Encode, upper or jsonEncode)".to_string(),
             ParseError::Json { .. } => "json error".to_string(),
             ParseError::Predicate { .. } => "expecting a predicate".to_string(),
             ParseError::PredicateValue { .. } => "invalid predicate value".to_string(),
@@ -1,5 +1,7 @@
+use super::combinators::*;
 use super::core::*;
 use super::error::*;
+use super::json;
 use super::primitives::*;
 use crate::core::ast::*;
 use crate::core::core::SourceInfo;
@@ -21,6 +23,8 @@ pub fn parse(p: &mut Parser) -> ParseResult<'static, Expr> {
 
     let space0 = zero_or_more_spaces(p)?;
     let variable = variable_name(p)?;
+    let arguments = zero_or_more(expr_argument, p)?;
+    let filters = zero_or_more(expr_filter, p)?;
     let space1 = zero_or_more_spaces(p)?;
 
     //literal("}}", p)?;
@@ -32,6 +36,8 @@ pub fn parse(p: &mut Parser) -> ParseResult<'static, Expr> {
     return Ok(Expr {
         space0,
         variable,
+        arguments,
+        filters,
         space1,
     });
 }
@@ -50,6 +56,8 @@ fn test_expr() {
                 name: String::from("name"),
                 source_info: SourceInfo::init(1, 4, 1, 8),
             },
+            arguments: vec![],
+            filters: vec![],
             space1: Whitespace {
                 value: String::from(""),
                 source_info: SourceInfo::init(1, 8, 1, 8),
@@ -70,6 +78,8 @@ fn test_expr() {
                     name: String::from("name"),
                     source_info: SourceInfo::init(1, 5, 1, 9),
                 },
+                arguments: vec![],
+                filters: vec![],
                 space1: Whitespace {
                     value: String::from(""),
                     source_info: SourceInfo::init(1, 9, 1, 9),
@@ -97,6 +107,201 @@ fn test_expr_error_eof() {
     assert_eq!(error.recoverable, false);
 }
 
+#[test]
+fn test_expr_function() {
+    let mut parser = Parser::init("{{randomInt 1 -10 }}");
+    let expr = parse(&mut parser).unwrap();
+    assert_eq!(expr.variable.name, String::from("randomInt"));
+    assert_eq!(
+        expr.arguments,
+        vec![
+            ExprArgument {
+                space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 12, 1, 13) },
+                value: ExprArgumentValue::Integer { value: 1, source_info: SourceInfo::init(1, 13, 1, 14) },
+            },
+            ExprArgument {
+                space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 14, 1, 15) },
+                value: ExprArgumentValue::Integer { value: -10, source_info: SourceInfo::init(1, 15, 1, 18) },
+            },
+        ]
+    );
+    assert_eq!(expr.space1.value, String::from(" "));
+
+    let mut parser = Parser::init("{{now \"%d\\\"%m\"}}");
+    let expr = parse(&mut parser).unwrap();
+    assert_eq!(
+        expr.arguments,
+        vec![
+            ExprArgument {
+                space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 6, 1, 7) },
+                value: ExprArgumentValue::String(HurlString {
+                    value: String::from("%d\"%m"),
+                    encoded: Some(String::from("%d\\\"%m")),
+                    source_info: SourceInfo::init(1, 7, 1, 15),
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_expr_function_error() {
+    let mut parser = Parser::init("{{env \"HOME}}");
+    let error = parse(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 14 });
+    assert_eq!(error.inner, ParseError::Expecting { value: String::from("\"") });
+    assert_eq!(error.recoverable, false);
+
+    let mut parser = Parser::init("{{randomInt a}}");
+    let error = parse(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 13 });
+    assert_eq!(error.inner, ParseError::Expecting { value: String::from("}}") });
+}
+
+// endregion
+
+// region expr-argument
+fn expr_argument(p: &mut Parser) -> ParseResult<'static, ExprArgument> {
+    let space0 = recover(one_or_more_spaces, p)?;
+    let value = choice(vec![string_argument, integer_argument], p)?;
+    return Ok(ExprArgument { space0, value });
+}
+
+fn string_argument(p: &mut Parser) -> ParseResult<'static, ExprArgumentValue> {
+    let start = p.state.clone();
+    try_literal("\"", p)?;
+    let mut value = String::from("");
+    let mut encoded = String::from("");
+    while let Some((c, s)) = json::json_char(p)? {
+        value.push(c);
+        encoded.push_str(s.as_str());
+    }
+    literal("\"", p)?;
+    return Ok(ExprArgumentValue::String(HurlString {
+        value,
+        encoded: Some(encoded),
+        source_info: SourceInfo {
+            start: start.pos,
+            end: p.state.pos.clone(),
+        },
+    }));
+}
+
+fn integer_argument(p: &mut Parser) -> ParseResult<'static, ExprArgumentValue> {
+    let start = p.state.clone();
+    let value = integer(p)?;
+    return Ok(ExprArgumentValue::Integer {
+        value,
+        source_info: SourceInfo {
+            start: start.pos,
+            end: p.state.pos.clone(),
+        },
+    });
+}
+
+// endregion
+
+// region expr-filter
+fn expr_filter(p: &mut Parser) -> ParseResult<'static, ExprFilter> {
+    let space0 = zero_or_more_spaces(p)?;
+    try_literal("|", p)?;
+    let space1 = zero_or_more_spaces(p)?;
+    let start = p.state.pos.clone();
+    let value = match choice(vec![default_filter, url_encode_filter, base64_encode_filter, upper_filter, json_encode_filter], p) {
+        Ok(value) => value,
+        Err(Error { recoverable: true, .. }) => {
+            return Err(Error {
+                pos: start,
+                recoverable: false,
+                inner: ParseError::TemplateFilter {},
+            });
+        }
+        Err(e) => return Err(e),
+    };
+    let end = p.state.pos.clone();
+    return Ok(ExprFilter {
+        space0,
+        space1,
+        source_info: SourceInfo { start, end },
+        value,
+    });
+}
+
+fn default_filter(p: &mut Parser) -> ParseResult<'static, ExprFilterValue> {
+    try_literal("default", p)?;
+    let space0 = zero_or_more_spaces(p)?;
+    literal(":", p)?;
+    let space1 = zero_or_more_spaces(p)?;
+    let value = nonrecover(|p1| choice(vec![string_argument, integer_argument], p1), p)?;
+    return Ok(ExprFilterValue::Default { space0, space1, value });
+}
+
+fn url_encode_filter(p: &mut Parser) -> ParseResult<'static, ExprFilterValue> {
+    try_literal("urlEncode", p)?;
+    return Ok(ExprFilterValue::UrlEncode {});
+}
+
+fn base64_encode_filter(p: &mut Parser) -> ParseResult<'static, ExprFilterValue> {
+    try_literal("base64Encode", p)?;
+    return Ok(ExprFilterValue::Base64Encode {});
+}
+
+fn upper_filter(p: &mut Parser) -> ParseResult<'static, ExprFilterValue> {
+    try_literal("upper", p)?;
+    return Ok(ExprFilterValue::Upper {});
+}
+
+fn json_encode_filter(p: &mut Parser) -> ParseResult<'static, ExprFilterValue> {
+    try_literal("jsonEncode", p)?;
+    return Ok(ExprFilterValue::JsonEncode {});
+}
+
+#[test]
+fn test_expr_filter() {
+    let mut parser = Parser::init("{{ host | default: \"localhost\" }}");
+    let expr = parse(&mut parser).unwrap();
+    assert_eq!(expr.variable.name, String::from("host"));
+    assert_eq!(
+        expr.filters,
+        vec![ExprFilter {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 8, 1, 9) },
+            space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 10, 1, 11) },
+            source_info: SourceInfo::init(1, 11, 1, 31),
+            value: ExprFilterValue::Default {
+                space0: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 18, 1, 18) },
+                space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 19, 1, 20) },
+                value: ExprArgumentValue::String(HurlString {
+                    value: String::from("localhost"),
+                    encoded: Some(String::from("localhost")),
+                    source_info: SourceInfo::init(1, 20, 1, 31),
+                }),
+            },
+        }]
+    );
+    assert_eq!(expr.space1.value, String::from(" "));
+
+    let mut parser = Parser::init("{{name|upper|urlEncode}}");
+    let expr = parse(&mut parser).unwrap();
+    assert_eq!(
+        expr.filters.iter().map(|f| f.value.clone()).collect::<Vec<ExprFilterValue>>(),
+        vec![ExprFilterValue::Upper {}, ExprFilterValue::UrlEncode {}]
+    );
+}
+
+#[test]
+fn test_expr_filter_error() {
+    let mut parser = Parser::init("{{ name | lower }}");
+    let error = parse(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 11 });
+    assert_eq!(error.inner, ParseError::TemplateFilter {});
+    assert_eq!(error.recoverable, false);
+
+    let mut parser = Parser::init("{{ name | default: localhost }}");
+    let error = parse(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 20 });
+    assert_eq!(error.recoverable, false);
+}
+
 // endregion
 
 // region variable-name
@@ -1,9 +1,11 @@
 use serde_json;
 
+use crate::core::ast::Expr;
 use crate::core::core::Pos;
 
 use super::core::*;
 use super::error;
+use super::expr;
 use super::primitives::*;
 
 #[cfg(test)]
@@ -55,6 +57,13 @@ fn test_json_char() {
     );
     assert_eq!(parser.state.cursor, 2);
 
+    let mut parser = Parser::init("\\\\");
+    assert_eq!(
+        json_char(&mut parser).unwrap(),
+        Some(('\\', String::from("\\\\")))
+    );
+    assert_eq!(parser.state.cursor, 2);
+
     let mut parser = Parser::init("\\u00e9");
     assert_eq!(
         json_char(&mut parser).unwrap(),
@@ -89,8 +98,8 @@ fn test_json_char_error() {
 fn escape_sequence(p: &mut Parser) -> ParseResult<'static, (char, String)> {
     return match p.next_char() {
         Some('"') => Ok(('"', String::from("\""))),
-        Some('\\') => Ok(('"', String::from("\\"))),
-        Some('/') => Ok(('"', String::from("/"))),
+        Some('\\') => Ok(('\\', String::from("\\"))),
+        Some('/') => Ok(('/', String::from("/"))),
         Some('b') => Ok(('\x08', String::from("b"))),
         Some('n') => Ok(('\n', String::from("n"))),
         Some('f') => Ok(('\x0c', String::from("f"))),
@@ -335,7 +344,7 @@ pub fn json_value(p: &mut Parser) -> ParseResult<'static, String> {
         _ => { p.state = start.clone();},
     }
 
-    let s = p.clone().remaining().clone();
+    let s = mask_expressions(p.clone().remaining().as_str());
 
     if p.clone().remaining().as_str().starts_with(" ")
         || p.clone().remaining().as_str().starts_with("\t")
@@ -404,6 +413,73 @@ pub fn json_value(p: &mut Parser) -> ParseResult<'static, String> {
     };
 }
 
+// {{name}} expression within a json body (spaces allowed around the name)
+// return the variable name and the length in bytes of the expression
+pub fn json_expression(s: &str) -> Option<(Expr, usize)> {
+    if !s.starts_with("{{") || s.starts_with("{{{") {
+        return None;
+    }
+    let mut p = Parser::init(s);
+    return match expr::parse(&mut p) {
+        Ok(expr) => {
+            let len: usize = s.chars().take(p.state.cursor).map(|c| c.len_utf8()).sum();
+            // the legacy {{{ }}} form is not used in json, the last brace closes a json object
+            let len = if s[..len].ends_with("}}}") { len - 1 } else { len };
+            Some((expr, len))
+        }
+        Err(_) => None,
+    };
+}
+
+// expressions are replaced by an empty json array with the same number of chars
+// the body can then be validated (and errors located) as plain json
+fn mask_expressions(s: &str) -> String {
+    let mut masked = String::from("");
+    let mut i = 0;
+    while i < s.len() {
+        match json_expression(&s[i..]) {
+            Some((_, len)) => {
+                let count = s[i..i + len].chars().count();
+                masked.push('[');
+                masked.push_str(" ".repeat(count - 2).as_str());
+                masked.push(']');
+                i += len;
+            }
+            None => {
+                let c = s[i..].chars().next().unwrap();
+                masked.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+    return masked;
+}
+
+#[test]
+fn test_json_expression() {
+    let (expr, len) = json_expression("{{name}}").unwrap();
+    assert_eq!(expr.variable.name, String::from("name"));
+    assert_eq!(len, 8);
+    let (expr, len) = json_expression("{{ ids }}, 1]").unwrap();
+    assert_eq!(expr.variable.name, String::from("ids"));
+    assert_eq!(len, 9);
+    let (expr, len) = json_expression("{{now \"%Y-%m-%d\"}}\"").unwrap();
+    assert_eq!(expr.arguments.len(), 1);
+    assert_eq!(len, 18);
+    let (_, len) = json_expression("{{id}}}").unwrap();
+    assert_eq!(len, 6);
+    assert_eq!(json_expression("{{}}"), None);
+    assert_eq!(json_expression("{{a b}}"), None);
+    assert_eq!(json_expression("{\"a\": 1}"), None);
+}
+
+#[test]
+fn test_json_value_expression() {
+    let mut parser = Parser::init("{\"ids\": {{ ids }}, \"name\": \"{{name}}\"}");
+    assert_eq!(json_value(&mut parser).unwrap(), String::from("{\"ids\": {{ ids }}, \"name\": \"{{name}}\"}"));
+    assert_eq!(parser.state.cursor, 38);
+}
+
 // used by the body
 // may start with whitespace - valid json?
 //
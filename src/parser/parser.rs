@@ -11,11 +11,11 @@ use super::primitives::*;
 
 // region hurl-file
 pub fn hurl_file(p: &mut Parser) -> ParseResult<'static, HurlFile> {
-    let entries = zero_or_more(|p1| entry(p1), p)?;
+    let statements = zero_or_more(|p1| statement(p1), p)?;
     let line_terminators = optional_line_terminators(p)?;
     eof(p)?;
     return Ok(HurlFile {
-        entries,
+        statements,
         line_terminators,
     });
 }
@@ -23,9 +23,67 @@ pub fn hurl_file(p: &mut Parser) -> ParseResult<'static, HurlFile> {
 #[test]
 fn test_hurl_file() {
     let mut parser = Parser::init("GET http://google.fr");
-    let hurl_file = hurl_file(&mut parser).unwrap();
+    let value = hurl_file(&mut parser).unwrap();
 
-    assert_eq!(hurl_file.entries.len(), 1);
+    assert_eq!(value.statements.len(), 1);
+
+    let mut parser = Parser::init("include login.hurl\nGET http://google.fr");
+    let value = hurl_file(&mut parser).unwrap();
+    assert_eq!(value.statements.len(), 2);
+    assert_eq!(value.entries().len(), 1);
+}
+// endregion
+
+// region statement
+fn statement(p: &mut Parser) -> ParseResult<'static, Statement> {
+    return choice(
+        vec![
+            |p1| match include(p1) {
+                Ok(value) => Ok(Statement::Include(value)),
+                Err(e) => Err(e),
+            },
+            |p1| match entry(p1) {
+                Ok(value) => Ok(Statement::Entry(value)),
+                Err(e) => Err(e),
+            },
+        ],
+        p,
+    );
+}
+
+pub fn include(p: &mut Parser) -> ParseResult<'static, Include> {
+    let line_terminators = optional_line_terminators(p)?;
+    let space0 = zero_or_more_spaces(p)?;
+    try_literal("include", p)?;
+    let space1 = one_or_more_spaces(p)?;
+    let filename = filename(p)?;
+    let line_terminator0 = line_terminator(p)?;
+    return Ok(Include {
+        line_terminators,
+        space0,
+        space1,
+        filename,
+        line_terminator0,
+    });
+}
+
+#[test]
+fn test_include() {
+    let mut parser = Parser::init("include common/login.hurl\n");
+    let value = include(&mut parser).unwrap();
+    assert_eq!(value.filename.value, String::from("common/login.hurl"));
+    assert_eq!(value.filename.source_info, SourceInfo::init(1, 9, 1, 26));
+    assert_eq!(parser.state.cursor, 26);
+
+    let mut parser = Parser::init("GET http://google.fr");
+    let error = include(&mut parser).err().unwrap();
+    assert_eq!(error.recoverable, true);
+
+    let mut parser = Parser::init("include /etc/passwd");
+    let error = include(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 9 });
+    assert_eq!(error.recoverable, false);
+    assert_eq!(error.inner, ParseError::Filename {});
 }
 // endregion
 
@@ -404,7 +462,7 @@ pub fn response(p: &mut Parser) -> ParseResult<'static, Response> {
     let space1 = one_or_more_spaces(p)?;
     let _status = status(p)?;
     let line_terminator0 = line_terminator(p)?;
-    let headers = zero_or_more(header, p)?;
+    let headers = zero_or_more(response_header, p)?;
     let sections = response_sections(p)?;
     let b = optional(|p1| body(p1), p)?;
     return Ok(Response {
@@ -618,6 +676,8 @@ fn test_url_with_expression() {
                             name: String::from("host"),
                             source_info: SourceInfo::init(1, 10, 1, 14),
                         },
+                        arguments: vec![],
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::from(""),
                             source_info: SourceInfo::init(1, 14, 1, 14),
@@ -815,6 +875,75 @@ fn test_header() {
 // endregion
 
 // region header-name
+pub fn response_header(p: &mut Parser) -> ParseResult<'static, ResponseHeader> {
+    return choice(
+        vec![
+            |p1| match header(p1) {
+                Ok(header) => Ok(ResponseHeader::Value(header)),
+                Err(e) => Err(e),
+            },
+            |p1| match header_assert(p1) {
+                Ok(header_assert) => Ok(ResponseHeader::Assert(header_assert)),
+                Err(e) => Err(e),
+            },
+        ],
+        p,
+    );
+}
+
+pub fn header_assert(p: &mut Parser) -> ParseResult<'static, HeaderAssert> {
+    let line_terminators = optional_line_terminators(p)?;
+    let space0 = zero_or_more_spaces(p)?;
+    let name = header_name(p)?;
+    let space1 = recover(one_or_more_spaces, p)?;
+
+    // the line may be the start of another entry or a body
+    // it is only a header assert if it starts with a predicate
+    let start = p.state.clone();
+    let predicate0 = match predicate(p) {
+        Err(e) if e.pos == start.pos => return Err(Error { pos: e.pos, recoverable: true, inner: e.inner }),
+        r => r?
+    };
+    let line_terminator0 = line_terminator(p)?;
+    return Ok(HeaderAssert {
+        line_terminators,
+        space0,
+        name,
+        space1,
+        predicate: predicate0,
+        line_terminator0,
+    });
+}
+
+#[test]
+fn test_response_header() {
+    let mut parser = Parser::init("Foo: Bar");
+    match response_header(&mut parser).unwrap() {
+        ResponseHeader::Value(header) => assert_eq!(header.name.value, "Foo"),
+        _ => panic!()
+    }
+
+    let mut parser = Parser::init("Foo not exists");
+    match response_header(&mut parser).unwrap() {
+        ResponseHeader::Assert(header_assert) => {
+            assert_eq!(header_assert.name.value, "Foo");
+            assert_eq!(header_assert.predicate.not, true);
+            assert_eq!(header_assert.predicate.predicate_func.value, PredicateFuncValue::Exist {});
+            assert_eq!(header_assert.predicate.predicate_func.source_info, SourceInfo::init(1, 9, 1, 15));
+        }
+        _ => panic!()
+    }
+
+    let mut parser = Parser::init("GET http://localhost");
+    let error = response_header(&mut parser).err().unwrap();
+    assert_eq!(error.recoverable, true);
+
+    let mut parser = Parser::init("Foo not 1");
+    let error = response_header(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 9 });
+    assert_eq!(error.recoverable, false);
+}
+
 pub fn header_name(p: &mut Parser) -> ParseResult<'static, HurlString> {
     //  let start = p.state.clone();
     let n = name(p)?;
@@ -967,6 +1096,8 @@ pub fn section(p: &mut Parser) -> ParseResult<'static, Section> {
         "Cookies" => section_value_cookies(p)?,
         "Captures" => section_value_captures(p)?,
         "Asserts" => section_value_asserts(p)?,
+        "Namespaces" => section_value_namespaces(p)?,
+        "Options" => section_value_options(p)?,
         _ => {
             return Err(Error {
                 pos: Pos {
@@ -1028,6 +1159,7 @@ fn test_asserts_section() {
                             source_info: SourceInfo::init(2, 8, 2, 16),
                         },
                     },
+                    filters: vec![],
                 },
                 space1: Whitespace {
                     value: String::from(" "),
@@ -1092,6 +1224,25 @@ fn test_asserts_section_error() {
     );
     assert_eq!(error.recoverable, false);
 }
+
+#[test]
+fn test_namespaces_section() {
+    let mut parser = Parser::init("[Namespaces]\nsoap: http://www.w3.org/2003/05/soap-envelope\n");
+    let section = section(&mut parser).unwrap();
+    assert_eq!(section.name(), "Namespaces");
+    let params = match section.value {
+        SectionValue::Namespaces(params) => params,
+        _ => panic!("expecting a namespaces section"),
+    };
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].name.value, String::from("soap"));
+    assert_eq!(params[0].value.elements, vec![HurlTemplateElement::Literal {
+        value: HurlString2 {
+            value: String::from("http://www.w3.org/2003/05/soap-envelope"),
+            encoded: None,
+        }
+    }]);
+}
 // endregion
 
 // region section-name
@@ -1139,6 +1290,16 @@ pub fn section_value_asserts(p: &mut Parser) -> ParseResult<'static, SectionValu
     return Ok(SectionValue::Asserts(asserts));
 }
 
+pub fn section_value_namespaces(p: &mut Parser) -> ParseResult<'static, SectionValue> {
+    let items = zero_or_more(|p1| param(p1), p)?;
+    return Ok(SectionValue::Namespaces(items));
+}
+
+pub fn section_value_options(p: &mut Parser) -> ParseResult<'static, SectionValue> {
+    let items = zero_or_more(|p1| option(p1), p)?;
+    return Ok(SectionValue::Options(items));
+}
+
 // endregion
 
 // region param
@@ -1216,6 +1377,115 @@ fn test_param_value() {
 
 // endregion
 
+// region option
+
+pub fn option(p: &mut Parser) -> ParseResult<'static, EntryOption> {
+    let line_terminators = optional_line_terminators(p)?;
+    let space0 = zero_or_more_spaces(p)?;
+    let start = p.state.clone();
+    let name = p.next_chars_while(|c| c.is_alphanumeric());
+    let space1 = zero_or_more_spaces(p)?;
+    if name.is_empty() {
+        return Err(Error {
+            pos: start.pos,
+            recoverable: true,
+            inner: ParseError::Expecting { value: String::from("option") },
+        });
+    }
+    try_literal(":", p)?;
+    let space2 = zero_or_more_spaces(p)?;
+    let kind = match name.as_str() {
+        "insecure" => OptionKind::Insecure(nonrecover(|p1| boolean(p1), p)?),
+        "location" => OptionKind::FollowLocation(nonrecover(|p1| boolean(p1), p)?),
+        "delay" => OptionKind::Delay(nonrecover(|p1| natural(p1), p)?),
+        "verbose" => OptionKind::Verbose(nonrecover(|p1| boolean(p1), p)?),
+        "skip" => OptionKind::Skip(nonrecover(|p1| boolean(p1), p)?),
+        "variable" => OptionKind::Variable(variable_definition(p)?),
+        _ => {
+            return Err(Error {
+                pos: start.pos,
+                recoverable: false,
+                inner: ParseError::OptionName { name },
+            });
+        }
+    };
+    let line_terminator0 = line_terminator(p)?;
+    return Ok(EntryOption {
+        line_terminators,
+        space0,
+        space1,
+        space2,
+        kind,
+        line_terminator0,
+    });
+}
+
+fn variable_definition(p: &mut Parser) -> ParseResult<'static, VariableDefinition> {
+    let start = p.state.clone();
+    let name = p.next_chars_while(|c| c.is_alphanumeric() || *c == '_' || *c == '-');
+    if name.is_empty() {
+        return Err(Error {
+            pos: start.pos,
+            recoverable: false,
+            inner: ParseError::TemplateVariable {},
+        });
+    }
+    let space0 = zero_or_more_spaces(p)?;
+    literal("=", p)?;
+    let space1 = zero_or_more_spaces(p)?;
+    let value = header_value(p)?;
+    return Ok(VariableDefinition {
+        name,
+        space0,
+        space1,
+        value,
+    });
+}
+
+#[test]
+fn test_option() {
+    let mut parser = Parser::init("insecure: true\n");
+    let o = option(&mut parser).unwrap();
+    assert_eq!(o.kind, OptionKind::Insecure(true));
+    assert_eq!(o.space2.value, String::from(" "));
+
+    let mut parser = Parser::init("delay : 200\n");
+    let o = option(&mut parser).unwrap();
+    assert_eq!(o.kind, OptionKind::Delay(200));
+    assert_eq!(o.space1.value, String::from(" "));
+
+    let mut parser = Parser::init("variable: id = {{user_id}}\n");
+    let o = option(&mut parser).unwrap();
+    match o.kind {
+        OptionKind::Variable(VariableDefinition { name, space0, value, .. }) => {
+            assert_eq!(name, String::from("id"));
+            assert_eq!(space0.value, String::from(" "));
+            assert_eq!(value.elements.len(), 1);
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_option_error() {
+    let mut parser = Parser::init("HTTP/1.1 200\n");
+    let error = option(&mut parser).err().unwrap();
+    assert_eq!(error.recoverable, true);
+
+    let mut parser = Parser::init("retry: 3\n");
+    let error = option(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 1 });
+    assert_eq!(error.recoverable, false);
+    assert_eq!(error.inner, ParseError::OptionName { name: String::from("retry") });
+
+    let mut parser = Parser::init("insecure: yes\n");
+    let error = option(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 11 });
+    assert_eq!(error.recoverable, false);
+}
+
+// endregion
+
 // region cookie
 
 pub fn cookie(p: &mut Parser) -> ParseResult<'static, Cookie> {
@@ -1328,6 +1598,7 @@ fn test_capture() {
                     source_info: SourceInfo::init(1, 13, 1, 21),
                 },
             },
+            filters: vec![],
         }
     );
 }
@@ -1355,8 +1626,23 @@ pub fn assert(p: &mut Parser) -> ParseResult<'static, Assert> {
                 match predicate0.clone().predicate_func.value {
                     PredicateFuncValue::EqualBool { space0, value } => PredicateFuncValue::FirstEqualBool { space0, value },
                     PredicateFuncValue::EqualInt { space0, value } => PredicateFuncValue::FirstEqualInt { space0, value },
+                    PredicateFuncValue::EqualFloat { space0, value } => PredicateFuncValue::FirstEqualFloat { space0, value },
+                    PredicateFuncValue::ApproxEqual { space0, value, space1, space2, tolerance } => PredicateFuncValue::FirstApproxEqual { space0, value, space1, space2, tolerance },
                     PredicateFuncValue::EqualString { space0, value } => PredicateFuncValue::FirstEqualString { space0, value },
+                    PredicateFuncValue::EqualJson { space0, value } => PredicateFuncValue::FirstEqualJson { space0, value },
                     PredicateFuncValue::CountEqual { space0, value } => PredicateFuncValue::FirstCountEqual { space0, value },
+                    PredicateFuncValue::CountEqualExpression { space0, value } => PredicateFuncValue::FirstCountEqualExpression { space0, value },
+                    PredicateFuncValue::ValidateSchema { space0, value } => PredicateFuncValue::FirstValidateSchema { space0, value },
+                    PredicateFuncValue::IsIsoDate {} => PredicateFuncValue::FirstIsIsoDate {},
+                    PredicateFuncValue::Before { space0, value } => PredicateFuncValue::FirstBefore { space0, value },
+                    PredicateFuncValue::After { space0, value } => PredicateFuncValue::FirstAfter { space0, value },
+                    _ => predicate0.clone().predicate_func.value
+                }
+            } else if query0.clone().is_css() {
+                // css query returns the list of the selected nodes
+                // countEquals applies to the list itself
+                match predicate0.clone().predicate_func.value {
+                    PredicateFuncValue::EqualString { space0, value } => PredicateFuncValue::FirstEqualString { space0, value },
                     _ => predicate0.clone().predicate_func.value
                 }
             } else {
@@ -1396,6 +1682,7 @@ fn test_assert() {
                     source_info: SourceInfo::init(1, 8, 1, 16),
                 },
             },
+            filters: vec![],
         }
     );
 }
@@ -1425,9 +1712,11 @@ pub fn query(p: &mut Parser) -> ParseResult<'static, Query> {
     let start = p.state.pos.clone();
     let value = query_value(p)?;
     let end = p.state.pos.clone();
+    let filters = zero_or_more(|p1| filter(p1), p)?;
     return Ok(Query {
         source_info: SourceInfo { start, end },
         value,
+        filters,
     });
 }
 
@@ -1437,9 +1726,221 @@ fn test_query() {
     assert_eq!(query(&mut parser).unwrap(), Query {
         source_info: SourceInfo::init(1, 1, 1, 7),
         value: QueryValue::Status {},
+        filters: vec![],
+    });
+}
+
+#[test]
+fn test_query_with_filters() {
+    let mut parser = Parser::init("header \"X-Version\" regex \"v(\\\\d+)\" equals \"2\"");
+    let query0 = query(&mut parser).unwrap();
+    assert_eq!(query0.source_info, SourceInfo::init(1, 1, 1, 19));
+    assert_eq!(query0.filters, vec![Filter {
+        space0: Whitespace {
+            value: String::from(" "),
+            source_info: SourceInfo::init(1, 19, 1, 20),
+        },
+        source_info: SourceInfo::init(1, 20, 1, 35),
+        value: FilterValue::Regex {
+            space0: Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::init(1, 25, 1, 26),
+            },
+            expr: HurlString {
+                value: String::from("v(\\d+)"),
+                encoded: Some(String::from("v(\\\\d+)")),
+                source_info: SourceInfo::init(1, 26, 1, 35),
+            },
+        },
+    }]);
+    assert_eq!(parser.state.cursor, 34);
+
+    let mut parser = Parser::init("header \"X-Ids\" split \",\" count countEquals 3");
+    let filters = query(&mut parser).unwrap().filters;
+    assert_eq!(filters.len(), 2);
+    assert_eq!(filters[1], Filter {
+        space0: Whitespace {
+            value: String::from(" "),
+            source_info: SourceInfo::init(1, 25, 1, 26),
+        },
+        source_info: SourceInfo::init(1, 26, 1, 31),
+        value: FilterValue::Count {},
+    });
+    assert_eq!(parser.state.cursor, 30);
+}
+
+
+// endregion
+
+// region filter
+
+pub fn filter(p: &mut Parser) -> ParseResult<'static, Filter> {
+    let space0 = recover(one_or_more_spaces, p)?;
+    let start = p.state.pos.clone();
+    let value = filter_value(p)?;
+    let end = p.state.pos.clone();
+    return Ok(Filter {
+        space0,
+        source_info: SourceInfo { start, end },
+        value,
+    });
+}
+
+pub fn filter_value(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    return choice(
+        vec![
+            regex_filter,
+            count_filter,
+            nth_filter,
+            to_int_filter,
+            split_filter,
+            replace_filter,
+            base64_decode_filter,
+            to_date_filter,
+            jsonpath_filter,
+        ],
+        p,
+    );
+}
+
+// the filter name must not be the prefix of a predicate (count/countEquals)
+fn filter_name(name: &str, p: &mut Parser) -> ParseResult<'static, ()> {
+    let start = p.state.clone();
+    try_literal(name, p)?;
+    match p.clone().next_char() {
+        Some(c) if c.is_alphanumeric() => {
+            p.state = start.clone();
+            return Err(Error {
+                pos: start.pos,
+                recoverable: true,
+                inner: ParseError::Expecting { value: name.to_string() },
+            });
+        }
+        _ => return Ok(()),
+    }
+}
+
+fn filter_string(p: &mut Parser) -> ParseResult<'static, HurlString> {
+    let start = p.state.clone();
+    let (value, encoded) = nonrecover(|p1| json::json_string2(p1), p)?;
+    return Ok(HurlString {
+        value,
+        encoded: Some(encoded),
+        source_info: SourceInfo {
+            start: start.pos,
+            end: p.clone().state.pos,
+        },
     });
 }
 
+pub fn regex_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("regex", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let expr = regex_expr(p)?;
+    return Ok(FilterValue::Regex { space0, expr });
+}
+
+pub fn count_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("count", p)?;
+    return Ok(FilterValue::Count {});
+}
+
+pub fn nth_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("nth", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let n = nonrecover(|p1| natural(p1), p)?;
+    return Ok(FilterValue::Nth { space0, n });
+}
+
+pub fn to_int_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("toInt", p)?;
+    return Ok(FilterValue::ToInt {});
+}
+
+pub fn split_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("split", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let sep = filter_string(p)?;
+    return Ok(FilterValue::Split { space0, sep });
+}
+
+pub fn replace_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("replace", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let old_value = filter_string(p)?;
+    let space1 = one_or_more_spaces(p)?;
+    let new_value = filter_string(p)?;
+    return Ok(FilterValue::Replace { space0, old_value, space1, new_value });
+}
+
+pub fn base64_decode_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("base64Decode", p)?;
+    return Ok(FilterValue::Base64Decode {});
+}
+
+pub fn to_date_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("toDate", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let format = filter_string(p)?;
+    return Ok(FilterValue::ToDate { space0, format });
+}
+
+pub fn jsonpath_filter(p: &mut Parser) -> ParseResult<'static, FilterValue> {
+    filter_name("jsonpath", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let expr = jsonpath_expr(p)?;
+    return Ok(FilterValue::Jsonpath { space0, expr });
+}
+
+#[test]
+fn test_filter() {
+    let mut parser = Parser::init(" nth 2");
+    assert_eq!(filter(&mut parser).unwrap(), Filter {
+        space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 1, 1, 2) },
+        source_info: SourceInfo::init(1, 2, 1, 7),
+        value: FilterValue::Nth {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 5, 1, 6) },
+            n: 2,
+        },
+    });
+
+    let mut parser = Parser::init(" replace \"-\" \"_\"");
+    assert_eq!(filter(&mut parser).unwrap().value, FilterValue::Replace {
+        space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 9, 1, 10) },
+        old_value: HurlString {
+            value: String::from("-"),
+            encoded: Some(String::from("-")),
+            source_info: SourceInfo::init(1, 10, 1, 13),
+        },
+        space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 13, 1, 14) },
+        new_value: HurlString {
+            value: String::from("_"),
+            encoded: Some(String::from("_")),
+            source_info: SourceInfo::init(1, 14, 1, 17),
+        },
+    });
+
+    let mut parser = Parser::init(" toDate \"%d/%m/%Y\"");
+    assert_eq!(filter(&mut parser).unwrap().value, FilterValue::ToDate {
+        space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 8, 1, 9) },
+        format: HurlString {
+            value: String::from("%d/%m/%Y"),
+            encoded: Some(String::from("%d/%m/%Y")),
+            source_info: SourceInfo::init(1, 9, 1, 19),
+        },
+    });
+
+    let mut parser = Parser::init(" countEquals 2");
+    let error = filter(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 2 });
+    assert_eq!(error.recoverable, true);
+
+    let mut parser = Parser::init(" split x");
+    let error = filter(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 8 });
+    assert_eq!(error.inner, ParseError::Expecting { value: String::from("\"") });
+    assert_eq!(error.recoverable, false);
+}
 
 // endregion
 
@@ -1449,12 +1950,19 @@ pub fn query_value(p: &mut Parser) -> ParseResult<'static, QueryValue> {
     return choice(
         vec![
             status_query,
+            url_query,
+            method_query,
             header_query,
+            request_header_query,
             cookie_query,
             body_query,
+            bytes_query,
+            sha256_query,
+            md5_query,
             xpath_query,
             jsonpath_query,
             regex_query,
+            css_query,
         ],
         p,
     );
@@ -1471,9 +1979,20 @@ fn test_status_query() {
     assert_eq!(query(&mut parser).unwrap(), Query {
         source_info: SourceInfo::init(1, 1, 1, 7),
         value: QueryValue::Status {},
+        filters: vec![],
     });
 }
 
+pub fn url_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
+    try_literal("url", p)?;
+    return Ok(QueryValue::Url {});
+}
+
+pub fn method_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
+    try_literal("method", p)?;
+    return Ok(QueryValue::Method {});
+}
+
 pub fn header_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
     try_literal("header", p)?;
     let space0 = one_or_more_spaces(p)?;
@@ -1500,6 +2019,41 @@ fn test_header_query() {
     );
 }
 
+// header sent with the request
+pub fn request_header_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
+    try_literal("request", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    literal("header", p)?;
+    let space1 = one_or_more_spaces(p)?;
+    let name = name(p)?;
+    return Ok(QueryValue::RequestHeader { space0, space1, name });
+}
+
+#[test]
+fn test_request_query() {
+    let mut parser = Parser::init("url");
+    assert_eq!(query_value(&mut parser).unwrap(), QueryValue::Url {});
+
+    let mut parser = Parser::init("method");
+    assert_eq!(query_value(&mut parser).unwrap(), QueryValue::Method {});
+
+    let mut parser = Parser::init("request header \"Authorization\"");
+    assert_eq!(query_value(&mut parser).unwrap(), QueryValue::RequestHeader {
+        space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 8, 1, 9) },
+        space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 15, 1, 16) },
+        name: HurlString {
+            value: String::from("Authorization"),
+            encoded: Some(String::from("Authorization")),
+            source_info: SourceInfo::init(1, 16, 1, 31),
+        },
+    });
+
+    let mut parser = Parser::init("request cookie \"id\"");
+    let error = query_value(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 9 });
+    assert_eq!(error.recoverable, false);
+}
+
 pub fn cookie_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
     try_literal("cookie", p)?;
     let space0 = one_or_more_spaces(p)?;
@@ -1532,10 +2086,41 @@ pub fn body_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
     return Ok(QueryValue::Body {});
 }
 
-pub fn xpath_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
-    recover(|p1| literal("xpath", p1), p)?;
-    let space0 = one_or_more_spaces(p)?;
-    let expr = xpath_expr(p)?;
+pub fn bytes_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
+    try_literal("bytes", p)?;
+    return Ok(QueryValue::Bytes {});
+}
+
+pub fn sha256_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
+    try_literal("sha256", p)?;
+    return Ok(QueryValue::Sha256 {});
+}
+
+pub fn md5_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
+    try_literal("md5", p)?;
+    return Ok(QueryValue::Md5 {});
+}
+
+#[test]
+fn test_bytes_queries() {
+    let mut parser = Parser::init("bytes countEquals 12");
+    assert_eq!(query(&mut parser).unwrap(), Query {
+        source_info: SourceInfo::init(1, 1, 1, 6),
+        value: QueryValue::Bytes {},
+        filters: vec![],
+    });
+
+    let mut parser = Parser::init("sha256");
+    assert_eq!(query(&mut parser).unwrap().value, QueryValue::Sha256 {});
+
+    let mut parser = Parser::init("md5");
+    assert_eq!(query(&mut parser).unwrap().value, QueryValue::Md5 {});
+}
+
+pub fn xpath_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
+    recover(|p1| literal("xpath", p1), p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let expr = xpath_expr(p)?;
     return Ok(QueryValue::Xpath { space0, expr });
 }
 
@@ -1566,6 +2151,16 @@ fn test_xpath_query() {
             source_info: SourceInfo::init(1, 7, 1, 100),
         },
     });
+
+    let mut parser = Parser::init("xpath //soap:Body/m:Price");
+    assert_eq!(xpath_query(&mut parser).unwrap(), QueryValue::Xpath {
+        space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 6, 1, 7) },
+        expr: HurlString {
+            value: String::from("//soap:Body/m:Price"),
+            encoded: None,
+            source_info: SourceInfo::init(1, 7, 1, 26),
+        },
+    });
 }
 
 pub fn jsonpath_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
@@ -1628,7 +2223,7 @@ pub fn xpath_expr(p: &mut Parser) -> ParseResult<'static, HurlString> {
                 let value = p.next_chars_while(|c| {
                     c.is_alphanumeric()
                         || vec![
-                        '-', '(', ')', '[', ']', '{', '}', '/', '@', ',', '\'', '_', '=', '"',
+                        '-', '(', ')', '[', ']', '{', '}', '/', '@', ',', '\'', '_', '=', '"', ':',
                     ]
                         .contains(&c)
                 });
@@ -1656,11 +2251,10 @@ pub fn xpath_expr(p: &mut Parser) -> ParseResult<'static, HurlString> {
 
 pub fn jsonpath_expr(p: &mut Parser) -> ParseResult<'static, HurlString> {
     let start = p.state.clone();
-    match json::json_string(p) {
-        Ok(v) => {
-            let encoded = String::from("TBD"); //p.deprecated_buffer[start.cursor..p.state.cursor].chars().as_str().to_string();
+    match json::json_string2(p) {
+        Ok((value, encoded)) => {
             return Ok(HurlString {
-                value: v,
+                value,
                 encoded: Some(encoded),
                 source_info: SourceInfo {
                     start: start.pos,
@@ -1764,6 +2358,92 @@ pub fn regex_expr(p: &mut Parser) -> ParseResult<'static, HurlString> {
     };
 }
 
+pub fn css_query(p: &mut Parser) -> ParseResult<'static, QueryValue> {
+    try_literal("css", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let expr = css_expr(p)?;
+    return Ok(QueryValue::Css { space0, expr });
+}
+
+#[test]
+fn test_css_query() {
+    let mut parser = Parser::init("css \"div.alert > p\"");
+    assert_eq!(
+        css_query(&mut parser).unwrap(),
+        QueryValue::Css {
+            space0: Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::init(1, 4, 1, 5),
+            },
+            expr: HurlString {
+                value: String::from("div.alert > p"),
+                encoded: Some(String::from("div.alert > p")),
+                source_info: SourceInfo::init(1, 5, 1, 20),
+            },
+        },
+    );
+
+    let mut parser = Parser::init("css a.next::attr(href)");
+    assert_eq!(
+        css_query(&mut parser).unwrap(),
+        QueryValue::Css {
+            space0: Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::init(1, 4, 1, 5),
+            },
+            expr: HurlString {
+                value: String::from("a.next::attr(href)"),
+                encoded: None,
+                source_info: SourceInfo::init(1, 5, 1, 23),
+            },
+        },
+    );
+
+    let mut parser = Parser::init("css ");
+    let error = css_query(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 5 });
+    assert_eq!(error.inner, ParseError::CssExpr {});
+}
+
+// an unquoted css selector can not contain spaces
+pub fn css_expr(p: &mut Parser) -> ParseResult<'static, HurlString> {
+    let start = p.state.clone();
+    return match json::json_string2(p) {
+        Ok((value, encoded)) => {
+            Ok(HurlString {
+                value,
+                encoded: Some(encoded),
+                source_info: SourceInfo {
+                    start: start.pos,
+                    end: p.clone().state.pos,
+                },
+            })
+        }
+        Err(e) => {
+            if e.recoverable {
+                let value = p.next_chars_while(|c| !c.is_whitespace() && *c != '#');
+                if value == "" {
+                    return Err(Error {
+                        pos: start.pos,
+                        recoverable: false,
+                        inner: ParseError::CssExpr {},
+                    });
+                };
+                return Ok(HurlString {
+                    value: value.clone(),
+                    encoded: None,
+                    source_info: SourceInfo {
+                        start: start.pos,
+                        end: p.clone().state.pos,
+                    },
+                });
+            } else {
+                return Err(e);
+            }
+        }
+    };
+}
+
 // endregion
 
 // region predicate
@@ -1858,10 +2538,20 @@ pub fn predicate_func_value(p: &mut Parser) -> ParseResult<'static, PredicateFun
    return match choice(
         vec![
             equal_predicate,
+            approx_equal_predicate,
             count_equal_predicate,
             start_with_predicate,
             contain_predicate,
             match_predicate,
+            include_predicate,
+            all_match_predicate,
+            any_match_predicate,
+            validate_schema_predicate,
+            validate_xsd_predicate,
+            validate_dtd_predicate,
+            is_iso_date_predicate,
+            before_predicate,
+            after_predicate,
             exist_predicate,
         ],
         p,
@@ -1884,6 +2574,8 @@ pub fn equal_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValu
         Ok(PredicateValue::Int { value }) => Ok(PredicateFuncValue::EqualInt { space0, value }),
         Ok(PredicateValue::Float { value }) => Ok(PredicateFuncValue::EqualFloat { space0, value }),
         Ok(PredicateValue::Template { value }) => Ok(PredicateFuncValue::EqualString { space0, value }),
+        Ok(PredicateValue::Bytes { value }) => Ok(PredicateFuncValue::EqualBytes { space0, value }),
+        Ok(PredicateValue::Json { value }) => Ok(PredicateFuncValue::EqualJson { space0, value }),
         _ => Err(Error {
             pos: start.pos,
             recoverable: false,
@@ -1910,7 +2602,7 @@ fn test_equal_predicate() {
     assert_eq!(
         equal_predicate(&mut parser).unwrap(),
         PredicateFuncValue::EqualFloat {
-            value: Float { int: 1, decimal: 100000000000000000, decimal_digits: 1 },
+            value: Float { value: 1.1, encoded: String::from("1.1") },
             space0: Whitespace {
                 value: String::from(" "),
                 source_info: SourceInfo::init(1, 7, 1, 8),
@@ -1950,12 +2642,99 @@ fn test_equal_predicate() {
             },
         }
     );
+
+    let mut parser = Parser::init("equals file,data.bin;");
+    assert_eq!(
+        equal_predicate(&mut parser).unwrap(),
+        PredicateFuncValue::EqualBytes {
+            value: Bytes::File {
+                space0: Whitespace {
+                    value: String::from(""),
+                    source_info: SourceInfo::init(1, 13, 1, 13),
+                },
+                filename: Filename {
+                    value: String::from("data.bin"),
+                    source_info: SourceInfo::init(1, 13, 1, 21),
+                },
+                space1: Whitespace {
+                    value: String::from(""),
+                    source_info: SourceInfo::init(1, 21, 1, 21),
+                },
+            },
+            space0: Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::init(1, 7, 1, 8),
+            },
+        }
+    );
+}
+
+pub fn approx_equal_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("approxEquals", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let value = number(p)?;
+    let space1 = one_or_more_spaces(p)?;
+    literal("within", p)?;
+    let space2 = one_or_more_spaces(p)?;
+    let tolerance = number(p)?;
+    return Ok(PredicateFuncValue::ApproxEqual { space0, value, space1, space2, tolerance });
+}
+
+// float or integer
+fn number(p: &mut Parser) -> ParseResult<'static, Float> {
+    let save = p.state.clone();
+    match float(p) {
+        Ok(value) => return Ok(value),
+        Err(Error { recoverable: false, pos, inner }) => return Err(Error { pos, recoverable: false, inner }),
+        Err(_) => p.state = save.clone(),
+    }
+    match integer(p) {
+        Ok(_) => {}
+        Err(_) => return Err(Error {
+            pos: save.pos,
+            recoverable: false,
+            inner: ParseError::PredicateValue {},
+        }),
+    }
+    let encoded: String = p.buffer[save.cursor..p.state.cursor].iter().collect();
+    let value = encoded.parse().unwrap();
+    return Ok(Float { value, encoded });
+}
+
+#[test]
+fn test_approx_equal_predicate() {
+    let mut parser = Parser::init("approxEquals 1.23 within 1");
+    assert_eq!(
+        approx_equal_predicate(&mut parser).unwrap(),
+        PredicateFuncValue::ApproxEqual {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 13, 1, 14) },
+            value: Float { value: 1.23, encoded: String::from("1.23") },
+            space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 18, 1, 19) },
+            space2: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 25, 1, 26) },
+            tolerance: Float { value: 1.0, encoded: String::from("1") },
+        }
+    );
+
+    let mut parser = Parser::init("approxEquals 1.23 x");
+    let error = approx_equal_predicate(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 19 });
+    assert_eq!(error.recoverable, false);
+
+    let mut parser = Parser::init("approxEquals x");
+    let error = approx_equal_predicate(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 14 });
+    assert_eq!(error.inner, ParseError::PredicateValue {});
 }
 
 pub fn count_equal_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
     try_literal("countEquals", p)?;
     let space0 = one_or_more_spaces(p)?;
     let save = p.state.clone();
+    match expr::parse(p) {
+        Ok(value) => return Ok(PredicateFuncValue::CountEqualExpression { space0, value }),
+        Err(Error { recoverable: false, .. }) => {}
+        Err(_) => p.state = save.clone(),
+    }
     let value = match natural(p) {
         Err(_) => return Err(Error {
             pos: save.pos,
@@ -1992,7 +2771,7 @@ pub fn start_with_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFun
     try_literal("startsWith", p)?;
     let space0 = one_or_more_spaces(p)?;
     let save = p.state.clone();
-    let value = match hurl_value_json(p) {
+    let value = match predicate_template(p) {
         Err(_) => return Err(Error {
             pos: save.pos,
             recoverable: false,
@@ -2015,24 +2794,314 @@ fn test_start_with_predicate() {
 pub fn contain_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
     try_literal("contains", p)?;
     let space0 = one_or_more_spaces(p)?;
-    let value = hurl_value_json(p)?;
+    let value = predicate_template(p)?;
     return Ok(PredicateFuncValue::Contain { space0, value });
 }
 
 pub fn match_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
     try_literal("matches", p)?;
     let space0 = one_or_more_spaces(p)?;
-    let start = p.clone().state.pos;
-    let (value, encoded) = json::json_string2(p)?;
-    let end = p.clone().state.pos;
-    let value = HurlString {
-        value,
-        encoded: Some(encoded),
-        source_info: SourceInfo { start, end },
-    };
+    let value = predicate_template(p)?;
     return Ok(PredicateFuncValue::Match { space0, value });
 }
 
+pub fn include_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("includes", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let start = p.state.clone();
+    return match predicate_value(p) {
+        Ok(PredicateValue::Bool { value }) => Ok(PredicateFuncValue::IncludeBool { space0, value }),
+        Ok(PredicateValue::Int { value }) => Ok(PredicateFuncValue::IncludeInt { space0, value }),
+        Ok(PredicateValue::Float { value }) => Ok(PredicateFuncValue::IncludeFloat { space0, value }),
+        Ok(PredicateValue::Template { value }) => Ok(PredicateFuncValue::IncludeString { space0, value }),
+        _ => Err(Error {
+            pos: start.pos,
+            recoverable: false,
+            inner: ParseError::PredicateValue {},
+        }),
+    };
+}
+
+#[test]
+fn test_include_predicate() {
+    let mut parser = Parser::init("includes 2");
+    assert_eq!(
+        include_predicate(&mut parser).unwrap(),
+        PredicateFuncValue::IncludeInt {
+            value: 2,
+            space0: Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::init(1, 9, 1, 10),
+            },
+        }
+    );
+
+    let mut parser = Parser::init("includes [2]");
+    let error = include_predicate(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 10 });
+    assert_eq!(error.recoverable, false);
+    assert_eq!(error.inner, ParseError::PredicateValue {});
+}
+
+pub fn all_match_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("allMatch", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let value = predicate_template(p)?;
+    return Ok(PredicateFuncValue::AllMatch { space0, value });
+}
+
+pub fn any_match_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("anyMatch", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let value = predicate_template(p)?;
+    return Ok(PredicateFuncValue::AnyMatch { space0, value });
+}
+
+#[test]
+fn test_match_predicates() {
+    let mut parser = Parser::init("allMatch \"^[a-z]+$\"");
+    assert_eq!(
+        all_match_predicate(&mut parser).unwrap(),
+        PredicateFuncValue::AllMatch {
+            space0: Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::init(1, 9, 1, 10),
+            },
+            value: HurlTemplate {
+                elements: vec![HurlTemplateElement::Literal {
+                    value: HurlString2 {
+                        value: String::from("^[a-z]+$"),
+                        encoded: Some(String::from("^[a-z]+$")),
+                    }
+                }],
+                delimiter: String::from("\""),
+                source_info: SourceInfo::init(1, 10, 1, 20),
+            },
+        }
+    );
+
+    let mut parser = Parser::init("anyMatch \"x\"");
+    match any_match_predicate(&mut parser).unwrap() {
+        PredicateFuncValue::AnyMatch { value, .. } => assert_eq!(value.delimiter, "\""),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_predicate_variable() {
+    let mut parser = Parser::init("equals {{count}}");
+    match predicate_func_value(&mut parser).unwrap() {
+        PredicateFuncValue::EqualString { value, .. } => {
+            assert!(value.is_expression());
+            assert_eq!(value.source_info, SourceInfo::init(1, 8, 1, 17));
+        }
+        _ => panic!(),
+    }
+
+    let mut parser = Parser::init("countEquals {{count}}");
+    match predicate_func_value(&mut parser).unwrap() {
+        PredicateFuncValue::CountEqualExpression { value, .. } => assert_eq!(value.variable.name, "count"),
+        _ => panic!(),
+    }
+
+    let mut parser = Parser::init("matches {{pattern}}");
+    match predicate_func_value(&mut parser).unwrap() {
+        PredicateFuncValue::Match { value, .. } => assert!(value.is_expression()),
+        _ => panic!(),
+    }
+
+    let mut parser = Parser::init("contains \"{{name}}\"");
+    match predicate_func_value(&mut parser).unwrap() {
+        PredicateFuncValue::Contain { value, .. } => assert!(!value.is_expression()),
+        _ => panic!(),
+    }
+}
+
+pub fn validate_schema_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("validatesSchema", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let value = schema_file(p)?;
+    return Ok(PredicateFuncValue::ValidateSchema { space0, value });
+}
+
+pub fn validate_xsd_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("validatesXsd", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let value = schema_file(p)?;
+    return Ok(PredicateFuncValue::ValidateXsd { space0, value });
+}
+
+pub fn validate_dtd_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("validatesDtd", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let value = schema_file(p)?;
+    return Ok(PredicateFuncValue::ValidateDtd { space0, value });
+}
+
+// schemas are only given as files
+fn schema_file(p: &mut Parser) -> ParseResult<'static, Bytes> {
+    let start = p.state.clone();
+    return match file_bytes(p) {
+        Ok(value) => Ok(value),
+        Err(Error { recoverable: false, pos, inner }) => Err(Error { pos, recoverable: false, inner }),
+        Err(_) => Err(Error {
+            pos: start.pos,
+            recoverable: false,
+            inner: ParseError::PredicateValue {},
+        }),
+    };
+}
+
+#[test]
+fn test_validate_schema_predicate() {
+    let mut parser = Parser::init("validatesSchema file,user.json;");
+    assert_eq!(
+        validate_schema_predicate(&mut parser).unwrap(),
+        PredicateFuncValue::ValidateSchema {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 16, 1, 17) },
+            value: Bytes::File {
+                space0: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 22, 1, 22) },
+                filename: Filename { value: String::from("user.json"), source_info: SourceInfo::init(1, 22, 1, 31) },
+                space1: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 31, 1, 31) },
+            },
+        }
+    );
+
+    let mut parser = Parser::init("validatesSchema \"user.json\"");
+    let error = validate_schema_predicate(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 17 });
+    assert_eq!(error.inner, ParseError::PredicateValue {});
+    assert_eq!(error.recoverable, false);
+
+    let mut parser = Parser::init("validatesXsd file,food.xsd;");
+    match validate_xsd_predicate(&mut parser).unwrap() {
+        PredicateFuncValue::ValidateXsd { value: Bytes::File { filename, .. }, .. } => assert_eq!(filename.value, "food.xsd"),
+        _ => panic!(),
+    }
+
+    let mut parser = Parser::init("validatesDtd file,note.dtd;");
+    match validate_dtd_predicate(&mut parser).unwrap() {
+        PredicateFuncValue::ValidateDtd { value: Bytes::File { filename, .. }, .. } => assert_eq!(filename.value, "note.dtd"),
+        _ => panic!(),
+    }
+}
+
+pub fn is_iso_date_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("isIsoDate", p)?;
+    return Ok(PredicateFuncValue::IsIsoDate {});
+}
+
+pub fn before_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("before", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let value = date_value(p)?;
+    return Ok(PredicateFuncValue::Before { space0, value });
+}
+
+pub fn after_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("after", p)?;
+    let space0 = one_or_more_spaces(p)?;
+    let value = date_value(p)?;
+    return Ok(PredicateFuncValue::After { space0, value });
+}
+
+// now, now + 1d, now - 30m or a date string ("2020-01-01")
+fn date_value(p: &mut Parser) -> ParseResult<'static, DateValue> {
+    let start = p.state.clone();
+    if try_literal("now", p).is_ok() {
+        let offset = optional(|p1| date_offset(p1), p)?;
+        return Ok(DateValue::Now { offset });
+    }
+    return match predicate_template(p) {
+        Ok(value) => Ok(DateValue::Template { value }),
+        Err(Error { recoverable: false, pos, inner }) => Err(Error { pos, recoverable: false, inner }),
+        Err(_) => Err(Error {
+            pos: start.pos,
+            recoverable: false,
+            inner: ParseError::PredicateValue {},
+        }),
+    };
+}
+
+fn date_offset(p: &mut Parser) -> ParseResult<'static, DateOffset> {
+    let space0 = zero_or_more_spaces(p)?;
+    let negative = match p.clone().next_char() {
+        Some('+') => false,
+        Some('-') => true,
+        _ => return Err(Error {
+            pos: p.state.pos.clone(),
+            recoverable: true,
+            inner: ParseError::Expecting { value: String::from("+") },
+        }),
+    };
+    p.next_char();
+    let space1 = zero_or_more_spaces(p)?;
+    let value = nonrecover(|p1| natural(p1), p)?;
+    let start = p.state.clone();
+    let unit = match p.next_chars_while(|c| c.is_alphanumeric()).as_str() {
+        "s" => DurationUnit::Second,
+        "m" => DurationUnit::Minute,
+        "h" => DurationUnit::Hour,
+        "d" => DurationUnit::Day,
+        "w" => DurationUnit::Week,
+        _ => return Err(Error {
+            pos: start.pos,
+            recoverable: false,
+            inner: ParseError::Expecting { value: String::from("duration unit (s, m, h, d or w)") },
+        }),
+    };
+    return Ok(DateOffset { space0, negative, space1, value, unit });
+}
+
+#[test]
+fn test_date_predicate() {
+    let mut parser = Parser::init("isIsoDate");
+    assert_eq!(predicate_func_value(&mut parser).unwrap(), PredicateFuncValue::IsIsoDate {});
+
+    let mut parser = Parser::init("after now");
+    assert_eq!(after_predicate(&mut parser).unwrap(), PredicateFuncValue::After {
+        space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 6, 1, 7) },
+        value: DateValue::Now { offset: None },
+    });
+
+    let mut parser = Parser::init("before now - 2d");
+    assert_eq!(before_predicate(&mut parser).unwrap(), PredicateFuncValue::Before {
+        space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 7, 1, 8) },
+        value: DateValue::Now {
+            offset: Some(DateOffset {
+                space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 11, 1, 12) },
+                negative: true,
+                space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 13, 1, 14) },
+                value: 2,
+                unit: DurationUnit::Day,
+            })
+        },
+    });
+
+    let mut parser = Parser::init("after now # comment");
+    match after_predicate(&mut parser).unwrap() {
+        PredicateFuncValue::After { value: DateValue::Now { offset: None }, .. } => {}
+        _ => panic!(),
+    }
+    assert_eq!(parser.state.cursor, 9);
+
+    let mut parser = Parser::init("before \"2020-01-01\"");
+    match before_predicate(&mut parser).unwrap() {
+        PredicateFuncValue::Before { value: DateValue::Template { value }, .. } => assert_eq!(value.delimiter, "\""),
+        _ => panic!(),
+    }
+
+    let mut parser = Parser::init("before now + 2y");
+    let error = before_predicate(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 15 });
+    assert_eq!(error.recoverable, false);
+
+    let mut parser = Parser::init("after tomorrow");
+    let error = after_predicate(&mut parser).err().unwrap();
+    assert_eq!(error.pos, Pos { line: 1, column: 7 });
+    assert_eq!(error.inner, ParseError::PredicateValue {});
+}
+
 pub fn exist_predicate(p: &mut Parser) -> ParseResult<'static, PredicateFuncValue> {
     try_literal("exists", p)?;
     return Ok(PredicateFuncValue::Exist{  });
@@ -2049,6 +3118,8 @@ enum PredicateValue {
     Float { value: Float },
     Bool { value: bool },
     Template { value: HurlTemplate },
+    Bytes { value: Bytes },
+    Json { value: String },
 }
 
 fn predicate_value(p: &mut Parser) -> ParseResult<'static, PredicateValue> {
@@ -2066,7 +3137,19 @@ fn predicate_value(p: &mut Parser) -> ParseResult<'static, PredicateValue> {
                 Ok(value) => Ok(PredicateValue::Int { value }),
                 Err(e) => Err(e),
             },
-            |p1| match hurl_value_json(p1) {
+            |p1| match file_bytes(p1) {
+                Ok(value) => Ok(PredicateValue::Bytes { value }),
+                Err(e) => Err(e),
+            },
+            |p1| match base64_bytes(p1) {
+                Ok(value) => Ok(PredicateValue::Bytes { value }),
+                Err(e) => Err(e),
+            },
+            |p1| match json_predicate_value(p1) {
+                Ok(value) => Ok(PredicateValue::Json { value }),
+                Err(e) => Err(e),
+            },
+            |p1| match predicate_template(p1) {
                 Ok(value) => Ok(PredicateValue::Template { value }),
                 Err(e) => Err(e),
             },
@@ -2075,6 +3158,36 @@ fn predicate_value(p: &mut Parser) -> ParseResult<'static, PredicateValue> {
     );
 }
 
+// quoted template or single variable ({{var}})
+// the variable takes the type of the value it is compared with
+fn predicate_template(p: &mut Parser) -> ParseResult<'static, HurlTemplate> {
+    let start = p.state.clone();
+    match expr::parse(p) {
+        Ok(value) => return Ok(HurlTemplate {
+            elements: vec![HurlTemplateElement::Expression { value }],
+            delimiter: String::from(""),
+            source_info: SourceInfo { start: start.pos, end: p.state.pos.clone() },
+        }),
+        Err(Error { recoverable: false, pos, inner }) => return Err(Error { pos, recoverable: false, inner }),
+        Err(_) => p.state = start,
+    }
+    return hurl_value_json(p);
+}
+
+// json array or object (json strings are parsed as templates)
+// a json object can not start with {{ (reserved for variables)
+fn json_predicate_value(p: &mut Parser) -> ParseResult<'static, String> {
+    let remaining = p.clone().remaining();
+    if !remaining.starts_with('[') && !remaining.starts_with('{') || remaining.starts_with("{{") {
+        return Err(Error {
+            pos: p.state.pos.clone(),
+            recoverable: true,
+            inner: ParseError::Json {},
+        });
+    }
+    return json::json_value(p);
+}
+
 #[test]
 fn test_predicate_value() {
     let mut parser = Parser::init("true");
@@ -2093,9 +3206,21 @@ fn test_predicate_value() {
     assert_eq!(
         predicate_value(&mut parser).unwrap(),
         PredicateValue::Float {
-            value: Float { int: 1, decimal: 100000000000000000, decimal_digits: 1 }
+            value: Float { value: 1.1, encoded: String::from("1.1") }
         }
     );
+
+    let mut parser = Parser::init("{ \"id\": 1, \"tags\": [\"a\"] } # comment");
+    assert_eq!(
+        predicate_value(&mut parser).unwrap(),
+        PredicateValue::Json { value: String::from("{ \"id\": 1, \"tags\": [\"a\"] }") }
+    );
+    assert_eq!(parser.state.cursor, 26);
+
+    let mut parser = Parser::init("[1, 2");
+    let error = predicate_value(&mut parser).err().unwrap();
+    assert_eq!(error.inner, ParseError::Json {});
+    assert_eq!(error.recoverable, false);
 }
 
 // endregion
@@ -644,6 +644,8 @@ fn test_hurl_value() {
                             name: String::from("name"),
                             source_info: SourceInfo::init(1, 7, 1, 11),
                         },
+                        arguments: vec![],
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::from(""),
                             source_info: SourceInfo::init(1, 11, 1, 11),
@@ -775,6 +777,8 @@ fn test_hurl_value_json_with_variable() {
                             name: String::from("name"),
                             source_info: SourceInfo::init(1, 16, 1, 20),
                         },
+                        arguments: vec![],
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::from(""),
                             source_info: SourceInfo::init(1, 20, 1, 20),
@@ -928,6 +932,8 @@ fn test_hurl_value_text_with_variable() {
                         name: String::from("name"),
                         source_info: SourceInfo::init(1, 3, 1, 7),
                     },
+                    arguments: vec![],
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::from(""),
                         source_info: SourceInfo::init(1, 7, 1, 7),
@@ -1159,7 +1165,8 @@ pub fn test_integer() {
 
 // an integer is parsed ok as float => no like a computer language
 pub fn float(p: &mut Parser) -> ParseResult<'static, Float> {
-    let int = integer(p)?;
+    let start = p.state.cursor;
+    integer(p)?;
 
     try_literal(".", p)?;
 
@@ -1179,39 +1186,39 @@ pub fn float(p: &mut Parser) -> ParseResult<'static, Float> {
             inner: ParseError::Expecting {value: String::from("natural")},
         });
     }
-    let decimal = format!("{:0<18}", s).parse().unwrap();
-    let decimal_digits = s.len();
-    return Ok(Float { int, decimal, decimal_digits });
+    let encoded: String = p.buffer[start..p.state.cursor].iter().collect();
+    let value = encoded.parse().unwrap();
+    return Ok(Float { value, encoded });
 }
 
 #[test]
 fn test_float() {
     let mut parser = Parser::init("1.0");
-    assert_eq!(float(&mut parser).unwrap(), Float { int: 1, decimal: 0, decimal_digits: 1 });
+    assert_eq!(float(&mut parser).unwrap(), Float { value: 1.0, encoded: String::from("1.0") });
     assert_eq!(parser.state.cursor, 3);
 
     let mut parser = Parser::init("-1.0");
-    assert_eq!(float(&mut parser).unwrap(), Float { int: -1, decimal: 0, decimal_digits: 1 });
+    assert_eq!(float(&mut parser).unwrap(), Float { value: -1.0, encoded: String::from("-1.0") });
     assert_eq!(parser.state.cursor, 4);
 
     let mut parser = Parser::init("1.1");
-    assert_eq!(float(&mut parser).unwrap(), Float { int: 1, decimal: 100000000000000000, decimal_digits: 1 });
+    assert_eq!(float(&mut parser).unwrap(), Float { value: 1.1, encoded: String::from("1.1") });
     assert_eq!(parser.state.cursor, 3);
 
     let mut parser = Parser::init("1.100");
-    assert_eq!(float(&mut parser).unwrap(), Float { int: 1, decimal: 100000000000000000, decimal_digits: 3 });
+    assert_eq!(float(&mut parser).unwrap(), Float { value: 1.1, encoded: String::from("1.100") });
     assert_eq!(parser.state.cursor, 5);
 
     let mut parser = Parser::init("1.01");
-    assert_eq!(float(&mut parser).unwrap(), Float { int: 1, decimal: 10000000000000000, decimal_digits: 2 });
+    assert_eq!(float(&mut parser).unwrap(), Float { value: 1.01, encoded: String::from("1.01") });
     assert_eq!(parser.state.cursor, 4);
 
     let mut parser = Parser::init("1.010");
-    assert_eq!(float(&mut parser).unwrap(), Float { int: 1, decimal: 10000000000000000, decimal_digits: 3 });
+    assert_eq!(float(&mut parser).unwrap(), Float { value: 1.01, encoded: String::from("1.010") });
     assert_eq!(parser.state.cursor, 5);
 
     let mut parser = Parser::init("-0.333333333333333333");
-    assert_eq!(float(&mut parser).unwrap(), Float { int: 0, decimal: 333333333333333333, decimal_digits: 18 });
+    assert_eq!(float(&mut parser).unwrap(), Float { value: -0.333333333333333333, encoded: String::from("-0.333333333333333333") });
     assert_eq!(parser.state.cursor, 21);
 }
 
@@ -31,6 +31,8 @@ fn test_template() {
                 TemplateElement::Expression(Expr {
                     space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 14, 1, 20) },
                     variable: Variable { name: String::from("name"), source_info: SourceInfo::init(1, 20, 1, 29) },
+                    arguments: vec![],
+                    filters: vec![],
                     space1: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 29, 1, 29) },
                 }),
                 TemplateElement::Literal(String::from("!")),
@@ -113,6 +115,8 @@ fn test_template_element_expression() {
                     name: String::from("name"),
                     source_info: SourceInfo::init(1, 4, 1, 8),
                 },
+                arguments: vec![],
+                filters: vec![],
                 space1: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 8, 1, 8) },
             }
         }
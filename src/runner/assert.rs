@@ -3,7 +3,8 @@
 use std::collections::HashMap;
 
 #[cfg(test)]
-use crate::core::core::{SourceInfo, Value};
+use crate::core::core::SourceInfo;
+use crate::core::core::Value;
 use crate::http;
 
 use super::core::{Error, RunnerError};
@@ -57,13 +58,13 @@ impl AssertResult {
             AssertResult::Header { actual, expected, source_info } => {
                 match actual {
                     Err(e) => Some(e),
-                    Ok(s) => {
-                        if s == expected {
+                    Ok(values) => {
+                        if values.contains(&expected) {
                             None
                         } else {
                             Some(Error {
                                 source_info,
-                                inner: RunnerError::AssertHeaderValueError { actual: s },
+                                inner: RunnerError::AssertHeaderValueError { actual: values.join(", ") },
                                 assert: false,
                             })
                         }
@@ -120,23 +121,52 @@ fn test_invalid_xpath() {}
 
 
 impl Assert {
-    pub fn eval(self, _variables: &HashMap<String, String>, http_response: http::response::Response) -> AssertResult {
-        let actual = self.query.eval(http_response);
+    pub fn eval(self, _variables: &HashMap<String, Value>, namespaces: &HashMap<String, String>, http_request: http::request::Request, http_response: http::response::Response, context_dir: String) -> AssertResult {
+        let actual = self.query.eval(namespaces, http_request, http_response);
         let source_info = self.predicate.clone().predicate_func.source_info;
         let predicate_result = match actual.clone() {
             Err(_) => None,
-            Ok(actual) => Some(self.predicate.eval(_variables, actual.clone()))
+            Ok(actual) => Some(self.predicate.eval(_variables, actual.clone(), context_dir))
         };
 
         return AssertResult::Explicit { actual, source_info, predicate_result };
     }
 }
 
+impl HeaderAssert {
+    // a predicate must be true for one of the headers with this name
+    // a negated predicate must be true for all of them
+    pub fn eval(self, variables: &HashMap<String, Value>, http_response: http::response::Response, context_dir: String) -> AssertResult {
+        let source_info = self.predicate.clone().predicate_func.source_info;
+        let values = http_response.get_headers(self.name.value.as_str());
+        if values.is_empty() {
+            return AssertResult::Explicit {
+                actual: Ok(Value::None),
+                source_info,
+                predicate_result: Some(self.predicate.eval(variables, Value::None, context_dir)),
+            };
+        }
+        let results: Vec<(Value, PredicateResult)> = values.iter()
+            .map(|value| (Value::String(value.clone()), self.predicate.clone().eval(variables, Value::String(value.clone()), context_dir.clone())))
+            .collect();
+        let selected = if self.predicate.not {
+            results.iter().find(|(_, result)| result.is_err())
+        } else {
+            results.iter().find(|(_, result)| result.is_ok())
+        };
+        let (actual, predicate_result) = match selected {
+            Some(result) => result.clone(),
+            None => results[0].clone(),
+        };
+        return AssertResult::Explicit { actual: Ok(actual), source_info, predicate_result: Some(predicate_result) };
+    }
+}
+
 #[test]
 fn test_eval() {
     let variables = HashMap::new();
     assert_eq!(
-        assert_count_user().eval(&variables, http::response::xml_three_users_http_response()),
+        assert_count_user().eval(&variables, &HashMap::new(), http::request::hello_http_request(), http::response::xml_three_users_http_response(), String::from("current_dir")),
         AssertResult::Explicit {
             actual: Ok(Value::Nodeset(3)),
             source_info: SourceInfo::init(1, 14, 1, 27),
@@ -153,4 +183,86 @@ fn test_eval() {
 //    assert_eq!(error.source_info, SourceInfo::init(1,1,1,13));
 }
 
+#[test]
+fn test_eval_header_assert() {
+    let variables = HashMap::new();
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+    let header_assert = |not: bool, value: PredicateFuncValue| HeaderAssert {
+        line_terminators: vec![],
+        space0: whitespace.clone(),
+        name: HurlString { value: String::from("Set-Cookie"), encoded: None, source_info: SourceInfo::init(1, 1, 1, 11) },
+        space1: whitespace.clone(),
+        predicate: Predicate {
+            not,
+            space0: whitespace.clone(),
+            predicate_func: PredicateFunc { source_info: SourceInfo::init(1, 12, 1, 30), value },
+        },
+        line_terminator0: LineTerminator { space0: whitespace.clone(), comment: None, newline: whitespace.clone() },
+    };
+    let contain = |value: &str| PredicateFuncValue::Contain {
+        space0: whitespace.clone(),
+        value: HurlTemplate {
+            elements: vec![HurlTemplateElement::Literal { value: HurlString2 { value: value.to_string(), encoded: None } }],
+            delimiter: String::from("\""),
+            source_info: SourceInfo::init(1, 21, 1, 30),
+        },
+    };
+    let http_response = http::response::Response {
+        version: http::response::Version::Http10,
+        status: 200,
+        url: String::from("http://localhost:8000/"),
+        headers: vec![
+            http::core::Header { name: String::from("Set-Cookie"), value: String::from("LSID=DQAAAKEaem_vYg") },
+            http::core::Header { name: String::from("set-cookie"), value: String::from("HSID=AYQEVnDKrdst") },
+        ],
+        body: vec![],
+    };
+
+    // one of the headers contains the value
+    assert_eq!(
+        header_assert(false, contain("HSID")).eval(&variables, http_response.clone(), String::from("current_dir")),
+        AssertResult::Explicit {
+            actual: Ok(Value::String(String::from("HSID=AYQEVnDKrdst"))),
+            source_info: SourceInfo::init(1, 12, 1, 30),
+            predicate_result: Some(Ok(())),
+        }
+    );
+
+    // a negated predicate must hold for all the headers
+    assert_eq!(
+        header_assert(true, contain("HSID")).eval(&variables, http_response.clone(), String::from("current_dir")),
+        AssertResult::Explicit {
+            actual: Ok(Value::String(String::from("HSID=AYQEVnDKrdst"))),
+            source_info: SourceInfo::init(1, 12, 1, 30),
+            predicate_result: Some(Err(Error {
+                source_info: SourceInfo::init(1, 12, 1, 30),
+                inner: RunnerError::PredicateValue(Value::String(String::from("HSID=AYQEVnDKrdst"))),
+                assert: false,
+            })),
+        }
+    );
+    assert_eq!(
+        header_assert(true, contain("SSID")).eval(&variables, http_response.clone(), String::from("current_dir")),
+        AssertResult::Explicit {
+            actual: Ok(Value::String(String::from("LSID=DQAAAKEaem_vYg"))),
+            source_info: SourceInfo::init(1, 12, 1, 30),
+            predicate_result: Some(Ok(())),
+        }
+    );
+
+    // absent header
+    let http_response = http::response::hello_http_response();
+    assert_eq!(
+        header_assert(true, PredicateFuncValue::Exist {}).eval(&variables, http_response, String::from("current_dir")),
+        AssertResult::Explicit {
+            actual: Ok(Value::None),
+            source_info: SourceInfo::init(1, 12, 1, 30),
+            predicate_result: Some(Ok(())),
+        }
+    );
+}
+
 // endregion
\ No newline at end of file
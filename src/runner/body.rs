@@ -1,5 +1,4 @@
-//use crate::core::core::Value;
-
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
 //#[cfg(test)]
@@ -7,14 +6,19 @@ use std::path::Path;
 
 #[cfg(test)]
 use crate::core::core::SourceInfo;
+use crate::core::core::Value;
 
 //use super::core::{Error, RunnerError};
 use super::core::{Error, RunnerError};
 use super::super::core::ast::*;
+use super::template::eval_json;
 
 impl Body {
-    pub fn eval(self, context_dir: String) -> Result<Vec<u8>, Error> {
-        return self.value.eval(context_dir);
+    pub fn eval(self, variables: &HashMap<String, Value>, context_dir: String) -> Result<Vec<u8>, Error> {
+        return match self.value {
+            Bytes::Json { value } => Ok(eval_json(value, self.space0.source_info.end, variables)?.into_bytes()),
+            _ => self.value.eval(context_dir),
+        };
     }
 }
 
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 #[cfg(test)]
 use crate::core::core::{Pos, SourceInfo};
 use crate::core::core::Value;
@@ -17,8 +19,18 @@ pub type CaptureResult = Result<(String, Value), Error>;
 
 
 impl Capture {
-    pub fn eval(self, http_response: http::response::Response) -> CaptureResult {
-        let value = self.query.clone().eval(http_response)?;
+    pub fn eval(self, namespaces: &HashMap<String, String>, http_request: http::request::Request, http_response: http::response::Response) -> CaptureResult {
+        let value = self.query.clone().eval(namespaces, http_request, http_response)?;
+
+        // a css query captures the first selected node
+        // and a jsonpath query its first match
+        let value = match value {
+            Value::List(values) if self.query.clone().is_css() || self.query.clone().is_jsonpath() => match values.get(0) {
+                None => Value::None,
+                Some(value) => value.clone(),
+            },
+            _ => value,
+        };
 //        if !value.is_scalar() {
 //            return Err(Error {
 //                source_info: self.query.clone().source_info,
@@ -84,7 +96,7 @@ fn test_invalid_xpath() {
         },
     };
 
-    let error = capture.eval(http::response::xml_three_users_http_response()).err().unwrap();
+    let error = capture.eval(&HashMap::new(), http::request::hello_http_request(), http::response::xml_three_users_http_response()).err().unwrap();
     assert_eq!(error.source_info.start, Pos { line: 1, column: 7 });
     assert_eq!(error.inner, RunnerError::QueryInvalidXpathEval)
 }
@@ -116,6 +128,7 @@ fn test_capture_unsupported() {
                     source_info: SourceInfo::init(1, 7, 1, 13),
                 },
             },
+            filters: vec![],
         },
         line_terminator0: LineTerminator {
             space0: whitespace.clone(),
@@ -133,8 +146,33 @@ fn test_capture_unsupported() {
 
 #[test]
 fn test_capture() {
-    assert_eq!(user_count_capture().eval(http::response::xml_three_users_http_response()).unwrap(),
-               (String::from("UserCount"), Value::from_f64(3.0))
+    assert_eq!(user_count_capture().eval(&HashMap::new(), http::request::hello_http_request(), http::response::xml_three_users_http_response()).unwrap(),
+               (String::from("UserCount"), Value::Float(3.0))
+    );
+}
+
+#[test]
+fn test_capture_css() {
+    let whitespace = Whitespace { value: String::from(""), source_info: SourceInfo::init(0, 0, 0, 0) };
+    let capture = Capture {
+        line_terminators: vec![],
+        space0: whitespace.clone(),
+        name: HurlString {
+            value: "charset".to_string(),
+            encoded: None,
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        },
+        space1: whitespace.clone(),
+        space2: whitespace.clone(),
+        query: query::css_query("meta::attr(charset)"),
+        line_terminator0: LineTerminator {
+            space0: whitespace.clone(),
+            comment: None,
+            newline: whitespace.clone(),
+        },
+    };
+    assert_eq!(capture.eval(&HashMap::new(), http::request::hello_http_request(), http::response::html_http_response()).unwrap(),
+               (String::from("charset"), Value::String(String::from("UTF-8")))
     );
 }
 
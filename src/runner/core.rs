@@ -9,9 +9,17 @@ use crate::http;
 pub struct HurlResult {
     pub filename: String,
     pub entries: Vec<EntryResult>,
+    pub dataset_row: Option<usize>,
 }
 
 impl HurlResult {
+    pub fn name(&self) -> String {
+        return match self.dataset_row {
+            None => self.filename.clone(),
+            Some(row) => format!("{} [row {}]", self.filename, row),
+        };
+    }
+
     pub fn errors(self) -> Vec<Error> {
         return self.entries.iter().flat_map(|e| e.errors.clone()).collect();
     }
@@ -22,15 +30,17 @@ pub struct EntryResult {
     pub request: Option<http::request::Request>,
     pub response: Option<http::response::Response>,
     pub captures: Vec<(String, Value)>,
+    pub generated: Vec<(String, Value)>,
     pub asserts: Vec<AssertResult>,
     pub errors: Vec<Error>,
+    pub skipped: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AssertResult {
     Version { actual: String, expected: String, source_info: SourceInfo },
     Status { actual: u64, expected: u64, source_info: SourceInfo },
-    Header { actual: Result<String, Error>, expected: String, source_info: SourceInfo },
+    Header { actual: Result<Vec<String>, Error>, expected: String, source_info: SourceInfo },
     Explicit { actual: Result<Value, Error>, source_info: SourceInfo, predicate_result: Option<PredicateResult> },
 }
 
@@ -52,20 +62,27 @@ pub struct Error {
 #[allow(dead_code)]
 pub enum RunnerError {
     TemplateVariableNotDefined { name: String },
+    TemplateFunctionNotDefined { name: String },
+    TemplateFunctionArguments { name: String, expected: String },
+    EnvVariableNotDefined { name: String },
     InvalidURL(String),
     HttpConnection { url: String, message: String },
     FileReadAccess { value: String },
+    IncludeCycle { value: String },
+    IncludeParsing { value: String },
 
     // Capture
     //CaptureNonScalarUnsupported,
     //??CaptureError {},
     InvalidUtf8,
+    InvalidDecoding { charset: String },
 
     // Query
     QueryHeaderNotFound,
     QueryCookieNotFound,
     QueryInvalidJsonpathExpression,
     QueryInvalidXpathEval,
+    QueryInvalidCssSelector,
     QueryInvalidXml,
     QueryInvalidJson,
 
@@ -73,7 +90,16 @@ pub enum RunnerError {
     // Predicate
     PredicateType,
     PredicateValue(Value),
-    InvalidRegex(),
+    PredicateJsonDiff(Vec<String>),
+    PredicateInvalidJson(String),
+    PredicateSchema(Vec<String>),
+    InvalidJsonSchema(String),
+    InvalidXmlSchema(Vec<String>),
+    InvalidRegex(String),
+    InvalidDate(String),
+    InvalidDateFormat(String),
+    FilterInvalidInput(Value),
+    InvalidJsonValue(Value),
 
     AssertHeaderValueError { actual: String },
     AssertVersion { actual: String },
@@ -91,12 +117,27 @@ impl FormatError for Error {
         return match &self.inner {
             RunnerError::InvalidURL(..) => format!("Invalid url"),
             RunnerError::TemplateVariableNotDefined { .. } => format!("Undefined Variable"),
+            RunnerError::TemplateFunctionNotDefined { .. } => format!("Undefined Function"),
+            RunnerError::TemplateFunctionArguments { .. } => format!("Invalid Function Arguments"),
+            RunnerError::EnvVariableNotDefined { .. } => format!("Undefined Environment Variable"),
             RunnerError::HttpConnection { .. } => format!("Http Connection"),
             RunnerError::PredicateValue { .. } => format!("Assert - Predicate Value Failed"),
-            RunnerError::InvalidRegex {} => format!("Invalid regex"),
+            RunnerError::PredicateJsonDiff { .. } => format!("Assert - Predicate Value Failed"),
+            RunnerError::PredicateSchema { .. } => format!("Assert - Predicate Value Failed"),
+            RunnerError::InvalidJsonSchema(..) => format!("Invalid JSON Schema"),
+            RunnerError::InvalidXmlSchema(..) => format!("Invalid XML Schema"),
+            RunnerError::InvalidRegex(..) => format!("Invalid regex"),
+            RunnerError::InvalidDate(..) => format!("Invalid date"),
+            RunnerError::InvalidDateFormat(..) => format!("Invalid date format"),
+            RunnerError::FilterInvalidInput(..) => format!("Invalid filter input"),
+            RunnerError::InvalidJsonValue(..) => format!("Invalid Json Value"),
+            RunnerError::PredicateInvalidJson(..) => format!("Invalid Json"),
             RunnerError::FileReadAccess { .. } => format!("File ReadAccess"),
+            RunnerError::IncludeCycle { .. } => format!("Include Cycle"),
+            RunnerError::IncludeParsing { .. } => format!("Include Parsing"),
             RunnerError::QueryInvalidXml { .. } => format!("Invalid XML"),
             RunnerError::QueryInvalidXpathEval {} => format!("Invalid xpath expression"),
+            RunnerError::QueryInvalidCssSelector {} => format!("Invalid css selector"),
             RunnerError::QueryHeaderNotFound {} => format!("Header not Found"),
             RunnerError::QueryCookieNotFound {} => format!("Cookie not Found"),
             RunnerError::AssertHeaderValueError { .. } => format!("Assert Header Value"),
@@ -104,6 +145,7 @@ impl FormatError for Error {
             RunnerError::AssertStatus { .. } => format!("Assert Status"),
             RunnerError::QueryInvalidJson { .. } => format!("Invalid Json"),
             RunnerError::InvalidUtf8 { .. } => format!("Invalid Utf8"),
+            RunnerError::InvalidDecoding { .. } => format!("Invalid Decoding"),
             RunnerError::QueryInvalidJsonpathExpression { .. } => format!("Invalid jsonpath"),
             RunnerError::PredicateType { .. } => format!("Assert - Inconsistent predicate type"),
         };
@@ -113,23 +155,54 @@ impl FormatError for Error {
         return match &self.inner {
             RunnerError::InvalidURL(url) => format!("Invalid url '{}'", url),
             RunnerError::TemplateVariableNotDefined { name } => format!("You must set the variable {}", name),
+            RunnerError::TemplateFunctionNotDefined { name } => format!("the function {} does not exist (available: newUuid, now, randomInt, env)", name),
+            RunnerError::TemplateFunctionArguments { name, expected } => format!("the function {} expects {}", name, expected),
+            RunnerError::EnvVariableNotDefined { name } => format!("You must set the environment variable {}", name),
             RunnerError::HttpConnection { url, message } => format!("can not connect to {} ({})", url, message),
             RunnerError::AssertVersion { actual, .. } => format!("actual value is {}", actual),
             RunnerError::AssertStatus { actual, .. } => format!("actual value is {}", actual),
             RunnerError::PredicateValue(value) => format!("actual value is {}", value.to_string()),
-            RunnerError::InvalidRegex {} => format!("Regex expression is not valid"),
+            RunnerError::PredicateJsonDiff(diffs) => format!("actual value differs: {}", diffs.join(", ")),
+            RunnerError::PredicateSchema(violations) => format!("actual value does not validate the schema:\n{}", violations.join("\n")),
+            RunnerError::InvalidJsonSchema(message) => format!("{}", message),
+            RunnerError::InvalidXmlSchema(errors) => format!("the schema can not be parsed:\n{}", errors.join("\n")),
+            RunnerError::InvalidRegex(message) => format!("Regex expression is not valid ({})", message),
+            RunnerError::InvalidDate(value) => format!("{} is not an ISO 8601 or http date", value),
+            RunnerError::InvalidDateFormat(message) => format!("the date format is not valid ({})", message),
+            RunnerError::FilterInvalidInput(value) => format!("the filter can not be applied to value {}", value.to_string()),
+            RunnerError::InvalidJsonValue(value) => format!("the value {} can not be represented in json", value.to_string()),
+            RunnerError::PredicateInvalidJson(message) => format!("the expected value is not a valid json ({})", message),
             RunnerError::FileReadAccess { value } => format!("File {} can not be read", value),
+            RunnerError::IncludeCycle { value } => format!("the file {} is already being included", value),
+            RunnerError::IncludeParsing { value } => format!("the file {} can not be parsed", value),
             RunnerError::QueryInvalidXml { .. } => format!("The Http response is not a valid XML"),
             RunnerError::QueryHeaderNotFound {} => format!("This header has not been found in the response"),
             RunnerError::QueryCookieNotFound {} => format!("This cookie has not been found in the response"),
             RunnerError::QueryInvalidXpathEval {} => format!("The xpath expression is not valid"),
+            RunnerError::QueryInvalidCssSelector {} => format!("The css selector is not valid"),
             RunnerError::AssertHeaderValueError { actual } => format!("actual value is {}", actual),
             RunnerError::QueryInvalidJson { .. } => format!("The http response is not a valid json"),
             RunnerError::InvalidUtf8 { .. } => format!("The http response is not a valid utf8 string"),
+            RunnerError::InvalidDecoding { charset } => format!("The http response can not be decoded with charset {}", charset),
             RunnerError::QueryInvalidJsonpathExpression { .. } => format!("the jsonpath expression is not valid"),
             RunnerError::PredicateType { .. } => format!("predicate type inconsistent with value return by query"),
         };
     }
 }
 
-// endregion
\ No newline at end of file
+// endregion
+
+// the error returned by the regex crate spans several lines
+// only the last one (the error itself) is kept
+pub fn regex_error_message(error: regex::Error) -> String {
+    let message = error.to_string();
+    let line = message.lines().filter(|line| !line.trim().is_empty()).last().unwrap_or("");
+    return line.trim().trim_start_matches("error: ").to_string();
+}
+
+#[test]
+#[allow(clippy::invalid_regex)]
+fn test_regex_error_message() {
+    let error = regex::Regex::new("Hello ([0-9]+!").err().unwrap();
+    assert_eq!(regex_error_message(error), String::from("unclosed group"));
+}
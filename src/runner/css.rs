@@ -0,0 +1,397 @@
+// css selectors are evaluated with libxml by translating them into xpath
+//
+// supported syntax:
+// - type selector (div, *), id (#main), class (.alert)
+// - attributes [attr], [attr=value], [attr~=value], [attr^=value], [attr$=value], [attr*=value], [attr|=value]
+// - pseudo-classes :first-child, :last-child, :only-child, :empty, :nth-child(n|odd|even)
+// - combinators: descendant (space), child (>), adjacent sibling (+), general sibling (~)
+// - selector list (,)
+// - pseudo-elements at the end of a selector: ::text (default) and ::attr(name)
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CssError {
+    InvalidSelector,
+}
+
+pub fn to_xpath(selector: &str) -> Result<String, CssError> {
+    let mut reader = Reader::init(selector);
+    let mut xpaths = vec![];
+    loop {
+        reader.skip_spaces();
+        xpaths.push(selector_xpath(&mut reader)?);
+        reader.skip_spaces();
+        if reader.is_eof() {
+            break;
+        }
+        if !reader.try_literal(",") {
+            return Err(CssError::InvalidSelector);
+        }
+    }
+    return Ok(xpaths.join(" | "));
+}
+
+struct Reader {
+    buffer: Vec<char>,
+    cursor: usize,
+}
+
+impl Reader {
+    fn init(s: &str) -> Reader {
+        return Reader { buffer: s.chars().collect(), cursor: 0 };
+    }
+
+    fn is_eof(&self) -> bool {
+        return self.cursor >= self.buffer.len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        return self.buffer.get(self.cursor).cloned();
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.cursor += 1;
+        }
+        return c;
+    }
+
+    fn try_literal(&mut self, s: &str) -> bool {
+        let chars: Vec<char> = s.chars().collect();
+        if self.buffer.len() >= self.cursor + chars.len() && self.buffer[self.cursor..self.cursor + chars.len()] == chars[..] {
+            self.cursor += chars.len();
+            return true;
+        }
+        return false;
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        let chars: Vec<char> = s.chars().collect();
+        return self.buffer.len() >= self.cursor + chars.len() && self.buffer[self.cursor..self.cursor + chars.len()] == chars[..];
+    }
+
+    // return true if at least one space has been read
+    fn skip_spaces(&mut self) -> bool {
+        let start = self.cursor;
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.cursor += 1;
+        }
+        return self.cursor > start;
+    }
+
+    fn identifier(&mut self) -> Result<String, CssError> {
+        let mut s = String::from("");
+        while let Some(c) = self.peek() {
+            if !is_identifier_char(c) {
+                break;
+            }
+            s.push(c);
+            self.cursor += 1;
+        }
+        if s.is_empty() {
+            return Err(CssError::InvalidSelector);
+        }
+        return Ok(s);
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    return c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii();
+}
+
+struct Compound {
+    tag: String,
+    conditions: Vec<String>,
+}
+
+fn selector_xpath(reader: &mut Reader) -> Result<String, CssError> {
+    let mut xpath = step("//", compound(reader)?);
+    loop {
+        let spaces = reader.skip_spaces();
+        match reader.peek() {
+            None | Some(',') => break,
+            Some('>') => {
+                reader.next();
+                reader.skip_spaces();
+                xpath.push_str(step("/", compound(reader)?).as_str());
+            }
+            Some('~') => {
+                reader.next();
+                reader.skip_spaces();
+                xpath.push_str(step("/following-sibling::", compound(reader)?).as_str());
+            }
+            Some('+') => {
+                reader.next();
+                reader.skip_spaces();
+                xpath.push_str(adjacent_step(compound(reader)?).as_str());
+            }
+            Some(':') if !spaces && reader.starts_with("::") => {
+                xpath.push_str(pseudo_element(reader)?.as_str());
+                reader.skip_spaces();
+                return match reader.peek() {
+                    None | Some(',') => Ok(xpath),
+                    _ => Err(CssError::InvalidSelector),
+                };
+            }
+            _ => {
+                if !spaces {
+                    return Err(CssError::InvalidSelector);
+                }
+                xpath.push_str(step("//", compound(reader)?).as_str());
+            }
+        }
+    }
+    return Ok(xpath);
+}
+
+fn step(axis: &str, compound: Compound) -> String {
+    let mut s = format!("{}{}", axis, compound.tag);
+    for condition in compound.conditions {
+        s.push_str(format!("[{}]", condition).as_str());
+    }
+    return s;
+}
+
+fn adjacent_step(compound: Compound) -> String {
+    let mut s = String::from("/following-sibling::*[1]");
+    if compound.tag != "*" {
+        s.push_str(format!("[self::{}]", compound.tag).as_str());
+    }
+    for condition in compound.conditions {
+        s.push_str(format!("[{}]", condition).as_str());
+    }
+    return s;
+}
+
+fn compound(reader: &mut Reader) -> Result<Compound, CssError> {
+    let mut tag = String::from("*");
+    let mut conditions = vec![];
+    let mut empty = true;
+
+    match reader.peek() {
+        Some('*') => {
+            reader.next();
+            empty = false;
+        }
+        Some(c) if is_identifier_char(c) => {
+            // html element names are lowercased by the libxml html parser
+            tag = reader.identifier()?.to_lowercase();
+            empty = false;
+        }
+        _ => {}
+    }
+
+    loop {
+        match reader.peek() {
+            Some('#') => {
+                reader.next();
+                let id = reader.identifier()?;
+                conditions.push(format!("@id={}", xpath_literal(id.as_str())?));
+            }
+            Some('.') => {
+                reader.next();
+                let class = reader.identifier()?;
+                conditions.push(contains_word("@class", class.as_str())?);
+            }
+            Some('[') => {
+                reader.next();
+                conditions.push(attribute(reader)?);
+            }
+            Some(':') if !reader.starts_with("::") => {
+                reader.next();
+                conditions.push(pseudo_class(reader)?);
+            }
+            _ => break,
+        }
+        empty = false;
+    }
+
+    if empty {
+        return Err(CssError::InvalidSelector);
+    }
+    return Ok(Compound { tag, conditions });
+}
+
+fn attribute(reader: &mut Reader) -> Result<String, CssError> {
+    reader.skip_spaces();
+    let name = format!("@{}", reader.identifier()?.to_lowercase());
+    reader.skip_spaces();
+    if reader.try_literal("]") {
+        return Ok(name);
+    }
+
+    let operator = if reader.try_literal("=") {
+        "="
+    } else {
+        let operators = ["~=", "^=", "$=", "*=", "|="];
+        match operators.iter().find(|op| reader.try_literal(op)) {
+            Some(op) => *op,
+            None => return Err(CssError::InvalidSelector),
+        }
+    };
+    reader.skip_spaces();
+    let value = match reader.peek() {
+        Some(quote) if quote == '"' || quote == '\'' => {
+            reader.next();
+            let mut value = String::from("");
+            loop {
+                match reader.next() {
+                    None => return Err(CssError::InvalidSelector),
+                    Some(c) if c == quote => break,
+                    Some(c) => value.push(c),
+                }
+            }
+            value
+        }
+        _ => reader.identifier()?,
+    };
+    reader.skip_spaces();
+    if !reader.try_literal("]") {
+        return Err(CssError::InvalidSelector);
+    }
+
+    let literal = xpath_literal(value.as_str())?;
+    return match operator {
+        "=" => Ok(format!("{}={}", name, literal)),
+        "~=" => contains_word(name.as_str(), value.as_str()),
+        "^=" => Ok(format!("starts-with({}, {})", name, literal)),
+        "$=" => Ok(format!("substring({}, string-length({}) - {} + 1)={}", name, name, value.chars().count(), literal)),
+        "*=" => Ok(format!("contains({}, {})", name, literal)),
+        _ => Ok(format!("({}={} or starts-with({}, {}))", name, literal, name, xpath_literal(format!("{}-", value).as_str())?)),
+    };
+}
+
+fn pseudo_class(reader: &mut Reader) -> Result<String, CssError> {
+    let name = reader.identifier()?;
+    return match name.as_str() {
+        "first-child" => Ok(String::from("not(preceding-sibling::*)")),
+        "last-child" => Ok(String::from("not(following-sibling::*)")),
+        "only-child" => Ok(String::from("not(preceding-sibling::*) and not(following-sibling::*)")),
+        "empty" => Ok(String::from("not(node())")),
+        "nth-child" => {
+            if !reader.try_literal("(") {
+                return Err(CssError::InvalidSelector);
+            }
+            reader.skip_spaces();
+            let arg = reader.identifier()?;
+            reader.skip_spaces();
+            if !reader.try_literal(")") {
+                return Err(CssError::InvalidSelector);
+            }
+            match arg.as_str() {
+                "odd" => Ok(String::from("count(preceding-sibling::*) mod 2=0")),
+                "even" => Ok(String::from("count(preceding-sibling::*) mod 2=1")),
+                _ => match arg.parse::<usize>() {
+                    Ok(n) if n > 0 => Ok(format!("count(preceding-sibling::*)={}", n - 1)),
+                    _ => Err(CssError::InvalidSelector),
+                }
+            }
+        }
+        _ => Err(CssError::InvalidSelector),
+    };
+}
+
+fn pseudo_element(reader: &mut Reader) -> Result<String, CssError> {
+    reader.try_literal("::");
+    let name = reader.identifier()?;
+    return match name.as_str() {
+        "text" => Ok(String::from("")),
+        "attr" => {
+            if !reader.try_literal("(") {
+                return Err(CssError::InvalidSelector);
+            }
+            reader.skip_spaces();
+            let attribute = reader.identifier()?.to_lowercase();
+            reader.skip_spaces();
+            if !reader.try_literal(")") {
+                return Err(CssError::InvalidSelector);
+            }
+            Ok(format!("/@{}", attribute))
+        }
+        _ => Err(CssError::InvalidSelector),
+    };
+}
+
+fn contains_word(name: &str, word: &str) -> Result<String, CssError> {
+    return Ok(format!("contains(concat(' ', normalize-space({}), ' '), {})", name, xpath_literal(format!(" {} ", word).as_str())?));
+}
+
+// xpath 1.0 string literals can not contain both kinds of quotes
+fn xpath_literal(s: &str) -> Result<String, CssError> {
+    if !s.contains('\'') {
+        return Ok(format!("'{}'", s));
+    }
+    if !s.contains('"') {
+        return Ok(format!("\"{}\"", s));
+    }
+    return Err(CssError::InvalidSelector);
+}
+
+
+#[test]
+fn test_type_selector() {
+    assert_eq!(to_xpath("p").unwrap(), "//p");
+    assert_eq!(to_xpath("*").unwrap(), "//*");
+    assert_eq!(to_xpath("  DIV ").unwrap(), "//div");
+}
+
+#[test]
+fn test_id_class_selector() {
+    assert_eq!(to_xpath("#main").unwrap(), "//*[@id='main']");
+    assert_eq!(to_xpath("div.alert").unwrap(), "//div[contains(concat(' ', normalize-space(@class), ' '), ' alert ')]");
+    assert_eq!(
+        to_xpath("p.a.b").unwrap(),
+        "//p[contains(concat(' ', normalize-space(@class), ' '), ' a ')][contains(concat(' ', normalize-space(@class), ' '), ' b ')]"
+    );
+}
+
+#[test]
+fn test_attribute_selector() {
+    assert_eq!(to_xpath("input[name]").unwrap(), "//input[@name]");
+    assert_eq!(to_xpath("input[name=csrf]").unwrap(), "//input[@name='csrf']");
+    assert_eq!(to_xpath("input[name = \"csrf\"]").unwrap(), "//input[@name='csrf']");
+    assert_eq!(to_xpath("a[href^='https']").unwrap(), "//a[starts-with(@href, 'https')]");
+    assert_eq!(to_xpath("a[href$='.pdf']").unwrap(), "//a[substring(@href, string-length(@href) - 4 + 1)='.pdf']");
+    assert_eq!(to_xpath("a[href*=example]").unwrap(), "//a[contains(@href, 'example')]");
+    assert_eq!(to_xpath("p[lang|=en]").unwrap(), "//p[(@lang='en' or starts-with(@lang, 'en-'))]");
+    assert_eq!(to_xpath("p[title~=hello]").unwrap(), "//p[contains(concat(' ', normalize-space(@title), ' '), ' hello ')]");
+}
+
+#[test]
+fn test_pseudo_class() {
+    assert_eq!(to_xpath("li:first-child").unwrap(), "//li[not(preceding-sibling::*)]");
+    assert_eq!(to_xpath("li:last-child").unwrap(), "//li[not(following-sibling::*)]");
+    assert_eq!(to_xpath("li:nth-child(2)").unwrap(), "//li[count(preceding-sibling::*)=1]");
+    assert_eq!(to_xpath("li:nth-child(odd)").unwrap(), "//li[count(preceding-sibling::*) mod 2=0]");
+    assert_eq!(to_xpath("p:empty").unwrap(), "//p[not(node())]");
+}
+
+#[test]
+fn test_combinators() {
+    assert_eq!(to_xpath("div p").unwrap(), "//div//p");
+    assert_eq!(to_xpath("div > p").unwrap(), "//div/p");
+    assert_eq!(to_xpath("div>p").unwrap(), "//div/p");
+    assert_eq!(to_xpath("h1 + p").unwrap(), "//h1/following-sibling::*[1][self::p]");
+    assert_eq!(to_xpath("h1 ~ p").unwrap(), "//h1/following-sibling::p");
+    assert_eq!(to_xpath("h1, h2").unwrap(), "//h1 | //h2");
+}
+
+#[test]
+fn test_pseudo_element() {
+    assert_eq!(to_xpath("a.next::attr(href)").unwrap(), "//a[contains(concat(' ', normalize-space(@class), ' '), ' next ')]/@href");
+    assert_eq!(to_xpath("p::text").unwrap(), "//p");
+    assert_eq!(to_xpath("a::attr(href), img::attr(src)").unwrap(), "//a/@href | //img/@src");
+}
+
+#[test]
+fn test_invalid_selector() {
+    assert_eq!(to_xpath("").err().unwrap(), CssError::InvalidSelector);
+    assert_eq!(to_xpath("div >").err().unwrap(), CssError::InvalidSelector);
+    assert_eq!(to_xpath("p[name").err().unwrap(), CssError::InvalidSelector);
+    assert_eq!(to_xpath("p:hover").err().unwrap(), CssError::InvalidSelector);
+    assert_eq!(to_xpath("a::attr(href) span").err().unwrap(), CssError::InvalidSelector);
+    assert_eq!(to_xpath("div}").err().unwrap(), CssError::InvalidSelector);
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use crate::core::core::Value;
+use crate::core::jsonpath;
+
+// each row of a dataset gives the initial variables of one run
+// - csv: a header row with the variable names, values are strings
+// - json: an array of objects, values keep their json type
+
+pub fn parse_csv(s: &str) -> Result<Vec<HashMap<String, Value>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .from_reader(s.as_bytes());
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut rows = vec![];
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => return Err(e.to_string()),
+        };
+        let mut row = HashMap::new();
+        for (name, value) in headers.iter().zip(record.iter()) {
+            row.insert(name.to_string(), Value::String(value.to_string()));
+        }
+        rows.push(row);
+    }
+    return Ok(rows);
+}
+
+pub fn parse_json(s: &str) -> Result<Vec<HashMap<String, Value>>, String> {
+    let elements = match jsonpath::parse_value(s) {
+        Ok(Value::List(elements)) => elements,
+        Ok(_) => return Err("expecting an array of objects".to_string()),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut rows = vec![];
+    for element in elements {
+        match element {
+            Value::Object(members) => rows.push(members.into_iter().collect()),
+            _ => return Err("expecting an array of objects".to_string()),
+        }
+    }
+    return Ok(rows);
+}
+
+#[test]
+fn test_parse_csv() {
+    let rows = parse_csv("user,tenant\nbob,acme\n\"doe, john\",\n").unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get("user").unwrap(), &Value::String("bob".to_string()));
+    assert_eq!(rows[0].get("tenant").unwrap(), &Value::String("acme".to_string()));
+    assert_eq!(rows[1].get("user").unwrap(), &Value::String("doe, john".to_string()));
+    assert_eq!(rows[1].get("tenant").unwrap(), &Value::String("".to_string()));
+
+    assert_eq!(parse_csv("user,tenant\n").unwrap().len(), 0);
+    assert!(parse_csv("user,tenant\nbob\n").is_err());
+}
+
+#[test]
+fn test_parse_json() {
+    let rows = parse_json(r#"[{"user": "bob", "id": 1}, {"user": "alice", "admin": true}]"#).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get("user").unwrap(), &Value::String("bob".to_string()));
+    assert_eq!(rows[0].get("id").unwrap(), &Value::Integer(1));
+    assert_eq!(rows[1].get("admin").unwrap(), &Value::Bool(true));
+
+    assert_eq!(parse_json("{}").err().unwrap(), "expecting an array of objects".to_string());
+    assert_eq!(parse_json("[1]").err().unwrap(), "expecting an array of objects".to_string());
+    assert!(parse_json("[").is_err());
+}
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 
 use crate::core::ast::*;
-use crate::core::core::SourceInfo;
+use crate::core::core::{SourceInfo, Value};
 //use crate::core::core::Value;
 use crate::http;
 
 use super::core::*;
+use super::output;
 use super::core::{Error, RunnerError};
+use super::template::take_generated_values;
 use super::text::*;
 use crate::http::cookie::CookieJar;
 
@@ -19,7 +21,8 @@ use crate::http::cookie::CookieJar;
 
 impl Entry {
     pub fn eval(self, http_client: &http::client::Client,
-                variables: &mut HashMap<String, String>,
+                variables: &mut HashMap<String, Value>,
+                namespaces: &HashMap<String, String>,
                 cookiejar: &mut CookieJar,
                 verbose: bool,
                 context_dir: String,
@@ -27,15 +30,17 @@ impl Entry {
 
         //let mut entry_log_builder = EntryLogBuilder::init();
 
-        let mut http_request = match self.clone().request.eval(variables, context_dir) {
+        let mut http_request = match self.clone().request.eval(variables, context_dir.clone()) {
             Ok(r) => r,
             Err(error) => {
                 return EntryResult {
                     request: None,
                     response: None,
                     captures: vec![],
+                    generated: take_generated_values(),
                     asserts: vec![],
                     errors: vec![error],
+                    skipped: false,
                 };
             }
         };
@@ -43,8 +48,8 @@ impl Entry {
         //eprintln!(">>cookies={:?}", cookies);
         http_request.add_session_cookies(cookies);
         if verbose {
-            eprintln!("---------------------------------------------------------------------------------------------------");
-            eprintln!("{}", http_request.to_text())
+            output::eprintln("---------------------------------------------------------------------------------------------------");
+            output::eprintln(http_request.to_text().as_str())
         }
 
 
@@ -55,6 +60,7 @@ impl Entry {
                     request: Some(http_request),
                     response: None,
                     captures: vec![],
+                    generated: take_generated_values(),
                     asserts: vec![],
                     errors: vec![
                         Error {
@@ -68,26 +74,47 @@ impl Entry {
                             },
                             assert: false,
                         }],
+                    skipped: false,
                 };
             }
         };
         if verbose {
-            eprintln!("{}", http_response.to_text())
+            output::eprintln(http_response.to_text().as_str())
         }
         //entry_log_builder.response(http_response.clone(), verbose);
 
         //hurl_log.entries.push(log_builder.build());
+        let namespaces = match self.response.clone() {
+            None => namespaces.clone(),
+            Some(response) => match response.eval_namespaces(variables, namespaces) {
+                Ok(namespaces) => namespaces,
+                Err(e) => {
+                    return EntryResult {
+                        request: Some(http_request.clone()),
+                        response: Some(http_response.clone()),
+                        captures: vec![],
+                        generated: take_generated_values(),
+                        asserts: vec![],
+                        errors: vec![e],
+                        skipped: false,
+                    };
+                }
+            }
+        };
+
         let captures = match self.response.clone() {
             None => vec![],
-            Some(response) => match response.eval_captures(variables, http_response.clone()) {
+            Some(response) => match response.eval_captures(variables, &namespaces, http_request.clone(), http_response.clone()) {
                 Ok(captures) => captures,
                 Err(e) => {
                     return EntryResult {
                         request: Some(http_request.clone()),
                         response: Some(http_response.clone()),
                         captures: vec![],
+                        generated: take_generated_values(),
                         asserts: vec![],
                         errors: vec![e],
+                        skipped: false,
                     };
                 }
             }
@@ -95,13 +122,13 @@ impl Entry {
 
         // update variables now!
         for (name, value) in captures.clone() {
-            variables.insert(name, value.to_string());
+            variables.insert(name, value);
         }
 
 
         let asserts = match self.response {
             None => vec![],
-            Some(response) => response.eval_asserts(variables, http_response.clone())
+            Some(response) => response.eval_asserts(variables, &namespaces, http_request.clone(), http_response.clone(), context_dir)
         };
 
         let errors = asserts
@@ -159,9 +186,9 @@ impl Entry {
         }
 
         if verbose {
-            eprintln!("[DEBUG] CookieJar");
+            output::eprintln("[DEBUG] CookieJar");
             for cookie in cookiejar.clone().cookies() {
-                eprintln!("[DEBUG] {}", cookie.to_string());
+                output::eprintln(format!("[DEBUG] {}", cookie.to_string()).as_str());
             }
         }
 
@@ -171,8 +198,10 @@ impl Entry {
             request: Some(http_request),
             response: Some(http_response),
             captures,
+            generated: take_generated_values(),
             asserts,
             errors,
+            skipped: false,
         };
     }
 }
@@ -13,6 +13,7 @@ impl Serialize for HurlResult {
     {
         let mut state = serializer.serialize_struct("??", 3)?;
         state.serialize_field("filename", &self.clone().filename)?;
+        state.serialize_field("dataset_row", &self.dataset_row)?;
         state.serialize_field("entries", &self.clone().entries)?;
         state.end()
     }
@@ -27,7 +28,9 @@ impl Serialize for EntryResult {
         state.serialize_field("request", &self.request)?;
         state.serialize_field("response", &self.response)?;
         state.serialize_field("captures", &self.captures)?;
+        state.serialize_field("generated", &self.generated)?;
         state.serialize_field("asserts", &self.asserts)?;
+        state.serialize_field("skipped", &self.skipped)?;
 
         state.end()
     }
@@ -61,7 +64,7 @@ impl Serialize for http::request::Request {
         // 3 is the number of fields in the struct.
         let mut state = serializer.serialize_struct("??", 3)?;
         state.serialize_field("url", &self.clone().url())?;
-        state.serialize_field("queryString", &self.clone().querystring)?;
+        state.serialize_field("queryString", &self.querystring_params())?;
         state.serialize_field("headers", &self.clone().headers())?;
         state.serialize_field("cookies", &self.clone().cookies)?;
 
@@ -11,4 +11,8 @@ pub mod predicate;
 pub mod template;
 pub mod response;
 pub mod log;
+pub mod dataset;
+pub mod output;
+mod css;
 mod xpath;
+mod xmlschema;
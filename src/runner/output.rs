@@ -0,0 +1,41 @@
+use std::cell::RefCell;
+
+// error and debug messages of a run, written to the standard error
+// when files are run in parallel, the messages of each file are buffered
+// and written at once, so that the logs of different files don't interleave
+thread_local! {
+    static BUFFER: RefCell<Option<String>> = RefCell::new(None);
+}
+
+pub fn eprintln(message: &str) {
+    BUFFER.with(|buffer| match buffer.borrow_mut().as_mut() {
+        Some(buffer) => {
+            buffer.push_str(message);
+            buffer.push('\n');
+        }
+        None => eprintln!("{}", message),
+    });
+}
+
+// buffer the messages of the current thread
+pub fn start_buffering() {
+    BUFFER.with(|buffer| buffer.replace(Some(String::from(""))));
+}
+
+// returns the messages buffered since the last call
+pub fn take_buffer() -> String {
+    return BUFFER.with(|buffer| match buffer.borrow_mut().as_mut() {
+        Some(buffer) => buffer.split_off(0),
+        None => String::from(""),
+    });
+}
+
+#[test]
+fn test_buffer() {
+    assert_eq!(take_buffer(), "");
+    start_buffering();
+    eprintln("error: a");
+    eprintln("error: b");
+    assert_eq!(take_buffer(), "error: a\nerror: b\n");
+    assert_eq!(take_buffer(), "");
+}
@@ -3,13 +3,17 @@ use std::collections::HashMap;
 use regex::Regex;
 
 //use serde::{Deserialize, Serialize};
-#[cfg(test)]
 use crate::core::core::SourceInfo;
 use crate::core::core::Value;
+use crate::core::date::Date;
+use crate::core::jsonpath;
+use crate::core::jsonschema;
 
 use super::core::{Error, RunnerError};
 use super::core::*;
 use super::super::core::ast::*;
+use super::template::eval_json;
+use super::xmlschema;
 
 // equals 10         function  return ()
 // not equals 10
@@ -40,8 +44,8 @@ use super::super::core::ast::*;
 
 
 impl Predicate {
-    pub fn eval(self, variables: &HashMap<String, String>, value: Value) -> PredicateResult {
-        return match self.predicate_func.clone().eval(variables, value.clone()) {
+    pub fn eval(self, variables: &HashMap<String, Value>, value: Value, context_dir: String) -> PredicateResult {
+        return match self.predicate_func.clone().eval(variables, value.clone(), context_dir) {
             Ok(_) => {
                 if self.not {
                     Err(Error {
@@ -62,6 +66,28 @@ impl Predicate {
                     })
                 }
             }
+            Err(Error { inner: RunnerError::PredicateJsonDiff(diffs), .. }) => {
+                if self.not {
+                    Ok(())
+                } else {
+                    Err(Error {
+                        source_info: self.predicate_func.source_info,
+                        inner: RunnerError::PredicateJsonDiff(diffs),
+                        assert: false,
+                    })
+                }
+            }
+            Err(Error { inner: RunnerError::PredicateSchema(violations), .. }) => {
+                if self.not {
+                    Ok(())
+                } else {
+                    Err(Error {
+                        source_info: self.predicate_func.source_info,
+                        inner: RunnerError::PredicateSchema(violations),
+                        assert: false,
+                    })
+                }
+            }
             Err(e) => Err(e)
         };
     }
@@ -98,16 +124,16 @@ fn test_predicate() {
         },
     };
 
-    let error = predicate.clone().eval(&variables, Value::Bool(true)).err().unwrap();
+    let error = predicate.clone().eval(&variables, Value::Bool(true), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateType {});
     assert_eq!(error.source_info, SourceInfo::init(1, 5, 1, 14));
 
 
-    let error = predicate.clone().eval(&variables, Value::Integer(10)).err().unwrap();
+    let error = predicate.clone().eval(&variables, Value::Integer(10), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateValue(Value::Integer(10)));
     assert_eq!(error.source_info, SourceInfo::init(1, 5, 1, 14));
 
-    assert_eq!(predicate.clone().eval(&variables, Value::Integer(1)).unwrap(), ());
+    assert_eq!(predicate.clone().eval(&variables, Value::Integer(1), String::from("current_dir")).unwrap(), ());
 }
 
 // endregion
@@ -118,7 +144,7 @@ fn test_predicate() {
 
 
 impl PredicateFunc {
-    pub fn eval(self, variables: &HashMap<String, String>, value: Value) -> Result<(), Error> {
+    pub fn eval(self, variables: &HashMap<String, Value>, value: Value, context_dir: String) -> Result<(), Error> {
         //eprintln!(">>> actual={:?}", value);
         //eprintln!(">>> predicate func={:#?}", self.clone());
         let source_info = self.source_info;
@@ -127,29 +153,42 @@ impl PredicateFunc {
             // equals integer
             (PredicateFuncValue::EqualInt { value: expected, .. }, Value::Integer(actual)) =>
                 if actual == expected { Ok(()) } else { Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false }) }
-            (PredicateFuncValue::EqualInt { value: expected, .. }, Value::Float(int, decimal)) =>
-                if int == expected && decimal == 0 { Ok(()) } else { Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false }) }
+            (PredicateFuncValue::EqualInt { value: expected, .. }, Value::Float(actual)) =>
+                if actual == expected as f64 { Ok(()) } else { Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false }) }
 
             // equals boolean
             (PredicateFuncValue::EqualBool { value: expected, .. }, Value::Bool(actual)) =>
                 if actual == expected { Ok(()) } else { Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false }) }
 
             // equals float
-            (PredicateFuncValue::EqualFloat { value: Float { int: expected_int, decimal: expected_dec, .. }, .. }, Value::Float(int, decimal)) => {
-                if int == expected_int && decimal == expected_dec {
+            (PredicateFuncValue::EqualFloat { value: Float { value: expected, .. }, .. }, Value::Float(actual)) => {
+                if actual == expected {
                     Ok(())
                 } else {
                     Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false })
                 }
             }
-            (PredicateFuncValue::EqualFloat { value: Float { int: expected_int, decimal: expected_dec, .. }, .. }, Value::Integer(actual)) => {
-                if actual == expected_int && expected_dec == 0 {
+            (PredicateFuncValue::EqualFloat { value: Float { value: expected, .. }, .. }, Value::Integer(actual)) => {
+                if actual as f64 == expected {
                     Ok(())
                 } else {
                     Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false })
                 }
             }
 
+            // equals within a tolerance
+            (PredicateFuncValue::ApproxEqual { value: expected, tolerance, .. }, Value::Float(actual)) =>
+                eval_approx_equal(expected, tolerance, actual, value, source_info),
+            (PredicateFuncValue::ApproxEqual { value: expected, tolerance, .. }, Value::Integer(actual)) =>
+                eval_approx_equal(expected, tolerance, actual as f64, value, source_info),
+
+            // equals captured variable, compared with its original type
+            // (rendered as text when compared with a string)
+            (PredicateFuncValue::EqualString { value: template, .. }, actual) if !matches!(actual, Value::String(_)) && typed_variable(&template, variables).is_some() => {
+                let expected = typed_variable(&template, variables).unwrap();
+                eval_typed_equal(expected, actual, source_info)
+            }
+
             // equals string
             (PredicateFuncValue::EqualString { value: template, .. }, Value::String(actual)) => {
                 let expected = template.eval(variables)?;
@@ -162,6 +201,55 @@ impl PredicateFunc {
                 }
             }
 
+            // equals hex string (hash or bytes)
+            (PredicateFuncValue::EqualString { value: template, .. }, Value::Bytes(actual)) => {
+                let expected = template.eval(variables)?;
+                if hex_string(actual) == expected.to_lowercase() {
+                    return Ok(());
+                } else {
+                    return Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false });
+                }
+            }
+
+            // equals date (toDate filter), given as an iso or http date
+            (PredicateFuncValue::EqualString { value: template, .. }, Value::Date(actual)) => {
+                let template_source_info = template.source_info.clone();
+                let expected = template.eval(variables)?;
+                match Date::from_string(expected.as_str()) {
+                    Some(expected) => if actual == expected {
+                        Ok(())
+                    } else {
+                        Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false })
+                    },
+                    None => Err(Error { source_info: template_source_info, inner: RunnerError::InvalidDate(expected), assert: false }),
+                }
+            }
+
+            // equals variable, coerced to the type of the actual value
+            (PredicateFuncValue::EqualString { value: template, .. }, actual) => {
+                if !template.is_expression() {
+                    return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false });
+                }
+                let expected = template.eval(variables)?;
+                eval_expression_equal(expected, actual, source_info)
+            }
+
+            // equals bytes
+            (PredicateFuncValue::EqualBytes { value: expected, .. }, Value::Bytes(actual)) => {
+                let expected = expected.eval(context_dir)?;
+                if actual == expected {
+                    return Ok(());
+                } else {
+                    return Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false });
+                }
+            }
+
+            // equals json (deep equality)
+            (PredicateFuncValue::EqualJson { space0, value: expected }, actual) => {
+                let expected = eval_json(expected, space0.source_info.end, variables)?;
+                eval_json_equal(expected, actual, source_info)
+            }
+
             // startswith string
             (PredicateFuncValue::StartWith { value: template, .. }, Value::String(actual)) => {
                 let value = template.eval(variables)?;
@@ -183,31 +271,34 @@ impl PredicateFunc {
             }
 
             // match regex
-            (PredicateFuncValue::Match { value, .. }, Value::String(actual)) => {
-                match Regex::new(value.value.as_str()) {
+            (PredicateFuncValue::Match { value: template, .. }, Value::String(actual)) => {
+                let regex_source_info = template.source_info.clone();
+                let value = template.eval(variables)?;
+                match Regex::new(value.as_str()) {
                     Ok(re) => if re.is_match(actual.as_str()) {
                         Ok(())
                     } else {
                         return Err(Error { source_info, inner: RunnerError::PredicateValue(Value::String(actual)), assert: false });
                     }
-                    _ => Err(Error { source_info: value.source_info, inner: RunnerError::InvalidRegex(), assert: false })
+                    Err(e) => Err(Error { source_info: regex_source_info, inner: RunnerError::InvalidRegex(regex_error_message(e)), assert: false })
                 }
             }
 
             // match on bytes => assume that it's utf8 encoded
-            (PredicateFuncValue::Match { value, .. }, Value::Bytes(actual)) => {
+            (PredicateFuncValue::Match { value: template, .. }, Value::Bytes(actual)) => {
+                let value = template.eval(variables)?;
                 let actual_string = match String::from_utf8(actual.clone()) {
                     Err(_) => return Err(Error { source_info, inner: RunnerError::InvalidUtf8, assert: false }),
                     Ok(v) => v
                 };
 
-                match Regex::new(value.value.as_str()) {
+                match Regex::new(value.as_str()) {
                     Ok(re) => if re.is_match(actual_string.as_str()) {
                         Ok(())
                     } else {
                         return Err(Error { source_info, inner: RunnerError::PredicateValue(Value::Bytes(actual)), assert: false });
                     }
-                    _ => Err(Error { source_info, inner: RunnerError::InvalidRegex(), assert: false })
+                    Err(e) => Err(Error { source_info, inner: RunnerError::InvalidRegex(regex_error_message(e)), assert: false })
                 }
 
             }
@@ -246,8 +337,18 @@ impl PredicateFunc {
                 }
             }
 
+            // firstEquals captured variable
+            (PredicateFuncValue::FirstEqualString { value: expected, .. }, Value::List(values)) if typed_variable(&expected, variables).is_some() => {
+                let expected = typed_variable(&expected, variables).unwrap();
+                match values.get(0) {
+                    Some(actual) => eval_typed_equal(expected, actual.clone(), source_info),
+                    None => Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+                }
+            }
+
             // firstEquals String
             (PredicateFuncValue::FirstEqualString { value: expected, .. }, Value::List(values)) => {
+                let is_expression = expected.is_expression();
                 let expected = expected.eval(variables)?;
                 match values.get(0) {
                     Some(Value::String(actual)) => {
@@ -257,6 +358,30 @@ impl PredicateFunc {
                             Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false })
                         }
                     }
+                    Some(actual) if is_expression => eval_expression_equal(expected, actual.clone(), source_info),
+                    _ => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false })
+                }
+            }
+
+            // firstEquals json
+            (PredicateFuncValue::FirstEqualJson { space0, value: expected }, Value::List(values)) => {
+                let expected = eval_json(expected, space0.source_info.end, variables)?;
+                match values.get(0) {
+                    Some(actual) => eval_json_equal(expected, actual.clone(), source_info),
+                    _ => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false })
+                }
+            }
+
+            // firstEquals Float / approxEquals
+            (PredicateFuncValue::FirstEqualFloat { space0, value: expected }, Value::List(values)) => {
+                match values.get(0) {
+                    Some(actual) => PredicateFunc { source_info, value: PredicateFuncValue::EqualFloat { space0, value: expected } }.eval(variables, actual.clone(), context_dir),
+                    _ => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false })
+                }
+            }
+            (PredicateFuncValue::FirstApproxEqual { space0, value: expected, space1, space2, tolerance }, Value::List(values)) => {
+                match values.get(0) {
+                    Some(actual) => PredicateFunc { source_info, value: PredicateFuncValue::ApproxEqual { space0, value: expected, space1, space2, tolerance } }.eval(variables, actual.clone(), context_dir),
                     _ => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false })
                 }
             }
@@ -275,6 +400,16 @@ impl PredicateFunc {
                 }
             }
 
+            // countEquals variable
+            (PredicateFuncValue::CountEqualExpression { space0, value: expr }, _) => {
+                let expected = eval_count_expression(expr, variables, source_info.clone())?;
+                PredicateFunc { source_info, value: PredicateFuncValue::CountEqual { space0, value: expected } }.eval(variables, value, context_dir)
+            }
+            (PredicateFuncValue::FirstCountEqualExpression { space0, value: expr }, _) => {
+                let expected = eval_count_expression(expr, variables, source_info.clone())?;
+                PredicateFunc { source_info, value: PredicateFuncValue::FirstCountEqual { space0, value: expected } }.eval(variables, value, context_dir)
+            }
+
             // firstCountEqual
             (PredicateFuncValue::FirstCountEqual { value: expected, .. }, Value::List(values)) => {
                 match values.get(0) {
@@ -284,10 +419,82 @@ impl PredicateFunc {
             }
 
 
+            // includes
+            (PredicateFuncValue::IncludeString { space0, value: expected }, _) =>
+                eval_include(PredicateFuncValue::EqualString { space0, value: expected }, variables, value, source_info, context_dir),
+            (PredicateFuncValue::IncludeInt { space0, value: expected }, _) =>
+                eval_include(PredicateFuncValue::EqualInt { space0, value: expected }, variables, value, source_info, context_dir),
+            (PredicateFuncValue::IncludeFloat { space0, value: expected }, _) =>
+                eval_include(PredicateFuncValue::EqualFloat { space0, value: expected }, variables, value, source_info, context_dir),
+            (PredicateFuncValue::IncludeBool { space0, value: expected }, _) =>
+                eval_include(PredicateFuncValue::EqualBool { space0, value: expected }, variables, value, source_info, context_dir),
+
+            // allMatch / anyMatch regex
+            (PredicateFuncValue::AllMatch { value: regex, .. }, _) => eval_match_elements(regex, variables, value, source_info, true),
+            (PredicateFuncValue::AnyMatch { value: regex, .. }, _) => eval_match_elements(regex, variables, value, source_info, false),
+
+            // json schema
+            // text values (body) are parsed as json
+            (PredicateFuncValue::ValidateSchema { value: schema, .. }, Value::String(json)) =>
+                eval_schema(schema, json, source_info, context_dir),
+            (PredicateFuncValue::ValidateSchema { value: schema, .. }, Value::Bytes(bytes)) => match String::from_utf8(bytes) {
+                Ok(json) => eval_schema(schema, json, source_info, context_dir),
+                Err(_) => Err(Error { source_info, inner: RunnerError::InvalidUtf8, assert: false }),
+            },
+            (PredicateFuncValue::ValidateSchema { .. }, Value::Nodeset(_)) =>
+                Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+            (PredicateFuncValue::ValidateSchema { value: schema, .. }, value) => match value.to_json() {
+                Some(json) => eval_schema(schema, json, source_info, context_dir),
+                None => Err(Error { source_info, inner: RunnerError::InvalidJsonValue(value), assert: false }),
+            },
+            (PredicateFuncValue::FirstValidateSchema { value: schema, .. }, Value::List(values)) => match values.get(0) {
+                Some(value) => match value.to_json() {
+                    Some(json) => eval_schema(schema, json, source_info, context_dir),
+                    None => Err(Error { source_info, inner: RunnerError::InvalidJsonValue(value.clone()), assert: false }),
+                },
+                None => Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+            },
+
+            // xml schema and dtd
+            (PredicateFuncValue::ValidateXsd { value: schema, .. }, Value::String(xml)) =>
+                eval_xml_schema(schema, xml, xmlschema::validate_xsd, source_info, context_dir),
+            (PredicateFuncValue::ValidateDtd { value: schema, .. }, Value::String(xml)) =>
+                eval_xml_schema(schema, xml, xmlschema::validate_dtd, source_info, context_dir),
+            (PredicateFuncValue::ValidateXsd { value: schema, .. }, Value::Bytes(bytes)) => match String::from_utf8(bytes) {
+                Ok(xml) => eval_xml_schema(schema, xml, xmlschema::validate_xsd, source_info, context_dir),
+                Err(_) => Err(Error { source_info, inner: RunnerError::InvalidUtf8, assert: false }),
+            },
+            (PredicateFuncValue::ValidateDtd { value: schema, .. }, Value::Bytes(bytes)) => match String::from_utf8(bytes) {
+                Ok(xml) => eval_xml_schema(schema, xml, xmlschema::validate_dtd, source_info, context_dir),
+                Err(_) => Err(Error { source_info, inner: RunnerError::InvalidUtf8, assert: false }),
+            },
+
+            // dates
+            (PredicateFuncValue::IsIsoDate {}, Value::String(actual)) => match Date::parse_iso(actual.as_str()) {
+                Some(_) => Ok(()),
+                None => Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false }),
+            },
+            (PredicateFuncValue::IsIsoDate {}, Value::Date(_)) => Ok(()),
+            (PredicateFuncValue::Before { value: expected, .. }, _) => eval_date_comparison(expected, variables, value, source_info, true),
+            (PredicateFuncValue::After { value: expected, .. }, _) => eval_date_comparison(expected, variables, value, source_info, false),
+            (PredicateFuncValue::FirstIsIsoDate {}, Value::List(values)) => match values.get(0) {
+                Some(actual) => PredicateFunc { source_info, value: PredicateFuncValue::IsIsoDate {} }.eval(variables, actual.clone(), context_dir),
+                None => Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+            },
+            (PredicateFuncValue::FirstBefore { space0, value: expected }, Value::List(values)) => match values.get(0) {
+                Some(actual) => PredicateFunc { source_info, value: PredicateFuncValue::Before { space0, value: expected } }.eval(variables, actual.clone(), context_dir),
+                None => Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+            },
+            (PredicateFuncValue::FirstAfter { space0, value: expected }, Value::List(values)) => match values.get(0) {
+                Some(actual) => PredicateFunc { source_info, value: PredicateFuncValue::After { space0, value: expected } }.eval(variables, actual.clone(), context_dir),
+                None => Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+            },
+
             // exist
             (PredicateFuncValue::Exist {}, value) => {
                 match value {
                     Value::None | Value::Nodeset(0) => Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false }),
+                    Value::List(ref values) if values.is_empty() => Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false }),
                     _ => Ok(())
                 }
             }
@@ -310,7 +517,7 @@ fn test_predicate_type_error() {
     let error = PredicateFunc {
         value: PredicateFuncValue::EqualInt { space0: whitespace.clone(), value: 10 },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, Value::Bool(true)).err().unwrap();
+    }.eval(&variables, Value::Bool(true), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateType);
 }
 
@@ -325,21 +532,21 @@ fn test_predicate_value_error() {
     let error = PredicateFunc {
         source_info: SourceInfo::init(0, 0, 0, 0),
         value: PredicateFuncValue::EqualInt { space0: whitespace.clone(), value: 2 },
-    }.eval(&variables, actual.clone()).err().unwrap();
+    }.eval(&variables, actual.clone(), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateValue(actual.clone()));
 
     let actual = Value::Bool(true);
     let error = PredicateFunc {
         source_info: SourceInfo::init(0, 0, 0, 0),
         value: PredicateFuncValue::EqualBool { space0: whitespace.clone(), value: false },
-    }.eval(&variables, actual.clone()).err().unwrap();
+    }.eval(&variables, actual.clone(), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateValue(actual.clone()));
 
-    let actual = Value::Float(1, 1);
+    let actual = Value::Float(1.1);
     let error = PredicateFunc {
         source_info: SourceInfo::init(0, 0, 0, 0),
-        value: PredicateFuncValue::EqualFloat { space0: whitespace.clone(), value: Float { int: 1, decimal: 200000000000000000, decimal_digits: 0 } },
-    }.eval(&variables, actual.clone()).err().unwrap();
+        value: PredicateFuncValue::EqualFloat { space0: whitespace.clone(), value: Float { value: 1.2, encoded: String::from("1.2") } },
+    }.eval(&variables, actual.clone(), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateValue(actual.clone()));
 }
 
@@ -354,29 +561,65 @@ fn test_predicate_value_equals() {
     assert_eq!(PredicateFunc {
         value: PredicateFuncValue::EqualInt { space0: whitespace.clone(), value: 10 },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, Value::Integer(10)).unwrap(), ());
+    }.eval(&variables, Value::Integer(10), String::from("current_dir")).unwrap(), ());
 
     assert_eq!(PredicateFunc {
         value: PredicateFuncValue::EqualBool { space0: whitespace.clone(), value: true },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, Value::Bool(true)).unwrap(), ());
+    }.eval(&variables, Value::Bool(true), String::from("current_dir")).unwrap(), ());
 
     assert_eq!(PredicateFunc {
-        value: PredicateFuncValue::EqualFloat { space0: whitespace.clone(), value: Float { int: 1, decimal: 100000000000000000, decimal_digits: 0 } },
+        value: PredicateFuncValue::EqualFloat { space0: whitespace.clone(), value: Float { value: 1.1, encoded: String::from("1.1") } },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, Value::Float(1, 100000000000000000)).unwrap(), ());
+    }.eval(&variables, Value::Float(1.1), String::from("current_dir")).unwrap(), ());
 
 
     // int and float => no type error
     assert_eq!(PredicateFunc {
         source_info: SourceInfo::init(0, 0, 0, 0),
         value: PredicateFuncValue::EqualInt { space0: whitespace.clone(), value: 1 },
-    }.eval(&variables, Value::Float(1, 0)).unwrap(), ());
+    }.eval(&variables, Value::Float(1.0), String::from("current_dir")).unwrap(), ());
+
+    assert_eq!(PredicateFunc {
+        value: PredicateFuncValue::EqualFloat { space0: whitespace.clone(), value: Float { value: 1.0, encoded: String::from("1.0") } },
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    }.eval(&variables, Value::Integer(1), String::from("current_dir")).unwrap(), ());
 
+    // negative floats
     assert_eq!(PredicateFunc {
-        value: PredicateFuncValue::EqualFloat { space0: whitespace.clone(), value: Float { int: 1, decimal: 0, decimal_digits: 0 } },
+        value: PredicateFuncValue::EqualFloat { space0: whitespace.clone(), value: Float { value: -0.5, encoded: String::from("-0.5") } },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, Value::Integer(1)).unwrap(), ());
+    }.eval(&variables, Value::Float(0.5), String::from("current_dir")).err().unwrap().inner, RunnerError::PredicateValue(Value::Float(0.5)));
+}
+
+#[test]
+fn test_predicate_approx_equal() {
+    let variables = HashMap::new();
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+    let approx_equal = |value: f64, tolerance: f64| PredicateFunc {
+        value: PredicateFuncValue::ApproxEqual {
+            space0: whitespace.clone(),
+            value: Float { value, encoded: value.to_string() },
+            space1: whitespace.clone(),
+            space2: whitespace.clone(),
+            tolerance: Float { value: tolerance, encoded: tolerance.to_string() },
+        },
+        source_info: SourceInfo::init(1, 1, 1, 30),
+    };
+    assert_eq!(approx_equal(1.23, 0.01).eval(&variables, Value::Float(1.231592), String::from("current_dir")).unwrap(), ());
+    assert_eq!(approx_equal(-1.6, 0.1).eval(&variables, Value::Float(-1.55), String::from("current_dir")).unwrap(), ());
+    assert_eq!(approx_equal(0.3, 0.0000001).eval(&variables, Value::Float(0.1 + 0.2), String::from("current_dir")).unwrap(), ());
+    assert_eq!(approx_equal(10.0, 1.0).eval(&variables, Value::Integer(11), String::from("current_dir")).unwrap(), ());
+
+    let error = approx_equal(1.23, 0.01).eval(&variables, Value::Float(1.3), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateValue(Value::Float(1.3)));
+    assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 30));
+
+    let error = approx_equal(1.23, 0.01).eval(&variables, Value::String(String::from("1.23")), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateType);
 }
 
 #[test]
@@ -394,6 +637,8 @@ fn test_predicate_value_equals_string() {
                 value: Expr {
                     space0: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 11, 1, 11) },
                     variable: Variable { name: String::from("base_url"), source_info: SourceInfo::init(1, 11, 1, 19) },
+                    arguments: vec![],
+                    filters: vec![],
                     space1: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 19, 1, 19) },
                 }
             }
@@ -405,16 +650,16 @@ fn test_predicate_value_equals_string() {
     let error = PredicateFunc {
         value: PredicateFuncValue::EqualString { space0: whitespace.clone(), value: template.clone() },
         source_info: SourceInfo::init(1, 1, 1, 21),
-    }.eval(&variables, Value::String(String::from("http://localhost:8000"))).err().unwrap();
+    }.eval(&variables, Value::String(String::from("http://localhost:8000")), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: String::from("base_url") });
     assert_eq!(error.source_info, SourceInfo::init(1, 11, 1, 19));
 
-    variables.insert(String::from("base_url"), String::from("http://localhost:8000"));
+    variables.insert(String::from("base_url"), Value::String(String::from("http://localhost:8000")));
     assert_eq!(
         PredicateFunc {
             value: PredicateFuncValue::EqualString { space0: whitespace.clone(), value: template.clone() },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval(&variables, Value::String(String::from("http://localhost:8000"))).unwrap(),
+        }.eval(&variables, Value::String(String::from("http://localhost:8000")), String::from("current_dir")).unwrap(),
         ()
     );
 
@@ -431,7 +676,7 @@ fn test_predicate_count_equals_error() {
     let error = PredicateFunc {
         value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 10 },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, Value::Bool(true)).err().unwrap();
+    }.eval(&variables, Value::Bool(true), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateType);
 
 
@@ -439,14 +684,14 @@ fn test_predicate_count_equals_error() {
     let error = PredicateFunc {
         value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 1 },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, actual.clone()).err().unwrap();
+    }.eval(&variables, actual.clone(), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateValue(actual.clone()));
 
     let actual = Value::Nodeset(3);
     let error = PredicateFunc {
         source_info: SourceInfo::init(0, 0, 0, 0),
         value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 1 },
-    }.eval(&variables, actual.clone()).err().unwrap();
+    }.eval(&variables, actual.clone(), String::from("current_dir")).err().unwrap();
     assert_eq!(error.inner, RunnerError::PredicateValue(actual.clone()));
 }
 
@@ -460,11 +705,22 @@ fn test_predicate_count_equals() {
     assert_eq!(PredicateFunc {
         value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 1 },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, Value::List(vec![Value::Integer(1)])).unwrap(), ());
+    }.eval(&variables, Value::List(vec![Value::Integer(1)]), String::from("current_dir")).unwrap(), ());
     assert_eq!(PredicateFunc {
         value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 1 },
         source_info: SourceInfo::init(0, 0, 0, 0),
-    }.eval(&variables, Value::Nodeset(1)).unwrap(), ());
+    }.eval(&variables, Value::Nodeset(1), String::from("current_dir")).unwrap(), ());
+
+    // a css query without match
+    assert_eq!(PredicateFunc {
+        value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 0 },
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    }.eval(&variables, Value::List(vec![]), String::from("current_dir")).unwrap(), ());
+    let error = PredicateFunc {
+        value: PredicateFuncValue::Exist {},
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    }.eval(&variables, Value::List(vec![]), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateValue(Value::List(vec![])));
 }
 
 //#[test]
@@ -474,4 +730,657 @@ fn test_predicate_count_equals() {
 //    assert_eq!(error, RunnerError::PredicateValue(actual.clone()));
 //
 //}
-// endregion
\ No newline at end of file
+// endregion
+
+// jsonpath returns the list of its matches
+// a single array match is the collection itself
+fn collection(value: Value) -> Option<Vec<Value>> {
+    return match value {
+        Value::List(values) => match values.as_slice() {
+            [Value::List(elements)] => Some(elements.clone()),
+            _ => Some(values),
+        },
+        _ => None,
+    };
+}
+
+// at least one element satisfies the equals predicate
+fn eval_include(equal: PredicateFuncValue, variables: &HashMap<String, Value>, value: Value, source_info: SourceInfo, context_dir: String) -> Result<(), Error> {
+    let elements = match collection(value.clone()) {
+        Some(elements) => elements,
+        None => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+    };
+    for element in elements {
+        let predicate_func = PredicateFunc { source_info: source_info.clone(), value: equal.clone() };
+        match predicate_func.eval(variables, element, context_dir.clone()) {
+            Ok(()) => return Ok(()),
+            Err(Error { inner: RunnerError::PredicateValue(_), .. }) => {}
+            Err(Error { inner: RunnerError::PredicateType, .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    return Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false });
+}
+
+// all (or any) scalar elements match the regex
+fn eval_match_elements(regex: HurlTemplate, variables: &HashMap<String, Value>, value: Value, source_info: SourceInfo, all: bool) -> Result<(), Error> {
+    let regex_source_info = regex.source_info.clone();
+    let regex = regex.eval(variables)?;
+    let re = match Regex::new(regex.as_str()) {
+        Ok(re) => re,
+        Err(e) => return Err(Error { source_info: regex_source_info, inner: RunnerError::InvalidRegex(regex_error_message(e)), assert: false }),
+    };
+    let elements = match collection(value.clone()) {
+        Some(elements) => elements,
+        None => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+    };
+    let is_match = |element: &Value| match element {
+        Value::String(s) => re.is_match(s.as_str()),
+        Value::Integer(_) | Value::Float(..) | Value::Bool(_) => re.is_match(element.to_string().as_str()),
+        _ => false,
+    };
+    let matched = if all { elements.iter().all(is_match) } else { elements.iter().any(is_match) };
+    if matched {
+        return Ok(());
+    } else {
+        return Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false });
+    }
+}
+
+fn eval_approx_equal(expected: Float, tolerance: Float, actual: f64, value: Value, source_info: SourceInfo) -> Result<(), Error> {
+    if (actual - expected.value).abs() <= tolerance.value.abs() {
+        return Ok(());
+    } else {
+        return Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false });
+    }
+}
+
+// value of a variable used alone as the expected value
+// string variables (given on the command line) are coerced instead
+fn typed_variable(template: &HurlTemplate, variables: &HashMap<String, Value>) -> Option<Value> {
+    if !template.is_expression() {
+        return None;
+    }
+    return match template.elements.as_slice() {
+        [HurlTemplateElement::Expression { value: Expr { variable: Variable { name, .. }, arguments, filters, .. } }] if arguments.is_empty() && filters.is_empty() => match variables.get(name) {
+            None | Some(Value::String(_)) => None,
+            Some(value) => Some(value.clone()),
+        },
+        _ => None,
+    };
+}
+
+// typed variable (captured value) compared with a value of the same type
+fn eval_typed_equal(expected: Value, actual: Value, source_info: SourceInfo) -> Result<(), Error> {
+    return match (&expected, &actual) {
+        (Value::List(_), Value::List(_))
+        | (Value::List(_), Value::Object(_))
+        | (Value::Object(_), Value::List(_))
+        | (Value::Object(_), Value::Object(_)) => {
+            let diffs = json_diff(String::from("$"), &expected, &actual);
+            if diffs.is_empty() {
+                Ok(())
+            } else {
+                Err(Error { source_info, inner: RunnerError::PredicateJsonDiff(diffs), assert: false })
+            }
+        }
+        (Value::Integer(_), Value::Integer(_))
+        | (Value::Float(..), Value::Float(..))
+        | (Value::Bool(_), Value::Bool(_))
+        | (Value::Bytes(_), Value::Bytes(_))
+        | (Value::Date(_), Value::Date(_))
+        | (Value::Nodeset(_), Value::Nodeset(_))
+        | (Value::None, Value::None) => {
+            if expected == actual {
+                Ok(())
+            } else {
+                Err(Error { source_info, inner: RunnerError::PredicateValue(actual), assert: false })
+            }
+        }
+        _ => Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+    };
+}
+
+// variable value (always a string) compared with a typed value
+fn eval_expression_equal(expected: String, actual: Value, source_info: SourceInfo) -> Result<(), Error> {
+    let expected_value = match actual {
+        Value::Integer(_) => match expected.parse::<i64>() {
+            Ok(v) => Value::Integer(v),
+            Err(_) => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+        },
+        Value::Float(..) => match expected.parse::<f64>() {
+            Ok(v) => Value::Float(v),
+            Err(_) => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+        },
+        Value::Bool(_) => match expected.as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+        },
+        Value::List(_) | Value::Object(_) => return match jsonpath::parse_value(expected.as_str()) {
+            Ok(_) => eval_json_equal(expected, actual, source_info),
+            Err(_) => Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+        },
+        _ => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+    };
+    if expected_value == actual {
+        return Ok(());
+    } else {
+        return Err(Error { source_info, inner: RunnerError::PredicateValue(expected_value), assert: false });
+    }
+}
+
+// the actual value is a date (toDate filter) or an iso/http date string
+fn eval_date_comparison(expected: DateValue, variables: &HashMap<String, Value>, value: Value, source_info: SourceInfo, before: bool) -> Result<(), Error> {
+    let actual = match value.clone() {
+        Value::Date(date) => date,
+        Value::String(s) => match Date::from_string(s.as_str()) {
+            Some(date) => date,
+            None => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+        },
+        _ => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+    };
+    let expected = match expected {
+        DateValue::Now { offset: None } => Date::now(),
+        DateValue::Now { offset: Some(DateOffset { negative, value, unit, .. }) } => {
+            let seconds = value as i64 * unit.seconds();
+            Date::now().add_seconds(if negative { -seconds } else { seconds })
+        }
+        DateValue::Template { value: template } => {
+            let template_source_info = template.source_info.clone();
+            let s = template.eval(variables)?;
+            match Date::from_string(s.as_str()) {
+                Some(date) => date,
+                None => return Err(Error { source_info: template_source_info, inner: RunnerError::InvalidDate(s), assert: false }),
+            }
+        }
+    };
+    let valid = if before { actual < expected } else { actual > expected };
+    if valid {
+        return Ok(());
+    } else {
+        return Err(Error { source_info, inner: RunnerError::PredicateValue(value), assert: false });
+    }
+}
+
+fn eval_count_expression(expr: Expr, variables: &HashMap<String, Value>, source_info: SourceInfo) -> Result<u64, Error> {
+    let value = HurlTemplateElement::Expression { value: expr }.eval(variables)?;
+    return match value.parse::<u64>() {
+        Ok(v) => Ok(v),
+        Err(_) => Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+    };
+}
+
+fn eval_schema(schema: Bytes, json: String, source_info: SourceInfo, context_dir: String) -> Result<(), Error> {
+    let schema_source_info = match schema.clone() {
+        Bytes::File { filename, .. } => filename.source_info,
+        _ => source_info.clone(),
+    };
+    let schema = match String::from_utf8(schema.eval(context_dir)?) {
+        Ok(schema) => schema,
+        Err(_) => return Err(Error { source_info: schema_source_info, inner: RunnerError::InvalidUtf8, assert: false }),
+    };
+    let schema = match jsonschema::Schema::parse(schema.as_str()) {
+        Ok(schema) => schema,
+        Err(message) => return Err(Error { source_info: schema_source_info, inner: RunnerError::InvalidJsonSchema(message), assert: false }),
+    };
+    let violations = match schema.validate(json.as_str()) {
+        Ok(violations) => violations,
+        Err(jsonschema::ValidationError::InvalidJson) => return Err(Error { source_info, inner: RunnerError::QueryInvalidJson, assert: false }),
+        Err(jsonschema::ValidationError::InvalidSchema(message)) => return Err(Error { source_info: schema_source_info, inner: RunnerError::InvalidJsonSchema(message), assert: false }),
+    };
+    if violations.is_empty() {
+        return Ok(());
+    } else {
+        return Err(Error { source_info, inner: RunnerError::PredicateSchema(violations), assert: false });
+    }
+}
+
+fn eval_xml_schema(schema: Bytes, xml: String, validate: fn(String, String) -> Result<Vec<String>, xmlschema::XmlSchemaError>, source_info: SourceInfo, context_dir: String) -> Result<(), Error> {
+    let schema_source_info = match schema.clone() {
+        Bytes::File { filename, .. } => filename.source_info,
+        _ => source_info.clone(),
+    };
+    let schema = match String::from_utf8(schema.eval(context_dir)?) {
+        Ok(schema) => schema,
+        Err(_) => return Err(Error { source_info: schema_source_info, inner: RunnerError::InvalidUtf8, assert: false }),
+    };
+    return match validate(xml, schema) {
+        Ok(errors) => if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error { source_info, inner: RunnerError::PredicateSchema(errors), assert: false })
+        },
+        Err(xmlschema::XmlSchemaError::InvalidXml(_)) => Err(Error { source_info, inner: RunnerError::QueryInvalidXml, assert: false }),
+        Err(xmlschema::XmlSchemaError::InvalidSchema(errors)) => Err(Error { source_info: schema_source_info, inner: RunnerError::InvalidXmlSchema(errors), assert: false }),
+    };
+}
+
+fn eval_json_equal(expected: String, actual: Value, source_info: SourceInfo) -> Result<(), Error> {
+    let expected = match jsonpath::parse_value(expected.as_str()) {
+        Ok(value) => value,
+        Err(e) => return Err(Error { source_info, inner: RunnerError::PredicateInvalidJson(e.to_string()), assert: false }),
+    };
+    match actual {
+        Value::List(_) | Value::Object(_) => {}
+        _ => return Err(Error { source_info, inner: RunnerError::PredicateType, assert: false }),
+    }
+    let diffs = json_diff(String::from("$"), &expected, &actual);
+    if diffs.is_empty() {
+        return Ok(());
+    } else {
+        return Err(Error { source_info, inner: RunnerError::PredicateJsonDiff(diffs), assert: false });
+    }
+}
+
+// differences between two json values, located by their jsonpath
+// object members are compared regardless of their order
+fn json_diff(path: String, expected: &Value, actual: &Value) -> Vec<String> {
+    return match (expected, actual) {
+        (Value::Object(expected_members), Value::Object(actual_members)) => {
+            let mut diffs = vec![];
+            for (name, expected_value) in expected_members {
+                let member_path = format!("{}.{}", path, name);
+                match actual_members.iter().find(|(n, _)| n == name) {
+                    None => diffs.push(format!("{} is missing", member_path)),
+                    Some((_, actual_value)) => diffs.append(&mut json_diff(member_path, expected_value, actual_value)),
+                }
+            }
+            for (name, _) in actual_members {
+                if !expected_members.iter().any(|(n, _)| n == name) {
+                    diffs.push(format!("{}.{} is unexpected", path, name));
+                }
+            }
+            diffs
+        }
+        (Value::List(expected_values), Value::List(actual_values)) => {
+            if expected_values.len() != actual_values.len() {
+                return vec![format!("{} has {} elements instead of {}", path, actual_values.len(), expected_values.len())];
+            }
+            let mut diffs = vec![];
+            for (i, (expected_value, actual_value)) in expected_values.iter().zip(actual_values.iter()).enumerate() {
+                diffs.append(&mut json_diff(format!("{}[{}]", path, i), expected_value, actual_value));
+            }
+            diffs
+        }
+        (Value::Integer(expected_int), Value::Float(actual_float)) if *expected_int as f64 == *actual_float => vec![],
+        (Value::Float(expected_float), Value::Integer(actual_int)) if *expected_float == *actual_int as f64 => vec![],
+        _ => if expected == actual {
+            vec![]
+        } else {
+            vec![format!("{} expected {} but was {}", path, json_string(expected), json_string(actual))]
+        }
+    };
+}
+
+// json representation of a value in a diff message
+fn json_string(value: &Value) -> String {
+    return match value.to_json() {
+        Some(json) => json,
+        None => value.to_string(),
+    };
+}
+
+// lowercase hexadecimal representation of bytes
+fn hex_string(bytes: Vec<u8>) -> String {
+    return bytes.iter().map(|b| format!("{:02x}", b)).collect();
+}
+
+#[test]
+fn test_predicate_bytes() {
+    let variables = HashMap::new();
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+
+    assert_eq!(hex_string(vec![0, 15, 255]), "000fff");
+
+    // equals "000FFF"
+    let predicate_func = PredicateFunc {
+        value: PredicateFuncValue::EqualString {
+            space0: whitespace.clone(),
+            value: HurlTemplate {
+                elements: vec![HurlTemplateElement::Literal {
+                    value: HurlString2 { value: String::from("000FFF"), encoded: None }
+                }],
+                delimiter: String::from("\""),
+                source_info: SourceInfo::init(1, 1, 1, 1),
+            },
+        },
+        source_info: SourceInfo::init(1, 1, 1, 1),
+    };
+    assert_eq!(predicate_func.clone().eval(&variables, Value::Bytes(vec![0, 15, 255]), String::from("current_dir")).unwrap(), ());
+    let error = predicate_func.clone().eval(&variables, Value::Bytes(vec![0, 15]), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateValue(Value::Bytes(vec![0, 15])));
+
+    // equals file,data.bin;
+    super::body::create_test_file();
+    let predicate_func = PredicateFunc {
+        value: PredicateFuncValue::EqualBytes {
+            space0: whitespace.clone(),
+            value: Bytes::File {
+                space0: whitespace.clone(),
+                filename: Filename { value: String::from("data.bin"), source_info: SourceInfo::init(1, 8, 1, 16) },
+                space1: whitespace.clone(),
+            },
+        },
+        source_info: SourceInfo::init(1, 1, 1, 17),
+    };
+    assert_eq!(predicate_func.clone().eval(&variables, Value::Bytes(b"Hello World!".to_vec()), String::from("/tmp")).unwrap(), ());
+    let error = predicate_func.clone().eval(&variables, Value::Bytes(vec![]), String::from("/tmp")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateValue(Value::Bytes(vec![])));
+    let error = predicate_func.clone().eval(&variables, Value::Bytes(vec![]), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::FileReadAccess { value: String::from("current_dir/data.bin") });
+}
+
+#[test]
+fn test_predicate_json() {
+    let variables = HashMap::new();
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+    let predicate = |json: &str| Predicate {
+        not: false,
+        space0: whitespace.clone(),
+        predicate_func: PredicateFunc {
+            value: PredicateFuncValue::EqualJson { space0: whitespace.clone(), value: json.to_string() },
+            source_info: SourceInfo::init(1, 1, 1, 10),
+        },
+    };
+    let actual = Value::Object(vec![
+        (String::from("id"), Value::Integer(1)),
+        (String::from("tags"), Value::List(vec![Value::String(String::from("a")), Value::String(String::from("b"))])),
+    ]);
+
+    assert_eq!(predicate("{\"tags\": [\"a\", \"b\"], \"id\": 1}").eval(&variables, actual.clone(), String::from("current_dir")).unwrap(), ());
+
+    let error = predicate("{\"id\": 2, \"tags\": [\"b\", \"a\"], \"name\": \"x\"}").eval(&variables, actual.clone(), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateJsonDiff(vec![
+        String::from("$.id expected 2 but was 1"),
+        String::from("$.name is missing"),
+        String::from("$.tags[0] expected \"b\" but was \"a\""),
+        String::from("$.tags[1] expected \"a\" but was \"b\""),
+    ]));
+    assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 10));
+
+    let error = predicate("{\"id\": 1}").eval(&variables, actual.clone(), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateJsonDiff(vec![String::from("$.tags is unexpected")]));
+
+    let error = predicate("[1]").eval(&variables, Value::List(vec![]), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateJsonDiff(vec![String::from("$ has 0 elements instead of 1")]));
+
+    let error = predicate("[1]").eval(&variables, Value::Integer(1), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateType);
+
+    let mut not_predicate = predicate("[1]");
+    not_predicate.not = true;
+    assert_eq!(not_predicate.eval(&variables, Value::List(vec![Value::Integer(2)]), String::from("current_dir")).unwrap(), ());
+
+    let message = jsonpath::parse_value("{\"id\": ").err().unwrap().to_string();
+    let error = predicate("{\"id\": ").eval(&variables, actual.clone(), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateInvalidJson(message));
+
+    // bytes are compared to their hexadecimal string
+    let mut variables = HashMap::new();
+    variables.insert(String::from("hash"), Value::Bytes(vec![1, 171]));
+    let actual = Value::Object(vec![(String::from("h"), Value::String(String::from("01ab")))]);
+    assert_eq!(predicate("{\"h\": {{hash}}}").eval(&variables, actual, String::from("current_dir")).unwrap(), ());
+}
+
+#[test]
+fn test_predicate_collections() {
+    let variables = HashMap::new();
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+    let regex = |value: &str| HurlTemplate {
+        elements: vec![HurlTemplateElement::Literal { value: HurlString2 { value: value.to_string(), encoded: None } }],
+        delimiter: String::from("\""),
+        source_info: SourceInfo::init(1, 10, 1, 10 + value.len()),
+    };
+    let tags = Value::List(vec![Value::String(String::from("a")), Value::String(String::from("b"))]);
+    let ids = Value::List(vec![Value::List(vec![Value::Integer(1), Value::Integer(2)])]);
+
+    let includes = |value: i64| PredicateFunc {
+        value: PredicateFuncValue::IncludeInt { space0: whitespace.clone(), value },
+        source_info: SourceInfo::init(1, 1, 1, 11),
+    };
+    assert_eq!(includes(2).eval(&variables, ids.clone(), String::from("current_dir")).unwrap(), ());
+    let error = includes(3).eval(&variables, ids.clone(), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateValue(ids.clone()));
+    assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 11));
+    let error = includes(1).eval(&variables, Value::Integer(1), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateType);
+
+    let all_match = |value: &str| PredicateFunc {
+        value: PredicateFuncValue::AllMatch { space0: whitespace.clone(), value: regex(value) },
+        source_info: SourceInfo::init(1, 1, 1, 20),
+    };
+    assert_eq!(all_match("^[a-z]$").eval(&variables, tags.clone(), String::from("current_dir")).unwrap(), ());
+    assert_eq!(all_match("a").eval(&variables, tags.clone(), String::from("current_dir")).err().unwrap().inner, RunnerError::PredicateValue(tags.clone()));
+    assert_eq!(all_match("^\\d$").eval(&variables, ids.clone(), String::from("current_dir")).unwrap(), ());
+    let error = all_match("[").eval(&variables, tags.clone(), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.source_info, SourceInfo::init(1, 10, 1, 11));
+
+    let any_match = PredicateFunc {
+        value: PredicateFuncValue::AnyMatch { space0: whitespace.clone(), value: regex("b") },
+        source_info: SourceInfo::init(1, 1, 1, 20),
+    };
+    assert_eq!(any_match.clone().eval(&variables, tags.clone(), String::from("current_dir")).unwrap(), ());
+    assert_eq!(any_match.clone().eval(&variables, Value::List(vec![]), String::from("current_dir")).err().unwrap().inner, RunnerError::PredicateValue(Value::List(vec![])));
+}
+
+#[test]
+fn test_predicate_variables() {
+    let mut variables = HashMap::new();
+    variables.insert(String::from("count"), Value::String(String::from("2")));
+    variables.insert(String::from("price"), Value::String(String::from("1.25")));
+    variables.insert(String::from("active"), Value::String(String::from("true")));
+    variables.insert(String::from("tags"), Value::String(String::from("[\"a\",\"b\"]")));
+    variables.insert(String::from("pattern"), Value::String(String::from("^[a-z]+$")));
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+    let expr = |name: &str| Expr {
+        space0: whitespace.clone(),
+        variable: Variable { name: name.to_string(), source_info: SourceInfo::init(1, 10, 1, 10 + name.len()) },
+        arguments: vec![],
+        filters: vec![],
+        space1: whitespace.clone(),
+    };
+    let template = |name: &str| HurlTemplate {
+        elements: vec![HurlTemplateElement::Expression { value: expr(name) }],
+        delimiter: String::from(""),
+        source_info: SourceInfo::init(1, 8, 1, 12 + name.len()),
+    };
+    let equals = |name: &str| PredicateFunc {
+        value: PredicateFuncValue::EqualString { space0: whitespace.clone(), value: template(name) },
+        source_info: SourceInfo::init(1, 1, 1, 20),
+    };
+    let current_dir = String::from("current_dir");
+
+    assert_eq!(equals("count").eval(&variables, Value::Integer(2), current_dir.clone()).unwrap(), ());
+    assert_eq!(equals("count").eval(&variables, Value::Integer(3), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateValue(Value::Integer(2)));
+    assert_eq!(equals("price").eval(&variables, Value::Float(1.25), current_dir.clone()).unwrap(), ());
+    assert_eq!(equals("active").eval(&variables, Value::Bool(true), current_dir.clone()).unwrap(), ());
+    assert_eq!(equals("active").eval(&variables, Value::Integer(1), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateType);
+    assert_eq!(equals("tags").eval(&variables, Value::List(vec![Value::String(String::from("a")), Value::String(String::from("b"))]), current_dir.clone()).unwrap(), ());
+    assert_eq!(equals("count").eval(&variables, Value::String(String::from("2")), current_dir.clone()).unwrap(), ());
+
+    let first_equals = PredicateFunc {
+        value: PredicateFuncValue::FirstEqualString { space0: whitespace.clone(), value: template("count") },
+        source_info: SourceInfo::init(1, 1, 1, 20),
+    };
+    assert_eq!(first_equals.eval(&variables, Value::List(vec![Value::Integer(2)]), current_dir.clone()).unwrap(), ());
+
+    let count_equals = PredicateFunc {
+        value: PredicateFuncValue::CountEqualExpression { space0: whitespace.clone(), value: expr("count") },
+        source_info: SourceInfo::init(1, 1, 1, 20),
+    };
+    assert_eq!(count_equals.clone().eval(&variables, Value::Nodeset(2), current_dir.clone()).unwrap(), ());
+    assert_eq!(count_equals.eval(&variables, Value::Nodeset(3), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateValue(Value::Nodeset(3)));
+
+    let matches = PredicateFunc {
+        value: PredicateFuncValue::Match { space0: whitespace.clone(), value: template("pattern") },
+        source_info: SourceInfo::init(1, 1, 1, 20),
+    };
+    assert_eq!(matches.clone().eval(&variables, Value::String(String::from("hello")), current_dir.clone()).unwrap(), ());
+    assert_eq!(matches.eval(&variables, Value::String(String::from("Hello")), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateValue(Value::String(String::from("Hello"))));
+}
+
+#[test]
+fn test_predicate_typed_variables() {
+    let mut variables = HashMap::new();
+    variables.insert(String::from("count"), Value::Integer(2));
+    variables.insert(String::from("price"), Value::Float(1.25));
+    variables.insert(String::from("tags"), Value::List(vec![Value::String(String::from("a")), Value::String(String::from("b"))]));
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+    let template = |name: &str| HurlTemplate {
+        elements: vec![HurlTemplateElement::Expression {
+            value: Expr {
+                space0: whitespace.clone(),
+                variable: Variable { name: name.to_string(), source_info: SourceInfo::init(1, 10, 1, 10 + name.len()) },
+                arguments: vec![],
+                filters: vec![],
+                space1: whitespace.clone(),
+            }
+        }],
+        delimiter: String::from(""),
+        source_info: SourceInfo::init(1, 8, 1, 12 + name.len()),
+    };
+    let equals = |name: &str| PredicateFunc {
+        value: PredicateFuncValue::EqualString { space0: whitespace.clone(), value: template(name) },
+        source_info: SourceInfo::init(1, 1, 1, 20),
+    };
+    let current_dir = String::from("current_dir");
+
+    assert_eq!(equals("count").eval(&variables, Value::Integer(2), current_dir.clone()).unwrap(), ());
+    assert_eq!(equals("count").eval(&variables, Value::Integer(3), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateValue(Value::Integer(3)));
+    assert_eq!(equals("count").eval(&variables, Value::String(String::from("2")), current_dir.clone()).unwrap(), ());
+    assert_eq!(equals("count").eval(&variables, Value::Bool(true), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateType);
+    assert_eq!(equals("price").eval(&variables, Value::Float(1.25), current_dir.clone()).unwrap(), ());
+    assert_eq!(equals("tags").eval(&variables, Value::List(vec![Value::String(String::from("a")), Value::String(String::from("b"))]), current_dir.clone()).unwrap(), ());
+    assert_eq!(
+        equals("tags").eval(&variables, Value::List(vec![Value::String(String::from("a")), Value::String(String::from("c"))]), current_dir.clone()).err().unwrap().inner,
+        RunnerError::PredicateJsonDiff(vec![String::from("$[1] expected \"b\" but was \"c\"")])
+    );
+
+    let first_equals = PredicateFunc {
+        value: PredicateFuncValue::FirstEqualString { space0: whitespace.clone(), value: template("tags") },
+        source_info: SourceInfo::init(1, 1, 1, 20),
+    };
+    assert_eq!(first_equals.eval(&variables, Value::List(vec![Value::List(vec![Value::String(String::from("a")), Value::String(String::from("b"))])]), current_dir.clone()).unwrap(), ());
+}
+
+#[test]
+fn test_predicate_schema() {
+    let variables = HashMap::new();
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+    let schema = |value: &str| Bytes::Base64 {
+        space0: whitespace.clone(),
+        value: value.as_bytes().to_vec(),
+        encoded: String::from("..."),
+        space1: whitespace.clone(),
+    };
+    let validates = |value: &str| PredicateFunc {
+        value: PredicateFuncValue::ValidateSchema { space0: whitespace.clone(), value: schema(value) },
+        source_info: SourceInfo::init(1, 1, 1, 30),
+    };
+    let user_schema = r#"{"type": "object", "required": ["id"], "properties": {"id": {"type": "integer"}}}"#;
+
+    assert_eq!(validates(user_schema).eval(&variables, Value::String(String::from(r#"{"id": 1}"#)), String::from("current_dir")).unwrap(), ());
+    assert_eq!(validates(user_schema).eval(&variables, Value::Bytes(String::from(r#"{"id": 1}"#).into_bytes()), String::from("current_dir")).unwrap(), ());
+
+    let error = validates(user_schema).eval(&variables, Value::String(String::from(r#"{"id": "1"}"#)), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateSchema(vec![String::from("$.id should be integer but was string")]));
+    assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 30));
+
+    let error = validates(user_schema).eval(&variables, Value::String(String::from("<user/>")), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::QueryInvalidJson);
+
+    let error = validates("{").eval(&variables, Value::String(String::from("{}")), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::InvalidJsonSchema(String::from("the schema is not a valid json (EOF while parsing an object at line 1 column 1)")));
+
+    let first_validates = PredicateFunc {
+        value: PredicateFuncValue::FirstValidateSchema { space0: whitespace.clone(), value: schema(r#"{"type": "string"}"#) },
+        source_info: SourceInfo::init(1, 1, 1, 30),
+    };
+    assert_eq!(first_validates.clone().eval(&variables, Value::List(vec![Value::String(String::from("1"))]), String::from("current_dir")).unwrap(), ());
+    let error = first_validates.eval(&variables, Value::List(vec![Value::Integer(1)]), String::from("current_dir")).err().unwrap();
+    assert_eq!(error.inner, RunnerError::PredicateSchema(vec![String::from("$ should be string but was integer")]));
+}
+
+#[test]
+fn test_predicate_date() {
+    let mut variables = HashMap::new();
+    variables.insert(String::from("release"), Value::String(String::from("2015-10-21")));
+    let whitespace = Whitespace {
+        value: String::from(" "),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+    };
+    let current_dir = String::from("current_dir");
+    let predicate = |value: PredicateFuncValue| PredicateFunc { value, source_info: SourceInfo::init(1, 1, 1, 20) };
+    let http_date = Value::String(String::from("Wed, 21 Oct 2015 07:28:00 GMT"));
+
+    let is_iso_date = predicate(PredicateFuncValue::IsIsoDate {});
+    assert_eq!(is_iso_date.clone().eval(&variables, Value::String(String::from("2015-10-21T07:28:00Z")), current_dir.clone()).unwrap(), ());
+    assert_eq!(is_iso_date.clone().eval(&variables, http_date.clone(), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateValue(http_date.clone()));
+    assert_eq!(is_iso_date.eval(&variables, Value::Integer(1), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateType);
+
+    let before_now = predicate(PredicateFuncValue::Before { space0: whitespace.clone(), value: DateValue::Now { offset: None } });
+    assert_eq!(before_now.clone().eval(&variables, http_date.clone(), current_dir.clone()).unwrap(), ());
+    assert_eq!(before_now.eval(&variables, Value::String(String::from("tomorrow")), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateType);
+
+    let after_next_week = predicate(PredicateFuncValue::After {
+        space0: whitespace.clone(),
+        value: DateValue::Now {
+            offset: Some(DateOffset { space0: whitespace.clone(), negative: false, space1: whitespace.clone(), value: 1, unit: DurationUnit::Week })
+        },
+    });
+    let in_six_days = Value::Date(Date::now().add_seconds(6 * 86400));
+    assert_eq!(after_next_week.eval(&variables, in_six_days.clone(), current_dir.clone()).err().unwrap().inner, RunnerError::PredicateValue(in_six_days));
+
+    let after_release = predicate(PredicateFuncValue::FirstAfter {
+        space0: whitespace.clone(),
+        value: DateValue::Template {
+            value: HurlTemplate {
+                elements: vec![HurlTemplateElement::Expression {
+                    value: Expr {
+                        space0: whitespace.clone(),
+                        variable: Variable { name: String::from("release"), source_info: SourceInfo::init(1, 10, 1, 17) },
+                        arguments: vec![],
+                        filters: vec![],
+                        space1: whitespace.clone(),
+                    }
+                }],
+                delimiter: String::from(""),
+                source_info: SourceInfo::init(1, 8, 1, 19),
+            }
+        },
+    });
+    assert_eq!(after_release.eval(&variables, Value::List(vec![http_date.clone()]), current_dir.clone()).unwrap(), ());
+
+    let after_invalid = predicate(PredicateFuncValue::After {
+        space0: whitespace.clone(),
+        value: DateValue::Template {
+            value: HurlTemplate {
+                elements: vec![HurlTemplateElement::Literal { value: HurlString2 { value: String::from("21/10/2015"), encoded: None } }],
+                delimiter: String::from("\""),
+                source_info: SourceInfo::init(1, 7, 1, 19),
+            }
+        },
+    });
+    let error = after_invalid.eval(&variables, http_date, current_dir).err().unwrap();
+    assert_eq!(error.inner, RunnerError::InvalidDate(String::from("21/10/2015")));
+    assert_eq!(error.source_info, SourceInfo::init(1, 7, 1, 19));
+}
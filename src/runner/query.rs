@@ -1,14 +1,20 @@
 #[cfg(test)]
 use crate::core::core::{Pos, SourceInfo};
+use std::collections::HashMap;
+
 use crate::core::core::Value;
+use crate::core::date;
 use crate::core::jsonpath;
 use crate::http;
 
-use super::core::{Error, RunnerError};
+use super::core::{Error, RunnerError, regex_error_message};
+use super::text::Textable;
 //use super::http;
 use super::super::core::ast::*;
+use super::css;
 use super::xpath;
 
+use openssl::hash::{hash, MessageDigest};
 use regex::Regex;
 
 // QueryResult
@@ -71,9 +77,36 @@ pub type QueryResult = Result<Value, Error>;
 
 
 impl Query {
-    pub fn eval(self, http_response: http::response::Response) -> QueryResult {
+    pub fn eval(self, namespaces: &HashMap<String, String>, http_request: http::request::Request, http_response: http::response::Response) -> QueryResult {
+        let mut value = self.clone().eval_value(namespaces, http_request, http_response)?;
+
+        // jsonpath always returns a list
+        // as for the equals predicates, the next filter applies to its first value
+        let mut is_jsonpath = match self.value {
+            QueryValue::Jsonpath { .. } => true,
+            _ => false,
+        };
+        for filter in self.filters {
+            if is_jsonpath {
+                value = match value {
+                    Value::List(values) => values.get(0).cloned().unwrap_or(Value::None),
+                    v => v,
+                };
+            }
+            is_jsonpath = match filter.value {
+                FilterValue::Jsonpath { .. } => true,
+                _ => false,
+            };
+            value = filter.eval(value)?;
+        }
+        return Ok(value);
+    }
+
+    fn eval_value(self, namespaces: &HashMap<String, String>, http_request: http::request::Request, http_response: http::response::Response) -> QueryResult {
         return match self.value {
             QueryValue::Status {} => Ok(Value::Integer(http_response.status as i64)),
+            QueryValue::Url {} => Ok(Value::String(http_response.url.clone())),
+            QueryValue::Method {} => Ok(Value::String(http_request.method.to_text())),
             QueryValue::Header { name: HurlString { value: header_name, .. }, .. } => {
                 match http_response.get_header(header_name.as_str(), false) {
                     //None =>  Err(Error { source_info, inner: RunnerError::QueryHeaderNotFound, assert: false }),
@@ -81,6 +114,12 @@ impl Query {
                     Some(value) => Ok(Value::String(value))
                 }
             }
+            QueryValue::RequestHeader { name: HurlString { value: header_name, .. }, .. } => {
+                match http_request.get_header(header_name.as_str(), false) {
+                    None => Ok(Value::None),
+                    Some(value) => Ok(Value::String(value))
+                }
+            }
             QueryValue::Cookie { name: HurlString { value: cookie_name, source_info, .. }, .. } => {
                 match http_response.get_cookie(cookie_name.as_str()) {
                     None => Err(Error { source_info, inner: RunnerError::QueryCookieNotFound, assert: false }),
@@ -88,24 +127,27 @@ impl Query {
                 }
             }
             QueryValue::Body {} => {
-                // can return a string if encoding is known and utf8
-                if http_response.has_utf8_body() {
-                    match String::from_utf8(http_response.body.clone()) {
-                        Ok(s) => Ok(Value::String(s)),
-                        Err(_) => Ok(Value::Bytes(http_response.body)),
-                    }
-                } else {
-                    Ok(Value::Bytes(http_response.body))
+                // decoded with the charset of the response
+                // binary content is kept as bytes
+                match http_response.encoding() {
+                    Some(_) => match http_response.text() {
+                        Some(s) => Ok(Value::String(s)),
+                        None => Ok(Value::Bytes(http_response.body)),
+                    },
+                    None => Ok(Value::Bytes(http_response.body)),
                 }
             }
+            QueryValue::Bytes {} => Ok(Value::Bytes(http_response.body)),
+            QueryValue::Sha256 {} => Ok(Value::Bytes(digest(MessageDigest::sha256(), http_response.body))),
+            QueryValue::Md5 {} => Ok(Value::Bytes(digest(MessageDigest::md5(), http_response.body))),
             QueryValue::Xpath { expr: HurlString { value, source_info, .. }, .. } => {
-                match String::from_utf8(http_response.clone().body) {
-                    Err(_) => Err(Error { source_info: self.source_info.clone(), inner: RunnerError::InvalidUtf8, assert: false }),
-                    Ok(xml) => {
+                match http_response.text() {
+                    None => Err(Error { source_info: self.source_info.clone(), inner: invalid_decoding(&http_response), assert: false }),
+                    Some(xml) => {
                         let result = if http_response.clone().is_html() {
-                            xpath::eval_html(xml, value.clone())
+                            xpath::eval_html(xml, value.clone(), namespaces)
                         } else {
-                            xpath::eval_xml(xml, value.clone())
+                            xpath::eval_xml(xml, value.clone(), namespaces)
                         };
                         match result {
                             Ok(value) =>
@@ -139,9 +181,9 @@ impl Query {
                     None => return Err(Error { source_info: source_info.clone(), inner: RunnerError::QueryInvalidJsonpathExpression {}, assert: false }),
                     Some(expr) => expr
                 };
-                let json = match String::from_utf8(http_response.body) {
-                    Err(_) => return Err(Error { source_info: self.source_info, inner: RunnerError::InvalidUtf8, assert: false }),
-                    Ok(v) => v
+                let json = match http_response.text() {
+                    None => return Err(Error { source_info: self.source_info, inner: invalid_decoding(&http_response), assert: false }),
+                    Some(v) => v
                 };
                 let value = match expr.eval(json.as_str()) {
                     Err(_) => {
@@ -154,31 +196,206 @@ impl Query {
                 Ok(value)
             }
             QueryValue::Regex { expr: HurlString { value, source_info, .. }, .. } => {
-                let s = match String::from_utf8(http_response.body) {
-                    Err(_) => return Err(Error { source_info: self.source_info, inner: RunnerError::InvalidUtf8, assert: false }),
-                    Ok(v) => v
+                let s = match http_response.text() {
+                    None => return Err(Error { source_info: self.source_info, inner: invalid_decoding(&http_response), assert: false }),
+                    Some(v) => v
                 };
                 match Regex::new(value.as_str()) {
-                    Ok(re) => match re.captures(s.as_str()) {
-                        Some(captures) => match captures.get(1) {
-                            Some(v) => Ok(Value::String(v.as_str().to_string())),
-                            None => Ok(Value::None),
-                        }
-                        None => Ok(Value::None),
-                    }
-                    Err(_) => Err(Error {
+                    Ok(re) => Ok(eval_regex(&re, s.as_str())),
+                    Err(e) => Err(Error {
                         source_info,
-                        inner: RunnerError::InvalidRegex(),
+                        inner: RunnerError::InvalidRegex(regex_error_message(e)),
                         assert: false
                     })
                 }
 
 
             }
+            QueryValue::Css { expr: HurlString { value, source_info, .. }, .. } => {
+                let expr = match css::to_xpath(value.as_str()) {
+                    Err(_) => return Err(Error { source_info, inner: RunnerError::QueryInvalidCssSelector, assert: false }),
+                    Ok(expr) => expr
+                };
+                let html = match http_response.text() {
+                    None => return Err(Error { source_info: self.source_info, inner: invalid_decoding(&http_response), assert: false }),
+                    Some(v) => v
+                };
+                // no match is an empty list, so that it can be counted
+                match xpath::eval_html_nodes(html, expr) {
+                    Ok(values) => Ok(Value::List(values.iter().map(|v| Value::String(v.clone())).collect())),
+                    Err(xpath::XpathError::InvalidHtml {}) => Err(Error {
+                        source_info: self.source_info,
+                        inner: RunnerError::QueryInvalidXml,
+                        assert: false,
+                    }),
+                    Err(_) => Err(Error {
+                        source_info,
+                        inner: RunnerError::QueryInvalidCssSelector,
+                        assert: false,
+                    }),
+                }
+            }
+        };
+    }
+}
+
+
+impl Filter {
+    pub fn eval(self, value: Value) -> QueryResult {
+        let invalid_input = |v: Value| Error { source_info: self.source_info.clone(), inner: RunnerError::FilterInvalidInput(v), assert: false };
+        return match self.value.clone() {
+            FilterValue::Regex { expr: HurlString { value: expr, source_info, .. }, .. } => {
+                let re = match Regex::new(expr.as_str()) {
+                    Ok(re) => re,
+                    Err(e) => return Err(Error { source_info, inner: RunnerError::InvalidRegex(regex_error_message(e)), assert: false }),
+                };
+                match value {
+                    Value::String(s) => Ok(eval_regex(&re, s.as_str())),
+                    Value::Bytes(bytes) => match String::from_utf8(bytes.clone()) {
+                        Ok(s) => Ok(eval_regex(&re, s.as_str())),
+                        Err(_) => Err(invalid_input(Value::Bytes(bytes))),
+                    }
+                    Value::None => Ok(Value::None),
+                    v => Err(invalid_input(v)),
+                }
+            }
+            FilterValue::Count {} => {
+                match value {
+                    Value::List(values) => Ok(Value::Integer(values.len() as i64)),
+                    Value::Nodeset(n) => Ok(Value::Integer(n as i64)),
+                    Value::Bytes(bytes) => Ok(Value::Integer(bytes.len() as i64)),
+                    Value::None => Ok(Value::Integer(0)),
+                    v => Err(invalid_input(v)),
+                }
+            }
+            FilterValue::Nth { n, .. } => {
+                match value {
+                    Value::List(values) => match values.get(n as usize) {
+                        None => Ok(Value::None),
+                        Some(value) => Ok(value.clone()),
+                    }
+                    Value::None => Ok(Value::None),
+                    v => Err(invalid_input(v)),
+                }
+            }
+            FilterValue::ToInt {} => {
+                match value {
+                    Value::Integer(i) => Ok(Value::Integer(i)),
+                    Value::Float(f) => Ok(Value::Integer(f.trunc() as i64)),
+                    Value::String(s) => match s.trim().parse::<i64>() {
+                        Ok(i) => Ok(Value::Integer(i)),
+                        Err(_) => Err(invalid_input(Value::String(s))),
+                    }
+                    Value::None => Ok(Value::None),
+                    v => Err(invalid_input(v)),
+                }
+            }
+            FilterValue::Split { sep: HurlString { value: sep, .. }, .. } => {
+                match value {
+                    Value::String(s) => Ok(Value::List(s.split(sep.as_str()).map(|e| Value::String(e.to_string())).collect())),
+                    Value::None => Ok(Value::None),
+                    v => Err(invalid_input(v)),
+                }
+            }
+            FilterValue::Replace { old_value: HurlString { value: old_value, .. }, new_value: HurlString { value: new_value, .. }, .. } => {
+                match value {
+                    Value::String(s) => Ok(Value::String(s.replace(old_value.as_str(), new_value.as_str()))),
+                    Value::None => Ok(Value::None),
+                    v => Err(invalid_input(v)),
+                }
+            }
+            FilterValue::Base64Decode {} => {
+                match value {
+                    Value::String(s) => {
+                        // accept standard and url-safe (jwt) encodings
+                        let bytes = match base64::decode(s.trim()) {
+                            Ok(bytes) => bytes,
+                            Err(_) => match base64::decode_config(s.trim(), base64::URL_SAFE_NO_PAD) {
+                                Ok(bytes) => bytes,
+                                Err(_) => return Err(invalid_input(Value::String(s))),
+                            }
+                        };
+                        match String::from_utf8(bytes.clone()) {
+                            Ok(s) => Ok(Value::String(s)),
+                            Err(_) => Ok(Value::Bytes(bytes)),
+                        }
+                    }
+                    Value::None => Ok(Value::None),
+                    v => Err(invalid_input(v)),
+                }
+            }
+            FilterValue::ToDate { format: HurlString { value: format, source_info, .. }, .. } => {
+                match date::check_format(format.as_str()) {
+                    Err(message) => return Err(Error { source_info, inner: RunnerError::InvalidDateFormat(message), assert: false }),
+                    Ok(()) => {}
+                }
+                match value {
+                    Value::String(s) => match date::Date::parse(s.trim(), format.as_str()) {
+                        Some(date) => Ok(Value::Date(date)),
+                        None => Err(invalid_input(Value::String(s))),
+                    }
+                    Value::Date(date) => Ok(Value::Date(date)),
+                    Value::None => Ok(Value::None),
+                    v => Err(invalid_input(v)),
+                }
+            }
+            FilterValue::Jsonpath { expr: HurlString { value: expr, source_info, .. }, .. } => {
+                let expr = match jsonpath::Expr::init(expr.as_str()) {
+                    None => return Err(Error { source_info, inner: RunnerError::QueryInvalidJsonpathExpression {}, assert: false }),
+                    Some(expr) => expr
+                };
+                let json = match value {
+                    Value::String(s) => s,
+                    Value::Bytes(bytes) => match String::from_utf8(bytes.clone()) {
+                        Ok(s) => s,
+                        Err(_) => return Err(invalid_input(Value::Bytes(bytes))),
+                    }
+                    Value::None => return Ok(Value::None),
+                    v => return Err(invalid_input(v)),
+                };
+                match expr.eval(json.as_str()) {
+                    Err(_) => Err(invalid_input(Value::String(json))),
+                    Ok(value) => if value == Value::List(vec![]) { Ok(Value::None) } else { Ok(value) }
+                }
+            }
         };
     }
 }
 
+fn invalid_decoding(http_response: &http::response::Response) -> RunnerError {
+    return match http_response.encoding() {
+        Some(encoding) if encoding == encoding_rs::UTF_8 => RunnerError::InvalidUtf8,
+        Some(encoding) => RunnerError::InvalidDecoding { charset: encoding.name().to_string() },
+        None => RunnerError::InvalidUtf8,
+    };
+}
+
+fn digest(algorithm: MessageDigest, bytes: Vec<u8>) -> Vec<u8> {
+    return match hash(algorithm, bytes.as_slice()) {
+        Ok(digest) => digest.to_vec(),
+        Err(e) => panic!("can not compute digest: {}", e), // only fails if openssl is broken
+    };
+}
+
+// value extracted by a regex
+// - the first named group if the regex has named groups (even if it is not the first group)
+// - the first capture group otherwise
+// - no value if the regex has no group
+fn eval_regex(re: &Regex, s: &str) -> Value {
+    let captures = match re.captures(s) {
+        None => return Value::None,
+        Some(captures) => captures,
+    };
+    let group = match re.capture_names().flatten().next() {
+        Some(name) => captures.name(name),
+        None => captures.get(1),
+    };
+    return match group {
+        Some(v) => Value::String(v.as_str().to_string()),
+        None => Value::None,
+    };
+}
+
 
 #[cfg(test)]
 pub fn xpath_invalid_query() -> Query {
@@ -197,6 +414,7 @@ pub fn xpath_invalid_query() -> Query {
                 source_info: SourceInfo::init(1, 7, 1, 10),
             },
         },
+        filters: vec![],
     }
 }
 
@@ -217,6 +435,7 @@ pub fn xpath_count_user_query() -> Query {
                 source_info: SourceInfo::init(0, 0, 0, 0),
             },
         },
+        filters: vec![],
     }
 }
 
@@ -236,6 +455,7 @@ pub fn xpath_users() -> Query {
                 source_info: SourceInfo::init(0, 0, 0, 0),
             },
         },
+        filters: vec![],
     }
 }
 
@@ -245,11 +465,42 @@ pub fn xpath_users() -> Query {
 #[test]
 fn test_query_status() {
     assert_eq!(
-        Query { source_info: SourceInfo::init(0, 0, 0, 0), value: QueryValue::Status {} }.eval(http::response::hello_http_response()).unwrap(),
+        Query { source_info: SourceInfo::init(0, 0, 0, 0), value: QueryValue::Status {}, filters: vec![] }.eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
         Value::Integer(200)
     );
 }
 
+#[test]
+fn test_query_request() {
+    let query = |value| Query { source_info: SourceInfo::init(0, 0, 0, 0), value, filters: vec![] };
+    let whitespace = Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) };
+    let request_header = |name: &str| query(QueryValue::RequestHeader {
+        space0: whitespace.clone(),
+        space1: whitespace.clone(),
+        name: HurlString { value: name.to_string(), encoded: None, source_info: SourceInfo::init(1, 16, 1, 16 + name.len()) },
+    });
+
+    // the url of the response, after redirects
+    let mut http_response = http::response::hello_http_response();
+    http_response.url = String::from("http://localhost:8000/redirected");
+    assert_eq!(
+        query(QueryValue::Url {}).eval(&HashMap::new(), http::request::hello_http_request(), http_response).unwrap(),
+        Value::String(String::from("http://localhost:8000/redirected"))
+    );
+    assert_eq!(
+        query(QueryValue::Method {}).eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::String(String::from("GET"))
+    );
+    assert_eq!(
+        request_header("host").eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::String(String::from("localhost"))
+    );
+    assert_eq!(
+        request_header("Authorization").eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::None
+    );
+}
+
 // endregion
 
 // region test header
@@ -267,11 +518,12 @@ fn test_header_not_found() {
                 source_info: SourceInfo::init(1, 8, 1, 14),
             },
         },
+        filters: vec![],
     };
 //    let error = query_header.eval(http::hello_http_response()).err().unwrap();
 //    assert_eq!(error.source_info.start, Pos { line: 1, column: 8 });
 //    assert_eq!(error.inner, RunnerError::QueryHeaderNotFound);
-    assert_eq!(query_header.eval(http::response::hello_http_response()).unwrap(), Value::None);
+    assert_eq!(query_header.eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(), Value::None);
 }
 
 #[test]
@@ -287,9 +539,10 @@ fn test_header() {
                 source_info: SourceInfo::init(1, 8, 1, 20),
             },
         },
+        filters: vec![],
     };
     assert_eq!(
-        query_header.eval(http::response::hello_http_response()).unwrap(),
+        query_header.eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
         Value::String(String::from("text/html; charset=utf-8"))
     );
 }
@@ -304,19 +557,41 @@ fn test_body() {
         Query {
             source_info: SourceInfo::init(0, 0, 0, 0),
             value: QueryValue::Body {},
-        }.eval(http::response::hello_http_response()).unwrap(),
+            filters: vec![],
+        }.eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
         Value::String(String::from("Hello World!"))
     );
     assert_eq!(
         Query {
             source_info: SourceInfo::init(0, 0, 0, 0),
             value: QueryValue::Body {},
-        }.eval(http::response::bytes_http_response()).unwrap(),
+            filters: vec![],
+        }.eval(&HashMap::new(), http::request::hello_http_request(), http::response::bytes_http_response()).unwrap(),
         Value::Bytes(vec![255])
     );
 
 }
 
+#[test]
+fn test_bytes() {
+    let query = |value| Query { source_info: SourceInfo::init(0, 0, 0, 0), value, filters: vec![] };
+    assert_eq!(
+        query(QueryValue::Bytes {}).eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::Bytes(b"Hello World!".to_vec())
+    );
+    assert_eq!(
+        query(QueryValue::Sha256 {}).eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::Bytes(vec![
+            127, 131, 177, 101, 127, 241, 252, 83, 185, 45, 193, 129, 72, 161, 214, 93,
+            252, 45, 75, 31, 163, 214, 119, 40, 74, 221, 210, 0, 18, 109, 144, 105
+        ])
+    );
+    assert_eq!(
+        query(QueryValue::Md5 {}).eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::Bytes(vec![237, 7, 98, 135, 83, 46, 134, 54, 94, 132, 30, 146, 191, 197, 13, 140])
+    );
+}
+
 // endregion
 
 // region test xpath
@@ -327,10 +602,11 @@ fn test_query_invalid_utf8() {
     let http_response = http::response::Response {
         version: http::response::Version::Http10,
         status: 0,
+        url: String::from("http://localhost:8000/"),
         headers: vec![],
         body: vec![200],
     };
-    let error = xpath_users().eval(http_response).err().unwrap();
+    let error = xpath_users().eval(&HashMap::new(), http::request::hello_http_request(), http_response).err().unwrap();
     assert_eq!(error.source_info.start, Pos { line: 1, column: 1 });
     assert_eq!(error.inner, RunnerError::InvalidUtf8);
 }
@@ -352,16 +628,17 @@ fn test_query_xpath_error_eval() {
                 source_info: SourceInfo::init(1, 7, 1, 10),
             },
         },
+        filters: vec![],
     };
-    let error = query.eval(http::response::xml_two_users_http_response()).err().unwrap();
+    let error = query.eval(&HashMap::new(), http::request::hello_http_request(), http::response::xml_two_users_http_response()).err().unwrap();
     assert_eq!(error.inner, RunnerError::QueryInvalidXpathEval);
     assert_eq!(error.source_info.start, Pos { line: 1, column: 7 });
 }
 
 #[test]
 fn test_query_xpath() {
-    assert_eq!(xpath_users().eval(http::response::xml_two_users_http_response()).unwrap(), Value::Nodeset(2));
-    assert_eq!(xpath_count_user_query().eval(http::response::xml_two_users_http_response()).unwrap(), Value::Float(2, 0));
+    assert_eq!(xpath_users().eval(&HashMap::new(), http::request::hello_http_request(), http::response::xml_two_users_http_response()).unwrap(), Value::Nodeset(2));
+    assert_eq!(xpath_count_user_query().eval(&HashMap::new(), http::request::hello_http_request(), http::response::xml_two_users_http_response()).unwrap(), Value::Float(2.0));
 }
 
 
@@ -382,13 +659,14 @@ pub fn xpath_html_charset() -> Query {
                 source_info: SourceInfo::init(0, 0, 0, 0),
             },
         },
+        filters: vec![],
     }
 }
 
 
 #[test]
 fn test_query_xpath_with_html() {
-    assert_eq!(xpath_html_charset().eval(http::response::html_http_response()).unwrap(), Value::String(String::from("UTF-8")));
+    assert_eq!(xpath_html_charset().eval(&HashMap::new(), http::request::hello_http_request(), http::response::html_http_response()).unwrap(), Value::String(String::from("UTF-8")));
 }
 
 
@@ -401,6 +679,7 @@ pub fn json_http_response() -> http::response::Response {
     return http::response::Response {
         version: http::response::Version::Http10,
         status: 0,
+        url: String::from("http://localhost:8000/json"),
         headers: vec![],
         body: String::into_bytes(r#"
 {
@@ -430,6 +709,7 @@ pub fn jsonpath_success() -> Query {
                 source_info: SourceInfo::init(1, 10, 1, 19),
             },
         },
+        filters: vec![],
     };
 }
 
@@ -450,6 +730,7 @@ pub fn jsonpath_errors() -> Query {
                 source_info: SourceInfo::init(1, 10, 1, 18),
             },
         },
+        filters: vec![],
     };
 }
 
@@ -471,9 +752,10 @@ fn test_query_jsonpath_invalid_expression() {
                 source_info: SourceInfo::init(1, 10, 1, 13),
             },
         },
+        filters: vec![],
     };
 
-    let error = jsonpath_query.eval(json_http_response()).err().unwrap();
+    let error = jsonpath_query.eval(&HashMap::new(), http::request::hello_http_request(), json_http_response()).err().unwrap();
     assert_eq!(error.source_info.start, Pos { line: 1, column: 10 });
     assert_eq!(error.inner, RunnerError::QueryInvalidJsonpathExpression);
 }
@@ -484,10 +766,11 @@ fn test_query_invalid_json() {
     let http_response = http::response::Response {
         version: http::response::Version::Http10,
         status: 0,
+        url: String::from("http://localhost:8000/"),
         headers: vec![],
         body: String::into_bytes(String::from("xxx")),
     };
-    let error = jsonpath_success().eval(http_response).err().unwrap();
+    let error = jsonpath_success().eval(&HashMap::new(), http::request::hello_http_request(), http_response).err().unwrap();
     assert_eq!(error.source_info.start, Pos { line: 1, column: 1 });
     assert_eq!(error.inner, RunnerError::QueryInvalidJson);
 }
@@ -497,21 +780,22 @@ fn test_query_json_not_found() {
     let http_response = http::response::Response {
         version: http::response::Version::Http10,
         status: 0,
+        url: String::from("http://localhost:8000/"),
         headers: vec![],
         body: String::into_bytes(String::from("{}")),
     };
     //assert_eq!(jsonpath_success().eval(http_response).unwrap(), Value::List(vec![]));
-    assert_eq!(jsonpath_success().eval(http_response).unwrap(), Value::None);
+    assert_eq!(jsonpath_success().eval(&HashMap::new(), http::request::hello_http_request(), http_response).unwrap(), Value::None);
 }
 
 #[test]
 fn test_query_json() {
     assert_eq!(
-        jsonpath_success().eval(json_http_response()).unwrap(),
+        jsonpath_success().eval(&HashMap::new(), http::request::hello_http_request(), json_http_response()).unwrap(),
         Value::List(vec![Value::Bool(false)])
     );
     assert_eq!(
-        jsonpath_errors().eval(json_http_response()).unwrap(),
+        jsonpath_errors().eval(&HashMap::new(), http::request::hello_http_request(), json_http_response()).unwrap(),
         Value::List(vec![Value::List(vec![
             Value::Object(vec![(String::from("id"), Value::String(String::from("error1")))]),
             Value::Object(vec![(String::from("id"), Value::String(String::from("error2")))])
@@ -539,9 +823,29 @@ pub fn regex_name() -> Query {
                 source_info: SourceInfo::init(1, 7, 1, 26),
             },
         },
+        filters: vec![],
     };
 }
 
+#[cfg(test)]
+pub fn regex_no_group() -> Query {
+// regex "Hello [a-zA-Z]+"
+    return Query {
+        source_info: SourceInfo::init(1, 1, 1, 23),
+        value: QueryValue::Regex {
+            space0: Whitespace {
+                value: String::from(""),
+                source_info: SourceInfo::init(1, 6, 1, 7),
+            },
+            expr: HurlString {
+                value: String::from("Hello [a-zA-Z]+"),
+                encoded: Some("Hello [a-zA-Z]+".to_string()),
+                source_info: SourceInfo::init(1, 7, 1, 23),
+            },
+        },
+        filters: vec![],
+    };
+}
 
 #[cfg(test)]
 pub fn regex_invalid() -> Query {
@@ -559,6 +863,7 @@ pub fn regex_invalid() -> Query {
                 source_info: SourceInfo::init(1, 7, 1, 10),
             },
         },
+        filters: vec![],
     };
 }
 
@@ -567,13 +872,209 @@ pub fn regex_invalid() -> Query {
 #[test]
 fn test_query_regex() {
     assert_eq!(
-        regex_name().eval(http::response::hello_http_response()).unwrap(),
+        regex_name().eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
         Value::String("World".to_string())
     );
 
-    let error = regex_invalid().eval(http::response::hello_http_response()).err().unwrap();
+    // no value is returned when there is no group
+    assert_eq!(
+        regex_no_group().eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::None
+    );
+
+    let error = regex_invalid().eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).err().unwrap();
     assert_eq!(error.source_info, SourceInfo::init(1, 7, 1, 10));
-    assert_eq!(error.inner, RunnerError::InvalidRegex());
+    assert_eq!(error.inner, RunnerError::InvalidRegex(String::from("repetition operator missing expression")));
+}
+
+#[test]
+fn test_eval_regex() {
+    let re = Regex::new("Hello [a-zA-Z]+").unwrap();
+    assert_eq!(eval_regex(&re, "Hello World!"), Value::None);
+
+    let re = Regex::new("(Hello) ([a-zA-Z]+)").unwrap();
+    assert_eq!(eval_regex(&re, "Hello World!"), Value::String(String::from("Hello")));
+
+    let re = Regex::new("(Hello) (?P<name>[a-zA-Z]+)").unwrap();
+    assert_eq!(eval_regex(&re, "Hello World!"), Value::String(String::from("World")));
+
+    let re = Regex::new("Hello ([0-9]+)").unwrap();
+    assert_eq!(eval_regex(&re, "Hello World!"), Value::None);
+}
+
+#[cfg(test)]
+pub fn filter(value: FilterValue) -> Filter {
+    Filter {
+        space0: Whitespace {
+            value: String::from(" "),
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        },
+        source_info: SourceInfo::init(1, 22, 1, 30),
+        value,
+    }
+}
+
+#[cfg(test)]
+pub fn filter_string(value: &str) -> HurlString {
+    HurlString {
+        value: value.to_string(),
+        encoded: None,
+        source_info: SourceInfo::init(1, 28, 1, 30),
+    }
 }
 
-// endregion
\ No newline at end of file
+#[cfg(test)]
+pub fn regex_filter(expr: &str) -> Filter {
+    filter(FilterValue::Regex {
+        space0: Whitespace {
+            value: String::from(" "),
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        },
+        expr: filter_string(expr),
+    })
+}
+
+#[test]
+fn test_query_with_filters() {
+    // header Content-Type regex "charset=(.*)"
+    let query = Query {
+        source_info: SourceInfo::init(1, 1, 1, 20),
+        value: QueryValue::Header {
+            space0: Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::init(1, 7, 1, 8),
+            },
+            name: HurlString {
+                value: String::from("Content-Type"),
+                encoded: None,
+                source_info: SourceInfo::init(1, 8, 1, 20),
+            },
+        },
+        filters: vec![regex_filter("charset=(.*)")],
+    };
+    assert_eq!(
+        query.eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::String(String::from("utf-8"))
+    );
+
+    // status regex "[0-9]"
+    let query = Query {
+        source_info: SourceInfo::init(1, 1, 1, 7),
+        value: QueryValue::Status {},
+        filters: vec![regex_filter("[0-9]")],
+    };
+    let error = query.eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).err().unwrap();
+    assert_eq!(error.source_info, SourceInfo::init(1, 22, 1, 30));
+    assert_eq!(error.inner, RunnerError::FilterInvalidInput(Value::Integer(200)));
+
+    // body split " " count
+    let query = Query {
+        source_info: SourceInfo::init(1, 1, 1, 5),
+        value: QueryValue::Body {},
+        filters: vec![
+            filter(FilterValue::Split { space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) }, sep: filter_string(" ") }),
+            filter(FilterValue::Count {}),
+        ],
+    };
+    assert_eq!(
+        query.eval(&HashMap::new(), http::request::hello_http_request(), http::response::hello_http_response()).unwrap(),
+        Value::Integer(2)
+    );
+}
+
+#[test]
+fn test_filter_eval() {
+    let whitespace = Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) };
+
+    assert_eq!(filter(FilterValue::Count {}).eval(Value::List(vec![Value::Integer(1), Value::Integer(2)])).unwrap(), Value::Integer(2));
+    assert_eq!(filter(FilterValue::Count {}).eval(Value::None).unwrap(), Value::Integer(0));
+
+    let nth = filter(FilterValue::Nth { space0: whitespace.clone(), n: 1 });
+    assert_eq!(nth.clone().eval(Value::List(vec![Value::Integer(1), Value::Integer(2)])).unwrap(), Value::Integer(2));
+    assert_eq!(nth.clone().eval(Value::List(vec![Value::Integer(1)])).unwrap(), Value::None);
+
+    let to_int = filter(FilterValue::ToInt {});
+    assert_eq!(to_int.clone().eval(Value::String(String::from("42"))).unwrap(), Value::Integer(42));
+    let error = to_int.clone().eval(Value::String(String::from("x"))).err().unwrap();
+    assert_eq!(error.source_info, SourceInfo::init(1, 22, 1, 30));
+    assert_eq!(error.inner, RunnerError::FilterInvalidInput(Value::String(String::from("x"))));
+
+    let replace = filter(FilterValue::Replace {
+        space0: whitespace.clone(),
+        old_value: filter_string("-"),
+        space1: whitespace.clone(),
+        new_value: filter_string("_"),
+    });
+    assert_eq!(replace.eval(Value::String(String::from("a-b-c"))).unwrap(), Value::String(String::from("a_b_c")));
+
+    let base64_decode = filter(FilterValue::Base64Decode {});
+    assert_eq!(base64_decode.clone().eval(Value::String(String::from("SGVsbG8="))).unwrap(), Value::String(String::from("Hello")));
+    assert_eq!(base64_decode.clone().eval(Value::String(String::from("eyJzdWIiOiJib2IifQ"))).unwrap(), Value::String(String::from("{\"sub\":\"bob\"}")));
+    assert_eq!(base64_decode.clone().eval(Value::String(String::from("/w=="))).unwrap(), Value::Bytes(vec![255]));
+
+    let jsonpath = filter(FilterValue::Jsonpath { space0: whitespace.clone(), expr: filter_string("$.sub") });
+    assert_eq!(jsonpath.clone().eval(Value::String(String::from("{\"sub\":\"bob\"}"))).unwrap(), Value::List(vec![Value::String(String::from("bob"))]));
+    assert_eq!(jsonpath.clone().eval(Value::String(String::from("{}"))).unwrap(), Value::None);
+    assert_eq!(jsonpath.clone().eval(Value::Integer(1)).err().unwrap().inner, RunnerError::FilterInvalidInput(Value::Integer(1)));
+
+    let to_date = filter(FilterValue::ToDate { space0: whitespace.clone(), format: filter_string("%d/%m/%Y") });
+    assert_eq!(to_date.clone().eval(Value::String(String::from("21/10/2015"))).unwrap(), Value::Date(date::Date::parse_iso("2015-10-21").unwrap()));
+    assert_eq!(to_date.clone().eval(Value::String(String::from("2015-10-21"))).err().unwrap().inner, RunnerError::FilterInvalidInput(Value::String(String::from("2015-10-21"))));
+    let to_date = filter(FilterValue::ToDate { space0: whitespace.clone(), format: filter_string("%Y-%i") });
+    assert_eq!(to_date.eval(Value::String(String::from("2015-10"))).err().unwrap().inner, RunnerError::InvalidDateFormat(String::from("unknown directive %i")));
+}
+
+// endregion
+// region query css
+
+#[cfg(test)]
+pub fn css_query(selector: &str) -> Query {
+    Query {
+        source_info: SourceInfo::init(1, 1, 1, 5 + selector.len()),
+        value: QueryValue::Css {
+            space0: Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::init(1, 4, 1, 5),
+            },
+            expr: HurlString {
+                value: selector.to_string(),
+                encoded: None,
+                source_info: SourceInfo::init(1, 5, 1, 5 + selector.len()),
+            },
+        },
+        filters: vec![],
+    }
+}
+
+#[test]
+fn test_query_css() {
+    let http_response = http::response::Response {
+        version: http::response::Version::Http10,
+        status: 200,
+        url: String::from("http://localhost:8000/"),
+        headers: vec![],
+        body: String::into_bytes(String::from(r#"<html><body>
+<div class="alert success"><p>Saved</p></div>
+<ul><li>Apple</li><li><a href="/banana">Banana</a></li></ul>
+</body></html>"#)),
+    };
+    assert_eq!(
+        css_query("div.alert > p").eval(&HashMap::new(), http::request::hello_http_request(), http_response.clone()).unwrap(),
+        Value::List(vec![Value::String(String::from("Saved"))])
+    );
+    assert_eq!(
+        css_query("li").eval(&HashMap::new(), http::request::hello_http_request(), http_response.clone()).unwrap(),
+        Value::List(vec![Value::String(String::from("Apple")), Value::String(String::from("Banana"))])
+    );
+    assert_eq!(
+        css_query("li a::attr(href)").eval(&HashMap::new(), http::request::hello_http_request(), http_response.clone()).unwrap(),
+        Value::List(vec![Value::String(String::from("/banana"))])
+    );
+    assert_eq!(css_query("table").eval(&HashMap::new(), http::request::hello_http_request(), http_response.clone()).unwrap(), Value::List(vec![]));
+
+    let error = css_query("li:hover").eval(&HashMap::new(), http::request::hello_http_request(), http_response.clone()).err().unwrap();
+    assert_eq!(error.source_info, SourceInfo::init(1, 5, 1, 13));
+    assert_eq!(error.inner, RunnerError::QueryInvalidCssSelector);
+}
+
+// endregion
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 
 #[cfg(test)]
 use crate::core::core::SourceInfo;
+use crate::core::core::Value;
 use crate::http;
 
 use super::core::{Error, RunnerError};
@@ -26,7 +27,7 @@ use super::super::core::ast::*;
 // region request
 impl Request {
     pub fn eval(self,
-                variables: &HashMap<String, String>,
+                variables: &HashMap<String, Value>,
                 context_dir: String,
     )
                 -> Result<http::request::Request, Error> {
@@ -46,7 +47,7 @@ impl Request {
 
         // query string from url
         // parse url string
-        let (url, params) = match external_url::Url::parse(url.as_str()) {
+        let url = match external_url::Url::parse(url.as_str()) {
             Err(_) => {
                 return Err(Error {
                     source_info: self.clone().url.source_info,
@@ -55,27 +56,16 @@ impl Request {
                 });
             }
             Ok(u) => {
-                let url = http::core::Url {
+                // the query string is not decoded, so that it is sent as written
+                http::core::Url {
                     scheme: u.scheme().to_string(),
                     host: u.host_str().unwrap().to_string(),
                     port: u.port(),
                     path: u.path().to_string(),
-                };
-                let mut params: Vec<http::core::Param> = vec![];
-                for (name, value) in u.query_pairs() {
-                    params.push(http::core::Param {
-                        name: name.to_string(),
-                        value: value.to_string(),
-                    });
+                    querystring: u.query().map(|s| s.to_string()),
                 }
-
-
-                (url, params)
             }
         };
-        for param in params {
-            querystring.push(param);
-        }
         for param in self.clone().querystring_params() {
             let name = param.name.value;
             let value = param.value.eval(variables)?;
@@ -135,7 +125,7 @@ impl Request {
 //        let querystring_params= vec![];
 
         let bytes = match self.clone().body {
-            Some(body) => body.eval(context_dir)?,
+            Some(body) => body.eval(variables, context_dir)?,
             None => {
                 if !self.clone().form_params().is_empty() {
                     let mut params = vec![];
@@ -170,7 +160,7 @@ impl Request {
 
 
 impl Header {
-    pub fn eval(self, variables: &HashMap<String, String>) -> Result<http::core::Header, Error> {
+    pub fn eval(self, variables: &HashMap<String, Value>) -> Result<http::core::Header, Error> {
         let name = self.name.value;
         let value = self.value.eval(variables)?;
         return Ok(http::core::Header { name, value });
@@ -221,6 +211,8 @@ pub fn hello_request() -> Request {
                             name: String::from("base_url"),
                             source_info: SourceInfo::init(1, 7, 1, 15),
                         },
+                        arguments: vec![],
+                        filters: vec![],
                         space1: whitespace.clone(),
                     }
                 },
@@ -303,6 +295,8 @@ pub fn query_request() -> Request {
                                         name: String::from("param1"),
                                         source_info: SourceInfo::init(1, 7, 1, 15),
                                     },
+                                    arguments: vec![],
+                                    filters: vec![],
                                     space1: whitespace.clone(),
                                 }
                             },
@@ -337,7 +331,7 @@ pub fn test_error_variable() {
 pub fn test_hello_request() {
     let mut variables = HashMap::new();
    // let cookies = HashMap::new();
-    variables.insert(String::from("base_url"), String::from("http://localhost:8000"));
+    variables.insert(String::from("base_url"), Value::String(String::from("http://localhost:8000")));
     let http_request = hello_request().eval(&variables, "current_dir".to_string()).unwrap();
     assert_eq!(http_request, http::request::hello_http_request());
 }
@@ -346,7 +340,7 @@ pub fn test_hello_request() {
 pub fn test_query_request() {
     let mut variables = HashMap::new();
     //let cookies = HashMap::new();
-    variables.insert(String::from("param1"), String::from("value1"));
+    variables.insert(String::from("param1"), Value::String(String::from("value1")));
     let http_request = query_request().eval(&variables, "current_dir".to_string()).unwrap();
     assert_eq!(http_request, http::request::query_http_request());
 }
@@ -398,10 +392,7 @@ pub fn eval_url(s: String) -> Result<http::core::Url, RunnerError> {
             host: u.host_str().unwrap().to_string(),
             port: u.port(),
             path: u.path().to_string(),
-//            querystring: match u.query() {
-//                None => None,
-//                Some(s) => Some(s.to_string())
-//            },
+            querystring: u.query().map(|s| s.to_string()),
         })
     };
 }
@@ -413,7 +404,7 @@ pub fn test_eval_url() {
     let url = eval_url(String::from("http://localhost:8000/querystring-params?param1=value1")).unwrap();
     assert_eq!(url.host, "localhost");
     assert_eq!(url.port, Some(8000));
-// assert_eq!(url.querystring.unwrap(), String::from("param1=value1"));
+    assert_eq!(url.querystring.unwrap(), String::from("param1=value1"));
 }
 
 // endregion
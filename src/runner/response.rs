@@ -41,7 +41,7 @@ impl Response {
 //    }
 
     //pub fn eval_asserts(self, _variables: &HashMap<String, String>, http_response: http::Response) -> Result<Vec<AssertResult>, Error> {
-    pub fn eval_asserts(self, _variables: &HashMap<String, String>, http_response: http::response::Response) -> Vec<AssertResult> {
+    pub fn eval_asserts(self, _variables: &HashMap<String, Value>, namespaces: &HashMap<String, String>, http_request: http::request::Request, http_response: http::response::Response, context_dir: String) -> Vec<AssertResult> {
         let mut asserts = vec![];
 
         let version = self.clone().version;
@@ -58,8 +58,14 @@ impl Response {
             source_info: status.source_info,
         });
 
-        for header in self.clone().headers {
-            //eprintln!(">> header {:?}", header);
+        for response_header in self.clone().headers {
+            let header = match response_header {
+                ResponseHeader::Value(header) => header,
+                ResponseHeader::Assert(header_assert) => {
+                    asserts.push(header_assert.eval(_variables, http_response.clone(), context_dir.clone()));
+                    continue;
+                }
+            };
             match header.value.clone().eval(_variables) {
                 Err(e) => {
                     asserts.push(AssertResult::Header {
@@ -69,45 +75,53 @@ impl Response {
                     });
                 }
                 Ok(expected) => {
-                    match http_response.get_header(header.name.value.clone().as_str(), false) {
-                        None => {
-                            asserts.push(AssertResult::Header {
-                                actual: Err(Error {
-                                    source_info: header.name.clone().source_info,
-                                    inner: RunnerError::QueryHeaderNotFound {},
-                                    assert: false,
-                                }),
-                                expected,
+                    let values = http_response.get_headers(header.name.value.as_str());
+                    if values.is_empty() {
+                        asserts.push(AssertResult::Header {
+                            actual: Err(Error {
                                 source_info: header.name.clone().source_info,
-                            });
-                        }
-                        Some(actual) => {
-                            asserts.push(AssertResult::Header {
-                                actual: Ok(actual),
-                                expected,
-                                source_info: header.value.clone().source_info,
-                            });
-                        }
+                                inner: RunnerError::QueryHeaderNotFound {},
+                                assert: false,
+                            }),
+                            expected,
+                            source_info: header.name.clone().source_info,
+                        });
+                    } else {
+                        asserts.push(AssertResult::Header {
+                            actual: Ok(values),
+                            expected,
+                            source_info: header.value.clone().source_info,
+                        });
                     }
                 }
             }
         }
         for assert in self.asserts() {
-            let assert_result = assert.eval(_variables, http_response.clone());
+            let assert_result = assert.eval(_variables, namespaces, http_request.clone(), http_response.clone(), context_dir.clone());
             //eprintln!(">> assert {:#?}", assert_result.clone());
             asserts.push(assert_result);
         }
         asserts
     }
 
-    pub fn eval_captures(self, _variables: &HashMap<String, String>, http_response: http::response::Response) -> Result<Vec<(String, Value)>, Error> {
+    pub fn eval_captures(self, _variables: &HashMap<String, Value>, namespaces: &HashMap<String, String>, http_request: http::request::Request, http_response: http::response::Response) -> Result<Vec<(String, Value)>, Error> {
         let mut captures = vec![];
-        for Capture { name, query, .. } in self.clone().captures() {
-            let value = query.eval(http_response.clone())?;
-            captures.push((name.value, value));
+        for capture in self.clone().captures() {
+            let capture_result = capture.eval(namespaces, http_request.clone(), http_response.clone())?;
+            captures.push(capture_result);
         }
         Ok(captures)
     }
+
+    // namespaces declared in the [Namespaces] section override the init ones (command line)
+    pub fn eval_namespaces(self, variables: &HashMap<String, Value>, init_namespaces: &HashMap<String, String>) -> Result<HashMap<String, String>, Error> {
+        let mut namespaces = init_namespaces.clone();
+        for param in self.namespaces() {
+            let href = param.value.eval(variables)?;
+            namespaces.insert(param.name.value, href);
+        }
+        Ok(namespaces)
+    }
 }
 
 
@@ -174,7 +188,7 @@ pub fn user_response() -> Response {
 pub fn test_eval_asserts() {
     let variables = HashMap::new();
     assert_eq!(
-        user_response().eval_asserts(&variables, http::response::xml_two_users_http_response()),
+        user_response().eval_asserts(&variables, &HashMap::new(), http::request::hello_http_request(), http::response::xml_two_users_http_response(), String::from("current_dir")),
         vec![
             AssertResult::Version {
                 actual: String::from("1.0"),
@@ -214,9 +228,9 @@ pub fn test_eval_asserts() {
 pub fn test_eval_captures() {
     let variables = HashMap::new();
     assert_eq!(
-        user_response().eval_captures(&variables, http::response::xml_two_users_http_response()).unwrap(),
+        user_response().eval_captures(&variables, &HashMap::new(), http::request::hello_http_request(), http::response::xml_two_users_http_response()).unwrap(),
         vec![
-            (String::from("UserCount"), Value::Float(2, 0))
+            (String::from("UserCount"), Value::Float(2.0))
         ]
     );
 }
\ No newline at end of file
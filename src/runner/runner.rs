@@ -1,18 +1,34 @@
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
 
 use crate::core::ast::*;
-use crate::core::core::FormatError;
+use crate::core::core::{FormatError, Value};
 use crate::http;
+use crate::parser;
 
 use super::core::*;
+use super::output;
+use super::template::take_generated_values;
+use super::xpath;
 //use super::log::*;
 use super::super::format;
 
+// to be called before running files in parallel
+pub fn init() {
+    xpath::init();
+}
+
 pub fn run(
     http_client: http::client::Client,
     hurl_file: HurlFile,
     fail_fast: bool,
-    init_variables: &HashMap<String, String>,
+    from_entry: Option<usize>,
+    to_entry: Option<usize>,
+    init_variables: &HashMap<String, Value>,
+    namespaces: &HashMap<String, String>,
     verbose: bool,
     context_dir: String,
     filename: String,
@@ -20,34 +36,116 @@ pub fn run(
     lines: Vec<String>,
     cookiejar: &mut http::cookie::CookieJar
 ) -> HurlResult {
-    let mut entries = vec![];
     let mut variables = HashMap::new();
 
     for (key, value) in init_variables {
-        variables.insert(key.to_string(), value.to_string());
+        variables.insert(key.to_string(), value.clone());
     }
 
-    //let mut variables = variables;
-    for entry in hurl_file.entries {
-        // eprintln!(">> entry ");
-        let entry_result = entry.eval(&http_client, &mut variables, cookiejar, verbose, context_dir.clone());
-        entries.push(entry_result.clone());
+    let mut context = RunContext {
+        http_client: &http_client,
+        fail_fast,
+        namespaces,
+        verbose,
+        output_color,
+        variables,
+        cookiejar,
+        entries: vec![],
+    };
+    let paths = if filename == "-" {
+        vec![]
+    } else {
+        match Path::new(filename.as_str()).canonicalize() {
+            Ok(path) => vec![path],
+            Err(_) => vec![],
+        }
+    };
+    let file = FileContext {
+        filename: filename.clone(),
+        lines,
+        context_dir,
+        includes: vec![],
+        paths,
+    };
+    run_statements(&mut context, &file, hurl_file.statements, from_entry, to_entry);
+
+    return HurlResult {
+        filename,
+        entries: context.entries,
+        dataset_row: None,
+    };
+}
+
+// shared by the hurl file and the files it includes
+// captures and cookies flow back into the including file
+struct RunContext<'a> {
+    http_client: &'a http::client::Client,
+    fail_fast: bool,
+    namespaces: &'a HashMap<String, String>,
+    verbose: bool,
+    output_color: bool,
+    variables: HashMap<String, Value>,
+    cookiejar: &'a mut http::cookie::CookieJar,
+    entries: Vec<EntryResult>,
+}
+
+struct FileContext {
+    filename: String,
+    lines: Vec<String>,
+    context_dir: String,
+    // include directives (filename, line) leading to this file, innermost first
+    includes: Vec<(String, usize)>,
+    // canonical paths of this file and the files including it, to detect cycles
+    paths: Vec<PathBuf>,
+}
+
+// return true if the run must be stopped (fail fast)
+fn run_statements(
+    context: &mut RunContext,
+    file: &FileContext,
+    statements: Vec<Statement>,
+    from_entry: Option<usize>,
+    to_entry: Option<usize>,
+) -> bool {
+    let mut entry_number = 0;
+    for statement in statements {
+        let entry_result = match statement {
+            Statement::Include(include) => {
+                // an include is run with the entry following it
+                if !is_in_range(entry_number + 1, from_entry, to_entry) {
+                    continue;
+                }
+                match run_include(context, file, include) {
+                    Ok(stopped) => {
+                        if stopped {
+                            return true;
+                        }
+                        continue;
+                    }
+                    Err(e) => EntryResult {
+                        request: None,
+                        response: None,
+                        captures: vec![],
+                        generated: take_generated_values(),
+                        asserts: vec![],
+                        errors: vec![e],
+                        skipped: false,
+                    },
+                }
+            }
+            Statement::Entry(entry) => {
+                entry_number += 1;
+                let in_range = is_in_range(entry_number, from_entry, to_entry);
+                run_entry(context, file, entry, in_range)
+            }
+        };
+        context.entries.push(entry_result.clone());
         for e in entry_result.errors.clone() {
-            let error = format::error::Error {
-                exit_code: 3,
-                source_info: e.clone().source_info,
-                description: e.clone().description(),
-                fixme: e.fixme(),
-                lines: lines.clone(),
-                filename: filename.to_string(),
-                warning: false,
-                color: output_color,
-            };
-            eprintln!("{}", error.format());
+            output::eprintln(format_error(&e, 3, file, context.output_color).format().as_str());
         }
 
-        if fail_fast && !entry_result.errors.is_empty() {
-            break;
+        if context.fail_fast && !entry_result.errors.is_empty() {
+            return true;
         }
         // TODO TO BE CLARIFIED
 //        let assert_errors: Vec<Error> = entry_result.errors.iter().filter(|e| e.assert).collect();
@@ -58,8 +156,182 @@ pub fn run(
 
         //eprintln!(">>> all cookies {:#?}", all_cookies);
     }
-    return HurlResult {
+    return false;
+}
+
+fn run_entry(context: &mut RunContext, file: &FileContext, entry: Entry, in_range: bool) -> EntryResult {
+    // the [Options] section overrides the command line options for this entry only
+    let mut client_options = context.http_client.options.clone();
+    let mut entry_verbose = context.verbose;
+    let mut delay = 0;
+    let mut skip = false;
+    let mut entry_variables = context.variables.clone();
+    let mut option_errors = vec![];
+    for option in entry.request.clone().options() {
+        match option.kind {
+            OptionKind::Insecure(value) => client_options.insecure = value,
+            OptionKind::FollowLocation(value) => client_options.follow_location = value,
+            OptionKind::Delay(value) => delay = value,
+            OptionKind::Verbose(value) => entry_verbose = value,
+            OptionKind::Skip(value) => skip = value,
+            OptionKind::Variable(VariableDefinition { name, value, .. }) => match value.eval(&entry_variables) {
+                Ok(value) => {
+                    entry_variables.insert(name, Value::String(value));
+                }
+                Err(e) => option_errors.push(e),
+            },
+        }
+    }
+    if skip || !in_range {
+        if entry_verbose {
+            output::eprintln(format!("[DEBUG] Skip entry line {}", entry.request.source_info.start.line).as_str());
+        }
+        return EntryResult {
+            request: None,
+            response: None,
+            captures: vec![],
+            generated: take_generated_values(),
+            asserts: vec![],
+            errors: vec![],
+            skipped: true,
+        };
+    }
+
+    if !option_errors.is_empty() {
+        return EntryResult {
+            request: None,
+            response: None,
+            captures: vec![],
+            generated: take_generated_values(),
+            asserts: vec![],
+            errors: option_errors,
+            skipped: false,
+        };
+    }
+    if delay > 0 {
+        thread::sleep(Duration::from_millis(delay));
+    }
+    let entry_client;
+    let client = if client_options == context.http_client.options {
+        context.http_client
+    } else {
+        entry_client = http::client::Client::init(client_options);
+        &entry_client
+    };
+    let entry_result = entry.eval(client, &mut entry_variables, context.namespaces, context.cookiejar, entry_verbose, file.context_dir.clone());
+    for (name, value) in entry_result.captures.clone() {
+        context.variables.insert(name, value);
+    }
+    return entry_result;
+}
+
+// the included file is resolved relative to the including file (or --file-root for the main file)
+// return true if the run must be stopped (fail fast)
+fn run_include(context: &mut RunContext, file: &FileContext, include: Include) -> Result<bool, Error> {
+    let source_info = include.filename.source_info.clone();
+    let read_error = |value: String| Error {
+        source_info: source_info.clone(),
+        inner: RunnerError::FileReadAccess { value },
+        assert: false,
+    };
+    let path = Path::new(file.context_dir.as_str()).join(include.filename.value.as_str());
+    let filename = match path.to_str() {
+        Some(filename) => filename.to_string(),
+        None => return Err(read_error(include.filename.value)),
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return Err(read_error(filename)),
+    };
+    // the file may have been removed since it has been read
+    let canonical_path = match path.canonicalize() {
+        Ok(canonical_path) => canonical_path,
+        Err(_) => return Err(read_error(filename)),
+    };
+    if file.paths.contains(&canonical_path) {
+        return Err(Error {
+            source_info,
+            inner: RunnerError::IncludeCycle { value: include.filename.value },
+            assert: false,
+        });
+    }
+    let context_dir = match path.parent().and_then(|parent| parent.to_str()) {
+        Some(context_dir) => context_dir.to_string(),
+        None => return Err(read_error(filename)),
+    };
+
+    let mut lines: Vec<String> = regex::Regex::new(r"\n|\r\n")
+        .unwrap()
+        .split(&contents)
+        .map(|s| s.to_string())
+        .collect();
+    lines.push(String::from(""));
+    let mut includes = vec![(file.filename.clone(), source_info.start.line)];
+    includes.extend(file.includes.clone());
+    let mut paths = file.paths.clone();
+    paths.push(canonical_path);
+    let included_file = FileContext {
         filename,
-        entries,
+        lines,
+        context_dir,
+        includes,
+        paths,
     };
+
+    let mut parser = parser::core::Parser::init(contents.as_str());
+    return match parser::parser::hurl_file(&mut parser) {
+        Err(e) => {
+            output::eprintln(format_error(&e, 1, &included_file, context.output_color).format().as_str());
+            Err(Error {
+                source_info,
+                inner: RunnerError::IncludeParsing { value: include.filename.value },
+                assert: false,
+            })
+        }
+        Ok(hurl_file) => {
+            if context.verbose {
+                output::eprintln(format!("[DEBUG] Include {}", included_file.filename).as_str());
+            }
+            Ok(run_statements(context, &included_file, hurl_file.statements, None, None))
+        }
+    };
+}
+
+fn format_error(error: &dyn FormatError, exit_code: usize, file: &FileContext, color: bool) -> format::error::Error {
+    return format::error::Error {
+        exit_code,
+        source_info: error.source_info(),
+        description: error.description(),
+        fixme: error.fixme(),
+        lines: file.lines.clone(),
+        filename: file.filename.clone(),
+        warning: false,
+        color,
+        includes: file.includes.clone(),
+    };
+}
+
+// entries are numbered from 1, both bounds are inclusive
+fn is_in_range(entry_number: usize, from_entry: Option<usize>, to_entry: Option<usize>) -> bool {
+    if let Some(from_entry) = from_entry {
+        if entry_number < from_entry {
+            return false;
+        }
+    }
+    if let Some(to_entry) = to_entry {
+        if entry_number > to_entry {
+            return false;
+        }
+    }
+    return true;
+}
+
+#[test]
+fn test_is_in_range() {
+    assert_eq!(is_in_range(1, None, None), true);
+    assert_eq!(is_in_range(1, Some(2), None), false);
+    assert_eq!(is_in_range(2, Some(2), None), true);
+    assert_eq!(is_in_range(3, None, Some(2)), false);
+    assert_eq!(is_in_range(2, Some(2), Some(2)), true);
+    assert_eq!(is_in_range(3, Some(2), Some(4)), true);
 }
@@ -1,7 +1,13 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 
-#[cfg(test)]
-use crate::core::core::SourceInfo;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use rand::distributions::Uniform;
+use rand::Rng;
+
+use crate::core::core::{Pos, SourceInfo, Value};
+use crate::core::date::{check_format, Date};
+use crate::parser::json::json_expression;
 
 use super::core::{Error, RunnerError};
 use super::super::core::ast::*;
@@ -9,7 +15,7 @@ use super::super::core::ast::*;
 // region template
 
 impl HurlTemplate {
-    pub fn eval(self, variables: &HashMap<String, String>) -> Result<String, Error> {
+    pub fn eval(self, variables: &HashMap<String, Value>) -> Result<String, Error> {
         match self {
             HurlTemplate { elements, .. } => {
                 let mut value = String::from("");
@@ -39,6 +45,8 @@ fn test_template() {
                 value: Expr {
                     space0: space.clone(),
                     variable: Variable { name: String::from("base_url"), source_info: SourceInfo::init(1, 3, 1, 11) },
+                    arguments: vec![],
+                    filters: vec![],
                     space1: space.clone(),
                 }
             },
@@ -49,7 +57,7 @@ fn test_template() {
     };
 
     let mut variables = HashMap::new();
-    variables.insert(String::from("base_url"), String::from("http://localhost:8000"));
+    variables.insert(String::from("base_url"), Value::String(String::from("http://localhost:8000")));
     assert_eq!(template.clone().eval(&variables), Ok(String::from("http://localhost:8000/hello")));
 
     let variables = HashMap::new();
@@ -62,24 +70,130 @@ fn test_template() {
 
 // region template-element
 impl HurlTemplateElement {
-    pub fn eval(self, variables: &HashMap<String, String>) -> Result<String, Error> {
+    pub fn eval(self, variables: &HashMap<String, Value>) -> Result<String, Error> {
         return match self {
             HurlTemplateElement::Literal { value: HurlString2 { value, .. } } => { Ok(value) }
-            HurlTemplateElement::Expression { value: Expr { variable: Variable { name, source_info }, .. } } => {
-                return match variables.get(&name as &str) {
-                    Some(value) => Ok(value.to_string()),
-                    _ => Err(Error { source_info, inner: RunnerError::TemplateVariableNotDefined { name }, assert: false }),
+            HurlTemplateElement::Expression { value } => Ok(render(&value.eval(variables)?)),
+        };
+    }
+}
+
+
+// lists and objects are rendered as json
+fn render(value: &Value) -> String {
+    return match value {
+        Value::List(_) | Value::Object(_) => match value.to_json() {
+            Some(json) => json,
+            None => value.to_string(),
+        },
+        _ => value.to_string(),
+    };
+}
+
+// region json-template
+
+// json text (body or predicate value) starting at pos, with {{name}} expressions
+// within a json string, the value is inserted as (escaped) text
+// otherwise, it is inserted as a json value
+pub fn eval_json(json: String, pos: Pos, variables: &HashMap<String, Value>) -> Result<String, Error> {
+    let mut value = String::from("");
+    let mut line = pos.line;
+    let mut column = pos.column;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut i = 0;
+    while i < json.len() {
+        if !escaped {
+            if let Some((expr, len)) = json_expression(&json[i..]) {
+                let count = json[i..i + len].chars().count();
+                let expr_value = match expr.eval(variables) {
+                    Ok(expr_value) => expr_value,
+                    // the expression has been parsed alone (on one line)
+                    Err(Error { source_info, inner, assert }) => {
+                        let source_info = SourceInfo::init(line, column + source_info.start.column - 1, line, column + source_info.end.column - 1);
+                        return Err(Error { source_info, inner, assert });
+                    }
                 };
+                if in_string {
+                    let s = serde_json::Value::String(render(&expr_value)).to_string();
+                    value.push_str(&s[1..s.len() - 1]);
+                } else {
+                    match expr_value.to_json() {
+                        Some(json) => value.push_str(json.as_str()),
+                        None => {
+                            let source_info = SourceInfo::init(line, column, line, column + count);
+                            return Err(Error { source_info, inner: RunnerError::InvalidJsonValue(expr_value), assert: false });
+                        }
+                    }
+                }
+                column += count;
+                i += len;
+                continue;
             }
-        };
+        }
+        let c = json[i..].chars().next().unwrap();
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && in_string {
+            escaped = true;
+        } else if c == '"' {
+            in_string = !in_string;
+        }
+        if c == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+        value.push(c);
+        i += c.len_utf8();
     }
+    return Ok(value);
+}
+
+#[test]
+fn test_eval_json() {
+    let mut variables = HashMap::new();
+    variables.insert(String::from("ids"), Value::List(vec![Value::Integer(1), Value::Integer(2)]));
+    variables.insert(String::from("name"), Value::String(String::from("Bob \"The\" Builder")));
+    variables.insert(String::from("price"), Value::Float(1.5));
+    let pos = Pos { line: 2, column: 1 };
+
+    assert_eq!(
+        eval_json(String::from("{\"ids\": {{ids}}, \"price\": {{ price }}}"), pos.clone(), &variables).unwrap(),
+        String::from("{\"ids\": [1,2], \"price\": 1.5}")
+    );
+    assert_eq!(
+        eval_json(String::from("{\"name\": {{name}}, \"label\": \"{{name}} ({{ids}})\"}"), pos.clone(), &variables).unwrap(),
+        String::from("{\"name\": \"Bob \\\"The\\\" Builder\", \"label\": \"Bob \\\"The\\\" Builder ([1,2])\"}")
+    );
+    assert_eq!(
+        eval_json(String::from("{\"s\": \"\\\"{{price}}\"}"), pos.clone(), &variables).unwrap(),
+        String::from("{\"s\": \"\\\"1.5\"}")
+    );
+
+    let error = eval_json(String::from("{\n  \"id\": {{ id }}\n}"), pos.clone(), &variables).err().unwrap();
+    assert_eq!(error.source_info, SourceInfo::init(3, 12, 3, 14));
+    assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: String::from("id") });
+
+    variables.insert(String::from("hash"), Value::Bytes(vec![1, 171]));
+    assert_eq!(
+        eval_json(String::from("{\"h\": {{hash}}}"), pos.clone(), &variables).unwrap(),
+        String::from("{\"h\": \"01ab\"}")
+    );
+
+    variables.insert(String::from("nan"), Value::Float(f64::NAN));
+    let error = eval_json(String::from("{\"n\": {{nan}}}"), pos, &variables).err().unwrap();
+    assert_eq!(error.source_info, SourceInfo::init(2, 7, 2, 14));
+    assert_eq!(error.inner, RunnerError::InvalidJsonValue(Value::Float(f64::NAN)));
 }
 
+// endregion
 
 #[test]
 fn test_template_element() {
     let mut variables = HashMap::new();
-    variables.insert(String::from("base_url"), String::from("http://localhost:8000"));
+    variables.insert(String::from("base_url"), Value::String(String::from("http://localhost:8000")));
 
     let template_element = HurlTemplateElement::Literal { value: HurlString2 { value: String::from("http://localhost:8000/hello"), encoded: None } };
     assert_eq!(template_element.eval(&variables), Ok(String::from("http://localhost:8000/hello")));
@@ -92,10 +206,18 @@ fn test_template_element() {
         value: Expr {
             space0: space.clone(),
             variable: Variable { name: String::from("base_url"), source_info: SourceInfo::init(1, 1, 1, 10) },
+            arguments: vec![],
+            filters: vec![],
             space1: space.clone(),
         }
     };
-    assert_eq!(template_element.eval(&variables), Ok(String::from("http://localhost:8000")));
+    assert_eq!(template_element.clone().eval(&variables), Ok(String::from("http://localhost:8000")));
+
+    let mut variables = HashMap::new();
+    variables.insert(String::from("base_url"), Value::List(vec![Value::Integer(1), Value::Float(2.0)]));
+    assert_eq!(template_element.clone().eval(&variables), Ok(String::from("[1,2.0]")));
+    variables.insert(String::from("base_url"), Value::Float(0.1));
+    assert_eq!(template_element.eval(&variables), Ok(String::from("0.1")));
 }
 
 #[test]
@@ -109,6 +231,8 @@ fn test_template_element_error() {
         value: Expr {
             space0: space.clone(),
             variable: Variable { name: String::from("base_url"), source_info: SourceInfo::init(1, 1, 1, 10) },
+            arguments: vec![],
+            filters: vec![],
             space1: space.clone(),
         }
     };
@@ -116,4 +240,240 @@ fn test_template_element_error() {
     assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 10));
     assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: String::from("base_url") });
 }
-//endregion
\ No newline at end of file
+//endregion
+
+// region expr
+
+// a variable or a function call, followed by filters
+// a variable takes precedence over a function without argument
+impl Expr {
+    pub fn eval(self, variables: &HashMap<String, Value>) -> Result<Value, Error> {
+        let filters = self.filters.clone();
+        let mut value = self.eval_value(variables);
+        for filter in filters {
+            value = filter.eval(value);
+        }
+        return value;
+    }
+
+    fn eval_value(self, variables: &HashMap<String, Value>) -> Result<Value, Error> {
+        let Expr { variable: Variable { name, source_info }, arguments, .. } = self;
+        if arguments.is_empty() {
+            if let Some(value) = variables.get(&name) {
+                return Ok(value.clone());
+            }
+        }
+        let value = match (name.as_str(), arguments.as_slice()) {
+            ("newUuid", []) => Value::String(uuid::Uuid::new_v4().to_string()),
+            ("now", []) => Value::String(Date::now().to_string()),
+            ("now", [ExprArgument { value: ExprArgumentValue::String(format), .. }]) => {
+                if let Err(message) = check_format(format.value.as_str()) {
+                    return Err(Error { source_info: format.source_info.clone(), inner: RunnerError::InvalidDateFormat(message), assert: false });
+                }
+                Value::String(Date::now().format(format.value.as_str()))
+            }
+            ("randomInt", [ExprArgument { value: ExprArgumentValue::Integer { value: min, .. }, .. },
+                           ExprArgument { value: ExprArgumentValue::Integer { value: max, .. }, .. }]) if min <= max => {
+                Value::Integer(rand::thread_rng().sample(Uniform::new_inclusive(*min, *max)))
+            }
+            // the value is read but not recorded, it may be a secret
+            ("env", [ExprArgument { value: ExprArgumentValue::String(env_name), .. }]) => {
+                return match std::env::var(env_name.value.as_str()) {
+                    Ok(value) => Ok(Value::String(value)),
+                    Err(_) => Err(Error { source_info: env_name.source_info.clone(), inner: RunnerError::EnvVariableNotDefined { name: env_name.value.clone() }, assert: false }),
+                };
+            }
+            ("newUuid", _) | ("now", _) | ("randomInt", _) | ("env", _) => {
+                let expected = match name.as_str() {
+                    "newUuid" => "no argument",
+                    "now" => "an optional date format",
+                    "randomInt" => "two integers min and max (min <= max)",
+                    _ => "the name of an environment variable",
+                };
+                return Err(Error { source_info, inner: RunnerError::TemplateFunctionArguments { name, expected: expected.to_string() }, assert: false });
+            }
+            _ => {
+                let inner = if arguments.is_empty() {
+                    RunnerError::TemplateVariableNotDefined { name }
+                } else {
+                    RunnerError::TemplateFunctionNotDefined { name }
+                };
+                return Err(Error { source_info, inner, assert: false });
+            }
+        };
+        record_generated_value(function_call(name, arguments), value.clone());
+        return Ok(value);
+    }
+}
+
+// unreserved characters (rfc 3986) are not encoded
+const URL_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+// the filter is applied to the value (or the error) of the previous stage
+// default replaces an undefined variable, the other filters return a string
+impl ExprFilter {
+    pub fn eval(self, value: Result<Value, Error>) -> Result<Value, Error> {
+        return match (self.value, value) {
+            (ExprFilterValue::Default { value: default, .. }, Err(Error { inner: RunnerError::TemplateVariableNotDefined { .. }, .. }))
+            | (ExprFilterValue::Default { value: default, .. }, Err(Error { inner: RunnerError::EnvVariableNotDefined { .. }, .. }))
+            | (ExprFilterValue::Default { value: default, .. }, Ok(Value::None)) => match default {
+                ExprArgumentValue::String(HurlString { value, .. }) => Ok(Value::String(value)),
+                ExprArgumentValue::Integer { value, .. } => Ok(Value::Integer(value)),
+            },
+            (_, Err(e)) => Err(e),
+            (ExprFilterValue::Default { .. }, Ok(value)) => Ok(value),
+            (ExprFilterValue::UrlEncode {}, Ok(value)) => Ok(Value::String(utf8_percent_encode(render(&value).as_str(), URL_ENCODE_SET).to_string())),
+            (ExprFilterValue::Base64Encode {}, Ok(Value::Bytes(bytes))) => Ok(Value::String(base64::encode(&bytes))),
+            (ExprFilterValue::Base64Encode {}, Ok(value)) => Ok(Value::String(base64::encode(render(&value).as_bytes()))),
+            (ExprFilterValue::Upper {}, Ok(value)) => Ok(Value::String(render(&value).to_uppercase())),
+            (ExprFilterValue::JsonEncode {}, Ok(value)) => match value.to_json() {
+                Some(json) => Ok(Value::String(json)),
+                None => Err(Error { source_info: self.source_info, inner: RunnerError::FilterInvalidInput(value), assert: false }),
+            },
+        };
+    }
+}
+
+#[test]
+fn test_expr_filter() {
+    let whitespace = Whitespace { value: String::from(""), source_info: SourceInfo::init(0, 0, 0, 0) };
+    let filter = |value: ExprFilterValue| ExprFilter {
+        space0: whitespace.clone(),
+        space1: whitespace.clone(),
+        source_info: SourceInfo::init(0, 0, 0, 0),
+        value,
+    };
+    let default = filter(ExprFilterValue::Default {
+        space0: whitespace.clone(),
+        space1: whitespace.clone(),
+        value: ExprArgumentValue::Integer { value: 8000, source_info: SourceInfo::init(0, 0, 0, 0) },
+    });
+    let undefined = Error { source_info: SourceInfo::init(1, 3, 1, 7), inner: RunnerError::TemplateVariableNotDefined { name: String::from("port") }, assert: false };
+
+    assert_eq!(default.clone().eval(Err(undefined.clone())).unwrap(), Value::Integer(8000));
+    assert_eq!(default.clone().eval(Ok(Value::None)).unwrap(), Value::Integer(8000));
+    assert_eq!(default.eval(Ok(Value::Integer(80))).unwrap(), Value::Integer(80));
+    assert_eq!(filter(ExprFilterValue::Upper {}).eval(Err(undefined.clone())).err().unwrap(), undefined);
+
+    assert_eq!(
+        filter(ExprFilterValue::UrlEncode {}).eval(Ok(Value::String(String::from("a b&c=d/é~")))).unwrap(),
+        Value::String(String::from("a%20b%26c%3Dd%2F%C3%A9~"))
+    );
+    assert_eq!(filter(ExprFilterValue::Base64Encode {}).eval(Ok(Value::String(String::from("bob:secret")))).unwrap(), Value::String(String::from("Ym9iOnNlY3JldA==")));
+    assert_eq!(filter(ExprFilterValue::Base64Encode {}).eval(Ok(Value::Bytes(vec![255, 0]))).unwrap(), Value::String(String::from("/wA=")));
+    assert_eq!(filter(ExprFilterValue::Upper {}).eval(Ok(Value::String(String::from("café")))).unwrap(), Value::String(String::from("CAFÉ")));
+    assert_eq!(
+        filter(ExprFilterValue::JsonEncode {}).eval(Ok(Value::String(String::from("Bob \"The\" Builder")))).unwrap(),
+        Value::String(String::from("\"Bob \\\"The\\\" Builder\""))
+    );
+    assert_eq!(filter(ExprFilterValue::JsonEncode {}).eval(Ok(Value::List(vec![Value::Integer(1)]))).unwrap(), Value::String(String::from("[1]")));
+}
+
+// function call as written in the hurl file, such as randomInt 1 100
+fn function_call(name: String, arguments: Vec<ExprArgument>) -> String {
+    let mut s = name;
+    for argument in arguments {
+        match argument.value {
+            ExprArgumentValue::String(HurlString { value, encoded, .. }) => s.push_str(format!(" \"{}\"", encoded.unwrap_or(value)).as_str()),
+            ExprArgumentValue::Integer { value, .. } => s.push_str(format!(" {}", value).as_str()),
+        }
+    }
+    return s;
+}
+
+// values generated by template functions
+// recorded for the current entry (and reported to be able to reproduce a run)
+thread_local! {
+    static GENERATED_VALUES: RefCell<Vec<(String, Value)>> = RefCell::new(vec![]);
+}
+
+fn record_generated_value(call: String, value: Value) {
+    GENERATED_VALUES.with(|values| values.borrow_mut().push((call, value)));
+}
+
+// returns the values generated since the last call
+pub fn take_generated_values() -> Vec<(String, Value)> {
+    return GENERATED_VALUES.with(|values| values.replace(vec![]));
+}
+
+#[test]
+fn test_expr_function() {
+    let whitespace = Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) };
+    let expr = |name: &str, arguments: Vec<ExprArgumentValue>| Expr {
+        space0: whitespace.clone(),
+        variable: Variable { name: name.to_string(), source_info: SourceInfo::init(1, 3, 1, 3 + name.len()) },
+        arguments: arguments.into_iter().map(|value| ExprArgument { space0: whitespace.clone(), value }).collect(),
+        filters: vec![],
+        space1: whitespace.clone(),
+    };
+    let string = |value: &str| ExprArgumentValue::String(HurlString { value: value.to_string(), encoded: None, source_info: SourceInfo::init(1, 10, 1, 20) });
+    let integer = |value: i64| ExprArgumentValue::Integer { value, source_info: SourceInfo::init(1, 10, 1, 20) };
+    let variables = HashMap::new();
+    take_generated_values();
+
+    let uuid = match expr("newUuid", vec![]).eval(&variables).unwrap() {
+        Value::String(s) => s,
+        v => panic!("unexpected value {:?}", v),
+    };
+    assert_eq!(uuid.len(), 36);
+    assert_eq!(&uuid[14..15], "4");
+    assert_eq!(expr("randomInt", vec![integer(3), integer(3)]).eval(&variables).unwrap(), Value::Integer(3));
+    match expr("randomInt", vec![integer(-1), integer(1)]).eval(&variables).unwrap() {
+        Value::Integer(i) => assert!(i >= -1 && i <= 1),
+        v => panic!("unexpected value {:?}", v),
+    }
+    let year = expr("now", vec![string("%Y")]).eval(&variables).unwrap();
+    assert_eq!(year, Value::String(Date::now().format("%Y")));
+    std::env::set_var("HURL_TEST_TEMPLATE_ENV", "hello");
+    assert_eq!(expr("env", vec![string("HURL_TEST_TEMPLATE_ENV")]).eval(&variables).unwrap(), Value::String(String::from("hello")));
+
+    // env values are not recorded
+    let generated = take_generated_values();
+    assert_eq!(generated.len(), 4);
+    assert_eq!(generated[0], (String::from("newUuid"), Value::String(uuid)));
+    assert_eq!(generated[1], (String::from("randomInt 3 3"), Value::Integer(3)));
+    assert_eq!(generated[3], (String::from("now \"%Y\""), year));
+    assert_eq!(take_generated_values(), vec![]);
+
+    // variables take precedence
+    let mut variables = HashMap::new();
+    variables.insert(String::from("now"), Value::String(String::from("yesterday")));
+    assert_eq!(expr("now", vec![]).eval(&variables).unwrap(), Value::String(String::from("yesterday")));
+}
+
+#[test]
+fn test_expr_function_error() {
+    let whitespace = Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) };
+    let expr = |name: &str, arguments: Vec<ExprArgumentValue>| Expr {
+        space0: whitespace.clone(),
+        variable: Variable { name: name.to_string(), source_info: SourceInfo::init(1, 3, 1, 3 + name.len()) },
+        arguments: arguments.into_iter().map(|value| ExprArgument { space0: whitespace.clone(), value }).collect(),
+        filters: vec![],
+        space1: whitespace.clone(),
+    };
+    let string = |value: &str| ExprArgumentValue::String(HurlString { value: value.to_string(), encoded: None, source_info: SourceInfo::init(1, 10, 1, 20) });
+    let integer = |value: i64| ExprArgumentValue::Integer { value, source_info: SourceInfo::init(1, 10, 1, 20) };
+    let variables = HashMap::new();
+
+    let error = expr("randomInt", vec![integer(2), integer(1)]).eval(&variables).err().unwrap();
+    assert_eq!(error.source_info, SourceInfo::init(1, 3, 1, 12));
+    assert_eq!(error.inner, RunnerError::TemplateFunctionArguments { name: String::from("randomInt"), expected: String::from("two integers min and max (min <= max)") });
+
+    let error = expr("newUuid", vec![integer(1)]).eval(&variables).err().unwrap();
+    assert_eq!(error.inner, RunnerError::TemplateFunctionArguments { name: String::from("newUuid"), expected: String::from("no argument") });
+
+    let error = expr("now", vec![string("%Q")]).eval(&variables).err().unwrap();
+    assert_eq!(error.source_info, SourceInfo::init(1, 10, 1, 20));
+    assert_eq!(error.inner, RunnerError::InvalidDateFormat(String::from("unknown directive %Q")));
+
+    let error = expr("env", vec![string("HURL_TEST_UNDEFINED")]).eval(&variables).err().unwrap();
+    assert_eq!(error.inner, RunnerError::EnvVariableNotDefined { name: String::from("HURL_TEST_UNDEFINED") });
+
+    let error = expr("uuid", vec![integer(1)]).eval(&variables).err().unwrap();
+    assert_eq!(error.inner, RunnerError::TemplateFunctionNotDefined { name: String::from("uuid") });
+
+    let error = expr("uuid", vec![]).eval(&variables).err().unwrap();
+    assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: String::from("uuid") });
+}
+
+// endregion
@@ -47,7 +47,10 @@ impl Textable for Response {
 
         // shoudl use number of char, not a number of bytes!!
         let limit_body = 200; // TODO should be explicitly pass as a command-line argument
-        let body = body_text(self.clone().body, get_header_value(self.clone().headers, "content-type"));
+        let body = match (self.encoding(), self.text()) {
+            (Some(_), Some(text)) => text,
+            _ => body_text(self.clone().body, get_header_value(self.clone().headers, "content-type")),
+        };
 //        let body = if body.len() < limit_body - 1 {
 //            body
 //        } else {
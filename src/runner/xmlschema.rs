@@ -0,0 +1,189 @@
+// validation of an xml document with a XSD schema or a DTD
+// errors are collected from libxml (with their line numbers)
+extern crate libxml;
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+
+use libxml::bindings;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XmlSchemaError {
+    InvalidXml(Vec<String>),
+    InvalidSchema(Vec<String>),
+}
+
+// list of validation errors
+pub fn validate_xsd(xml: String, xsd: String) -> Result<Vec<String>, XmlSchemaError> {
+    // the errors are only accessed through the raw pointer while libxml may write to them
+    let errors = Box::into_raw(Box::new(Vec::<String>::new()));
+    let context = errors as *mut c_void;
+    set_error_handler(context);
+
+    let parser_context = unsafe { bindings::xmlSchemaNewMemParserCtxt(xsd.as_ptr() as *const c_char, xsd.len() as c_int) };
+    unsafe { bindings::xmlSchemaSetParserStructuredErrors(parser_context, error_handler(), context) };
+    let schema = unsafe { bindings::xmlSchemaParse(parser_context) };
+    unsafe { bindings::xmlSchemaFreeParserCtxt(parser_context) };
+    if schema.is_null() {
+        return Err(XmlSchemaError::InvalidSchema(take_errors(errors)));
+    }
+
+    let doc = match read_xml(xml, errors) {
+        Some(doc) => doc,
+        None => {
+            unsafe { bindings::xmlSchemaFree(schema) };
+            return Err(XmlSchemaError::InvalidXml(take_errors(errors)));
+        }
+    };
+
+    let valid_context = unsafe { bindings::xmlSchemaNewValidCtxt(schema) };
+    unsafe { bindings::xmlSchemaSetValidStructuredErrors(valid_context, error_handler(), context) };
+    unsafe { bindings::xmlSchemaValidateDoc(valid_context, doc) };
+    unsafe {
+        bindings::xmlSchemaFreeValidCtxt(valid_context);
+        bindings::xmlSchemaFree(schema);
+        bindings::xmlFreeDoc(doc);
+    }
+    return Ok(take_errors(errors));
+}
+
+// list of validation errors
+pub fn validate_dtd(xml: String, dtd: String) -> Result<Vec<String>, XmlSchemaError> {
+    let errors = Box::into_raw(Box::new(Vec::<String>::new()));
+    set_error_handler(errors as *mut c_void);
+
+    // the input buffer is freed by the dtd parser
+    let input = unsafe { bindings::xmlParserInputBufferCreateMem(dtd.as_ptr() as *const c_char, dtd.len() as c_int, bindings::xmlCharEncoding_XML_CHAR_ENCODING_NONE) };
+    let dtd = unsafe { bindings::xmlIOParseDTD(std::ptr::null_mut(), input, bindings::xmlCharEncoding_XML_CHAR_ENCODING_NONE) };
+    if dtd.is_null() {
+        return Err(XmlSchemaError::InvalidSchema(take_errors(errors)));
+    }
+
+    let doc = match read_xml(xml, errors) {
+        Some(doc) => doc,
+        None => {
+            unsafe { bindings::xmlFreeDtd(dtd) };
+            return Err(XmlSchemaError::InvalidXml(take_errors(errors)));
+        }
+    };
+
+    unsafe {
+        let valid_context = bindings::xmlNewValidCtxt();
+        bindings::xmlValidateDtd(valid_context, doc, dtd);
+        bindings::xmlFreeValidCtxt(valid_context);
+        bindings::xmlFreeDtd(dtd);
+        bindings::xmlFreeDoc(doc);
+    }
+    return Ok(take_errors(errors));
+}
+
+// the error handler must have been set with the errors as context
+fn read_xml(xml: String, errors: *const Vec<String>) -> Option<bindings::xmlDocPtr> {
+    // the xml is already decoded, its declared encoding is ignored
+    let encoding = b"UTF-8\0";
+    let doc = unsafe { bindings::xmlReadMemory(xml.as_ptr() as *const c_char, xml.len() as c_int, std::ptr::null(), encoding.as_ptr() as *const c_char, 0) };
+    if doc.is_null() {
+        return None;
+    }
+    if unsafe { !(*errors).is_empty() } {
+        unsafe { bindings::xmlFreeDoc(doc) };
+        return None;
+    }
+    return Some(doc);
+}
+
+// reset the error handler and give back the collected errors
+fn take_errors(errors: *mut Vec<String>) -> Vec<String> {
+    reset_error_handler();
+    return unsafe { *Box::from_raw(errors) };
+}
+
+// libxml errors (parsing and validation) are pushed to the vector given as context
+// instead of being printed to the standard error
+fn set_error_handler(context: *mut c_void) {
+    unsafe { bindings::xmlSetStructuredErrorFunc(context, error_handler()) };
+}
+
+fn reset_error_handler() {
+    unsafe { bindings::xmlSetStructuredErrorFunc(std::ptr::null_mut(), None) };
+}
+
+// the generated binding declares the handler without the C calling convention
+fn error_handler() -> bindings::xmlStructuredErrorFunc {
+    let handler: unsafe extern "C" fn(*mut c_void, bindings::xmlErrorPtr) = collect_error;
+    return Some(unsafe {
+        std::mem::transmute::<unsafe extern "C" fn(*mut c_void, bindings::xmlErrorPtr), unsafe fn(*mut c_void, bindings::xmlErrorPtr)>(handler)
+    });
+}
+
+unsafe extern "C" fn collect_error(context: *mut c_void, error: bindings::xmlErrorPtr) {
+    if context.is_null() || error.is_null() {
+        return;
+    }
+    let errors = &mut *(context as *mut Vec<String>);
+    let message = if (*error).message.is_null() {
+        String::from("unknown error")
+    } else {
+        CStr::from_ptr((*error).message).to_string_lossy().trim().to_string()
+    };
+    errors.push(format!("line {}: {}", (*error).line, message));
+}
+
+
+#[cfg(test)]
+const XSD: &str = r#"<?xml version="1.0"?>
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+  <xs:element name="food">
+    <xs:complexType>
+      <xs:sequence>
+        <xs:element name="fruit" maxOccurs="unbounded">
+          <xs:complexType>
+            <xs:attribute name="price" type="xs:decimal" use="required"/>
+          </xs:complexType>
+        </xs:element>
+      </xs:sequence>
+    </xs:complexType>
+  </xs:element>
+</xs:schema>"#;
+
+#[test]
+fn test_validate_xsd() {
+    let xml = String::from("<food>\n<fruit price=\"1.1\"/>\n<fruit price=\"2\"/>\n</food>");
+    assert_eq!(validate_xsd(xml, String::from(XSD)).unwrap(), Vec::<String>::new());
+
+    let xml = String::from("<food>\n<fruit price=\"abc\"/>\n<fruit/>\n<vegetable/>\n</food>");
+    assert_eq!(validate_xsd(xml, String::from(XSD)).unwrap(), vec![
+        "line 2: Element 'fruit', attribute 'price': 'abc' is not a valid value of the atomic type 'xs:decimal'.",
+        "line 3: Element 'fruit': The attribute 'price' is required but missing.",
+        "line 4: Element 'vegetable': This element is not expected. Expected is ( fruit ).",
+    ]);
+}
+
+#[test]
+fn test_validate_xsd_error() {
+    match validate_xsd(String::from("<food>"), String::from(XSD)).err().unwrap() {
+        XmlSchemaError::InvalidXml(errors) => assert_eq!(errors.is_empty(), false),
+        _ => panic!(),
+    }
+    match validate_xsd(String::from("<food/>"), String::from("<xs:schema/>")).err().unwrap() {
+        XmlSchemaError::InvalidSchema(errors) => assert_eq!(errors.is_empty(), false),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_validate_dtd() {
+    let dtd = String::from("<!ELEMENT note (to,body)>\n<!ELEMENT to (#PCDATA)>\n<!ELEMENT body (#PCDATA)>");
+    let xml = String::from("<note><to>Bob</to><body>Hello</body></note>");
+    assert_eq!(validate_dtd(xml, dtd.clone()).unwrap(), Vec::<String>::new());
+
+    let xml = String::from("<note>\n<to>Bob</to>\n</note>");
+    assert_eq!(validate_dtd(xml, dtd.clone()).unwrap(), vec![
+        "line 1: Element note content does not follow the DTD, expecting (to , body), got (to )",
+    ]);
+
+    match validate_dtd(String::from("<note/>"), String::from("<!ELEMENT note")).err().unwrap() {
+        XmlSchemaError::InvalidSchema(errors) => assert_eq!(errors.is_empty(), false),
+        _ => panic!(),
+    }
+}
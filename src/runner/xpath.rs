@@ -1,7 +1,8 @@
 // unique entry point to libxml
 extern crate libxml;
 
-use std::ffi::CStr;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 
 use super::super::core::core::Value;
 
@@ -13,35 +14,112 @@ pub enum XpathError {
     Unsupported,
 }
 
-pub fn eval_xml(xml: String, expr: String) -> Result<Value, XpathError> {
-    let parser = libxml::parser::Parser::default();
-    return match parser.parse_string(xml) {
+// documents are given as (decoded) rust strings
+// the encoding declared in the xml prolog or html meta tag must be ignored
+pub fn eval_xml(xml: String, expr: String, namespaces: &HashMap<String, String>) -> Result<Value, XpathError> {
+    return match parse_xml(xml) {
         Ok(doc) => if doc.get_root_element() == None {
             Err(XpathError::InvalidXML {})
         } else {
-            eval(doc, expr)
+            eval(doc, expr, namespaces)
         },
         Err(_) => Err(XpathError::InvalidXML {})
     };
 }
 
-pub fn eval_html(html: String, expr: String) -> Result<Value, XpathError> {
-    let parser = libxml::parser::Parser::default_html();
-    return match parser.parse_string(html) {
+pub fn eval_html(html: String, expr: String, namespaces: &HashMap<String, String>) -> Result<Value, XpathError> {
+    return match parse_html(html) {
         Ok(doc) => {
             // You can have a doc structure even if the input xml is not valid
             // check that the root element exists
             if doc.get_root_element() == None {
                 Err(XpathError::InvalidHtml {})
             } else {
-                eval(doc, expr)
+                eval(doc, expr, namespaces)
             }
         }
         Err(_) => Err(XpathError::InvalidHtml {})
     };
 }
 
-pub fn eval(doc: libxml::tree::Document, expr: String) -> Result<Value, XpathError> {
+// libxml must be initialized once before being used from several threads
+pub fn init() {
+    unsafe { libxml::bindings::xmlInitParser() };
+}
+
+// libxml::parser::Parser::parse_string_with_encoding frees the encoding name before using it
+fn parse_xml(xml: String) -> Result<libxml::tree::Document, ()> {
+    let options = libxml::bindings::xmlParserOption_XML_PARSE_RECOVER
+        + libxml::bindings::xmlParserOption_XML_PARSE_NOERROR
+        + libxml::bindings::xmlParserOption_XML_PARSE_NOWARNING;
+    let encoding = CString::new("UTF-8").unwrap();
+    let doc_ptr = unsafe {
+        libxml::bindings::xmlReadMemory(
+            xml.as_ptr() as *const std::os::raw::c_char,
+            xml.len() as std::os::raw::c_int,
+            std::ptr::null(),
+            encoding.as_ptr(),
+            options as std::os::raw::c_int,
+        )
+    };
+    if doc_ptr.is_null() {
+        return Err(());
+    }
+    return Ok(libxml::tree::Document::new_ptr(doc_ptr));
+}
+
+// the libxml html parser switches to the meta charset even if an encoding is given
+// HTML_PARSE_IGNORE_ENC is not exposed by libxml::parser::Parser
+fn parse_html(html: String) -> Result<libxml::tree::Document, ()> {
+    let options = libxml::bindings::htmlParserOption_HTML_PARSE_RECOVER
+        + libxml::bindings::htmlParserOption_HTML_PARSE_NOERROR
+        + libxml::bindings::htmlParserOption_HTML_PARSE_NOWARNING
+        + libxml::bindings::htmlParserOption_HTML_PARSE_IGNORE_ENC;
+    let url = CString::new("").unwrap();
+    let encoding = CString::new("UTF-8").unwrap();
+    let doc_ptr = unsafe {
+        libxml::bindings::htmlReadMemory(
+            html.as_ptr() as *const std::os::raw::c_char,
+            html.len() as std::os::raw::c_int,
+            url.as_ptr(),
+            encoding.as_ptr(),
+            options as std::os::raw::c_int,
+        )
+    };
+    if doc_ptr.is_null() {
+        return Err(());
+    }
+    return Ok(libxml::tree::Document::new_ptr(doc_ptr));
+}
+
+// return the text content of the nodes selected by the xpath expression
+// (the value for attribute nodes)
+pub fn eval_html_nodes(html: String, expr: String) -> Result<Vec<String>, XpathError> {
+    let doc = match parse_html(html) {
+        Ok(doc) => doc,
+        Err(_) => return Err(XpathError::InvalidHtml {})
+    };
+    if doc.get_root_element() == None {
+        return Err(XpathError::InvalidHtml {});
+    }
+    let context = match libxml::xpath::Context::new(&doc) {
+        Ok(context) => context,
+        Err(_) => panic!("error setting context in xpath module")
+    };
+    unsafe {
+        libxml::bindings::initGenericErrorDefaultFunc(&mut None);
+    }
+    let result = match context.evaluate(expr.as_str()) {
+        Ok(object) => object,
+        Err(_) => return Err(XpathError::Eval {})
+    };
+    if unsafe { *result.ptr }.type_ != libxml::bindings::xmlXPathObjectType_XPATH_NODESET {
+        return Err(XpathError::Unsupported {});
+    }
+    return Ok(result.get_nodes_as_vec().iter().map(|node| node.get_content().trim().to_string()).collect());
+}
+
+pub fn eval(doc: libxml::tree::Document, expr: String, namespaces: &HashMap<String, String>) -> Result<Value, XpathError> {
     //let parser = libxml::parser::Parser::default();
 //    let doc = match parser.parse_string(xml) {
 //        Ok(doc) => doc,
@@ -53,6 +131,26 @@ pub fn eval(doc: libxml::tree::Document, expr: String) -> Result<Value, XpathErr
         Ok(context) => context,
         Err(_) => panic!("error setting context in xpath module")
     };
+
+    // namespaces declared on the root element are registered first
+    // so that they can be overridden by the user-defined ones
+    let mut all_namespaces = HashMap::new();
+    if let Some(root) = doc.get_root_element() {
+        for namespace in root.get_namespace_declarations() {
+            let prefix = namespace.get_prefix();
+            if !prefix.is_empty() {
+                all_namespaces.insert(prefix, namespace.get_href());
+            }
+        }
+    }
+    for (prefix, href) in namespaces {
+        all_namespaces.insert(prefix.clone(), href.clone());
+    }
+    for (prefix, href) in all_namespaces {
+        if context.register_namespace(prefix.as_str(), href.as_str()).is_err() {
+            return Err(XpathError::Eval {});
+        }
+    }
     unsafe {
         libxml::bindings::initGenericErrorDefaultFunc(&mut None);
     }
@@ -64,7 +162,7 @@ pub fn eval(doc: libxml::tree::Document, expr: String) -> Result<Value, XpathErr
     };
     // eprintln!("result> {}", result);
     return match unsafe { *result.ptr }.type_ {
-        libxml::bindings::xmlXPathObjectType_XPATH_NUMBER => Ok(Value::from_f64(unsafe { *result.ptr }.floatval)),
+        libxml::bindings::xmlXPathObjectType_XPATH_NUMBER => Ok(Value::Float(unsafe { *result.ptr }.floatval)),
         libxml::bindings::xmlXPathObjectType_XPATH_BOOLEAN =>
             Ok(Value::Bool(unsafe { *result.ptr }.boolval != 0)),
         libxml::bindings::xmlXPathObjectType_XPATH_STRING => {
@@ -94,23 +192,23 @@ fn test_xml() {
 </food>
 "#);
     let xpath = String::from("count(//food/*)");
-    assert_eq!(eval_xml(xml.clone(), xpath).unwrap(), Value::from_f64(3.0));
+    assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::Float(3.0));
 
     let xpath = String::from("//food/*");
-    assert_eq!(eval_xml(xml.clone(), xpath).unwrap(), Value::Nodeset(3));
+    assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::Nodeset(3));
 
     let xpath = String::from("count(//*[@type='fruit'])");
-    assert_eq!(eval_xml(xml.clone(), xpath).unwrap(), Value::from_f64(2.0));
+    assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::Float(2.0));
 
     let xpath = String::from("number(//food/banana/@price)");
-    assert_eq!(eval_xml(xml.clone(), xpath).unwrap(), Value::from_f64(1.1));
+    assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::Float(1.1));
 }
 
 
 #[test]
 fn test_error_eval() {
-    assert_eq!(eval_xml(String::from("<a/>"), String::from("^^^")).err().unwrap(), XpathError::Eval {});
-    assert_eq!(eval_xml(String::from("<a/>"), String::from("//")).err().unwrap(), XpathError::Eval {});
+    assert_eq!(eval_xml(String::from("<a/>"), String::from("^^^"), &HashMap::new()).err().unwrap(), XpathError::Eval {});
+    assert_eq!(eval_xml(String::from("<a/>"), String::from("//"), &HashMap::new()).err().unwrap(), XpathError::Eval {});
     // assert_eq!(1,2);
 }
 
@@ -119,14 +217,14 @@ fn test_error_eval() {
 // Invalid XML not detected at parsing??? => goes into an eval error
 #[test]
 fn test_invalid_xml() {
-    assert_eq!(eval_xml(String::from("??"), String::from("//person")).err().unwrap(), XpathError::InvalidXML);
+    assert_eq!(eval_xml(String::from("??"), String::from("//person"), &HashMap::new()).err().unwrap(), XpathError::InvalidXML);
 }
 
 #[test]
 fn test_cafe() {
     //println!("{:?}", eval( String::from("<data>café</data>"), String::from("normalize-space(//data)")));
     assert_eq!(eval_xml(
-        String::from("<data>café</data>"), String::from("normalize-space(//data)")).unwrap(),
+        String::from("<data>café</data>"), String::from("normalize-space(//data)"), &HashMap::new()).unwrap(),
                Value::String(String::from("café"))
     );
 }
@@ -143,7 +241,7 @@ fn test_html() {
   </body>
 </html>"#);
     let xpath = String::from("normalize-space(/html/head/meta/@charset)");
-    assert_eq!(eval_html(html.clone(), xpath).unwrap(), Value::String(String::from("UTF-8")));
+    assert_eq!(eval_html(html.clone(), xpath, &HashMap::new()).unwrap(), Value::String(String::from("UTF-8")));
 }
 
 
@@ -152,6 +250,57 @@ fn test_bug() {
     let html = String::from(r#"<html></html>"#);
     //let xpath = String::from("boolean(count(//a[contains(@href,'xxx')]))");
     let xpath = String::from("boolean(count(//a[contains(@href,'xxx')]))");
-    assert_eq!(eval_html(html.clone(), xpath).unwrap(), Value::Bool(false));
+    assert_eq!(eval_html(html.clone(), xpath, &HashMap::new()).unwrap(), Value::Bool(false));
 }
 
+
+
+#[test]
+fn test_namespaces() {
+    let xml = String::from(r#"<?xml version="1.0" encoding="utf-8"?>
+<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope">
+  <soap:Body>
+    <m:GetStockPriceResponse xmlns:m="https://www.example.org/stock">
+      <m:Price>34.5</m:Price>
+    </m:GetStockPriceResponse>
+  </soap:Body>
+</soap:Envelope>
+"#);
+
+    // root namespaces are registered automatically
+    let xpath = String::from("count(//soap:Body)");
+    assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::Float(1.0));
+
+    // other namespaces must be declared
+    let xpath = String::from("string(//m:Price)");
+    assert_eq!(eval_xml(xml.clone(), xpath.clone(), &HashMap::new()).err().unwrap(), XpathError::Eval {});
+
+    let mut namespaces = HashMap::new();
+    namespaces.insert(String::from("m"), String::from("https://www.example.org/stock"));
+    assert_eq!(eval_xml(xml.clone(), xpath, &namespaces).unwrap(), Value::String(String::from("34.5")));
+
+    // user-defined prefixes do not need to match the document ones
+    let mut namespaces = HashMap::new();
+    namespaces.insert(String::from("s"), String::from("http://www.w3.org/2003/05/soap-envelope"));
+    let xpath = String::from("count(/s:Envelope/s:Body)");
+    assert_eq!(eval_xml(xml.clone(), xpath, &namespaces).unwrap(), Value::Float(1.0));
+}
+
+
+#[test]
+fn test_html_nodes() {
+    let html = String::from(r#"<html>
+  <body>
+    <ul>
+      <li class="item">Apple</li>
+      <li class="item">
+        <a href="/banana">Banana</a>
+      </li>
+    </ul>
+  </body>
+</html>"#);
+    assert_eq!(eval_html_nodes(html.clone(), String::from("//li")).unwrap(), vec![String::from("Apple"), String::from("Banana")]);
+    assert_eq!(eval_html_nodes(html.clone(), String::from("//a/@href")).unwrap(), vec![String::from("/banana")]);
+    assert_eq!(eval_html_nodes(html.clone(), String::from("//p")).unwrap(), Vec::<String>::new());
+    assert_eq!(eval_html_nodes(html.clone(), String::from("count(//li)")).err().unwrap(), XpathError::Unsupported {});
+}
@@ -4,7 +4,7 @@ use hurl::http;
 
 
 fn default_client_options() -> http::client::ClientOptions {
-    return http::client::ClientOptions { noproxy_hosts: vec![], insecure: true };
+    return http::client::ClientOptions { noproxy_hosts: vec![], insecure: true, follow_location: false };
 }
 
 #[test]
@@ -18,6 +18,7 @@ fn test_hello() {
             host: "localhost".to_string(),
             port: Some(8000),
             path: "/hello".to_string(),
+            querystring: None,
             //   querystring: None
         }, //"http://localhost:8000/hello".to_string(),
         //querystring_params: vec![],
@@ -69,6 +70,7 @@ fn hello_request() -> http::request::Request {
             host: "localhost".to_string(),
             port: Some(8000),
             path: "/hello".to_string(),
+            querystring: None,
             //   querystring: None
         }, //"http://localhost:8000/hello".to_string(),
         querystring: vec![],
@@ -110,6 +112,7 @@ fn test_send_cookie() {
             host: "localhost".to_string(),
             port: Some(8000),
             path: "/cookies/set-request-cookie1-valueA".to_string(),
+            querystring: None,
             //      querystring: None
         }, //"http://localhost:8000/send-cookie".to_string(),
         querystring: vec![],
@@ -154,6 +157,7 @@ fn test_redirect() {
             host: "localhost".to_string(),
             port: Some(8000),
             path: "/redirect".to_string(),
+            querystring: None,
             //   querystring: None
         }, // "http://localhost:8000/redirect".to_string(),
         querystring: vec![],
@@ -180,6 +184,7 @@ fn test_querystring_param() {
             host: "localhost".to_string(),
             port: Some(8000),
             path: "/querystring-params".to_string(),
+            querystring: None,
             //  querystring: Some(String::from("param1=value1&param2&param3=a%3db"))
         },
         querystring: vec![
@@ -207,6 +212,7 @@ fn test_form_param() {
             host: "localhost".to_string(),
             port: Some(8000),
             path: "/form-params".to_string(),
+            querystring: None,
         }, // "http://localhost:8000/form-params".to_string(),
         querystring: vec![],
         headers: vec![http::core::Header {
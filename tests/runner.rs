@@ -24,6 +24,7 @@ fn test_hurl_file() {
     let client = http::client::Client::init(http::client::ClientOptions {
         noproxy_hosts: vec![],
         insecure: false,
+        follow_location: false,
     });
     let mut lines: Vec<&str> = regex::Regex::new(r"\n|\r\n")
         .unwrap()
@@ -37,7 +38,10 @@ fn test_hurl_file() {
         client,
         hurl_file,
         true,
+        None,
+        None,
         &mut variables,
+        &HashMap::new(),
         true,
         "current_dir".to_string(),
         filename.to_string(),
@@ -121,6 +125,7 @@ fn test_hello() {
     let client = http::client::Client::init(http::client::ClientOptions {
         noproxy_hosts: vec![],
         insecure: false,
+        follow_location: false,
     });
     let source_info = SourceInfo {
         start: Pos { line: 1, column: 1 },
@@ -132,7 +137,7 @@ fn test_hello() {
     };
     let request = hello_request();
     let hurl_file = ast::HurlFile {
-        entries: vec![ast::Entry {
+        statements: vec![ast::Statement::Entry(ast::Entry {
             request,
             response: Some(ast::Response {
                 line_terminators: vec![],
@@ -156,7 +161,7 @@ fn test_hello() {
                 body: None,
                 source_info: source_info.clone(),
             }),
-        }],
+        })],
         line_terminators: vec![],
     };
     let lines = vec![
@@ -167,7 +172,10 @@ fn test_hello() {
         client,
         hurl_file,
         false,
+        None,
+        None,
         &mut variables,
+        &HashMap::new(),
         true,
         "current_dir".to_string(),
         String::from("filename"),